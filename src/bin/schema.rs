@@ -1,4 +1,4 @@
-use did_contract::contract::sv::{ContractExecMsg, ContractQueryMsg, InstantiateMsg};
+use did_contract::contract::sv::{ContractExecMsg, ContractQueryMsg, InstantiateMsg, MigrateMsg};
 use sylvia::cw_schema::write_api;
 
 fn main() {
@@ -6,5 +6,6 @@ fn main() {
         instantiate: InstantiateMsg,
         execute: ContractExecMsg,
         query: ContractQueryMsg,
+        migrate: MigrateMsg,
     }
 }
\ No newline at end of file
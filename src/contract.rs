@@ -1,14 +1,80 @@
-use cosmwasm_std::{Deps, Event, Order, Response, StdError, StdResult, Storage};
-// use cw_storage_plus::Item;
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+use cosmwasm_std::{
+    to_json_vec, Addr, BankMsg, Binary, BlockInfo, Coin, Coins, Deps, DepsMut, Env, Event, MessageInfo, Order, Response, StdError, StdResult, Storage, Timestamp,
+};
 use crate::error::ContractError;
-use crate::multiset::MultiSet;
-use crate::state::{self, Controller, Controllers, Did, DidDocument, Service, ToEventData};
-use cw_storage_plus::{Bound, Map};
+use crate::migration::{self, LEGACY_CONTRACT_VERSION};
+use crate::did_syntax::{self, DidSyntaxError};
+use crate::signature::PublicKey;
+use crate::state::{
+    self, AuthorizationExplanation, AuthorizationPolicy, BlockStamp, Config, ConfigUpdate, ContractVersion, Deposit, ControlledDid, ControlledDidsCursor, ControlledDidsPage, Controller, Controllers, Did, DidDocument, DidDocumentMetadata, DidDocuments,
+    DidDereferencingError, DidDereferencingResult, DidDocumentRevision, DidOperation, DidResolutionError, DidResolutionResult, OperationKind, PauseScope, Proposal, Proposals, RelationshipMethod, Service, ServiceEndpoint, ServiceIndex, ServiceType, SignDoc, ToEventData,
+    uri_host, VerificationMethod, VerificationRelationship, VersionTime,
+};
+use cw_storage_plus::{Bound, Item, Map};
 use sylvia::contract;
-use sylvia::ctx::{ExecCtx, InstantiateCtx, QueryCtx};
+use sylvia::ctx::{ExecCtx, InstantiateCtx, MigrateCtx, QueryCtx};
+pub const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// Proposals expire after a week unless the proposer says otherwise
+const DEFAULT_PROPOSAL_TTL: u64 = 7 * 24 * 60 * 60;
+
 pub struct DidContract {
-    pub did_docs: Map<String, DidDocument>,
-    pub controllers: MultiSet, // TODO optimize indexing on controllers
+    pub contract_info: Item<ContractVersion>,
+    pub config: Item<Config>,
+    pub did_docs: DidDocuments,
+    pub did_metadata: Map<String, DidDocumentMetadata>,
+    pub did_history: Map<(String, u64), DidDocumentRevision>,
+    pub did_count: Item<u64>,
+    pub policies: Map<String, AuthorizationPolicy>,
+    pub proposals: Proposals,
+    pub proposal_seq: Item<u64>,
+    pub nonces: Map<String, u64>,
+    pub paused: Item<PauseScope>,
+    pub deposits: Map<String, Deposit>,
+}
+
+// Who an operation is applied on behalf of
+enum Authority {
+    // Senders or proposal approvals, checked against the controllers
+    Controllers(Vec<Controller>),
+    // A capability invocation key of the document whose signature was verified
+    VerificationMethod(Did),
+}
+
+// Funds attached to an exec, which pay for deposits. What is left is
+// returned to the sender.
+struct AttachedFunds {
+    sender: Addr,
+    coins: Coins,
+}
+
+impl AttachedFunds {
+    fn new(info: &MessageInfo) -> Result<Self, ContractError> {
+        Ok(AttachedFunds {
+            sender: info.sender.clone(),
+            coins: Coins::try_from(info.funds.as_slice())
+                .map_err(|e| StdError::generic_err(e.to_string()))?,
+        })
+    }
+
+    fn take(&mut self, did: &Did, coin: Coin) -> Result<(), ContractError> {
+        self.coins
+            .sub(coin.clone())
+            .map_err(|_| ContractError::InsufficientDeposit(did.to_string(), coin.to_string()))
+    }
+
+    fn refund(self, response: Response) -> Response {
+        if self.coins.is_empty() {
+            return response;
+        }
+        response.add_message(BankMsg::Send {
+            to_address: self.sender.to_string(),
+            amount: self.coins.into_vec(),
+        })
+    }
 }
 
 #[cfg(not(feature = "library"))]
@@ -26,22 +92,349 @@ use sylvia::entry_points;
 impl DidContract {
     pub const fn new() -> Self {
         Self {
-            did_docs: Map::new("dids"),
-            controllers: MultiSet::new("controllers"),
+            contract_info: Item::new("contract_info"),
+            config: Item::new("config"),
+            did_docs: state::did_documents(),
+            did_metadata: Map::new("did_metadata"),
+            did_history: Map::new("did_history"),
+            did_count: Item::new("did_count"),
+            policies: Map::new("policies"),
+            proposals: state::proposals(),
+            proposal_seq: Item::new("proposal_seq"),
+            nonces: Map::new("nonces"),
+            paused: Item::new("paused"),
+            deposits: Map::new("deposits"),
         }
     }
 
     #[sv::msg(instantiate)]
-    pub fn instantiate(&self, _ctx: InstantiateCtx) -> StdResult<Response> {
-        Ok(Response::default())
+    pub fn instantiate(
+        &self,
+        ctx: InstantiateCtx,
+        did_method: String,
+        admin: Option<String>,
+        config: Option<ConfigUpdate>,
+    ) -> Result<Response, ContractError> {
+        let admin = match admin {
+            Some(admin) => ctx.deps.api.addr_validate(&admin)?,
+            None => ctx.info.sender.clone(),
+        };
+        let update = config;
+        let mut config = Config::new(&did_method)?;
+        config.admin = Some(admin.clone());
+        config.update(update.unwrap_or_default())?;
+        self.config.save(ctx.deps.storage, &config)?;
+        self.did_count.save(ctx.deps.storage, &0)?;
+        self.contract_info.save(
+            ctx.deps.storage,
+            &ContractVersion {
+                contract: CONTRACT_NAME.to_string(),
+                version: CONTRACT_VERSION.to_string(),
+            },
+        )?;
+
+        let event = Event::new("instantiate")
+            .add_attribute("admin", admin)
+            .add_attribute("did_prefix", config.did_prefix)
+            .add_attribute(
+                "max_delegation_depth",
+                config.traversal_limits.max_delegation_depth.to_string(),
+            )
+            .add_attribute(
+                "max_visited_nodes",
+                config.traversal_limits.max_visited_nodes.to_string(),
+            );
+        Ok(Response::default().add_event(event))
+    }
+
+    // Instances without an admin, such as legacy ones, can get one here
+    #[sv::msg(migrate)]
+    pub fn migrate(&self, ctx: MigrateCtx, admin: Option<String>) -> Result<Response, ContractError> {
+        let stored = self
+            .contract_info
+            .may_load(ctx.deps.storage)?
+            .unwrap_or_else(|| ContractVersion {
+                contract: CONTRACT_NAME.to_string(),
+                version: LEGACY_CONTRACT_VERSION.to_string(),
+            });
+        if stored.contract != CONTRACT_NAME {
+            return Err(ContractError::MigrationContractMismatch(stored.contract));
+        }
+        let from = migration::parse_version(&stored.version)?;
+        let to = migration::parse_version(CONTRACT_VERSION)?;
+        if from > to {
+            return Err(ContractError::MigrationDowngrade(
+                stored.version,
+                CONTRACT_VERSION.to_string(),
+            ));
+        }
+
+        let applied = migration::run_migrations(self, ctx.deps.storage, &from, &to)?;
+        if let Some(admin) = &admin {
+            let admin = ctx.deps.api.addr_validate(admin)?;
+            self.config.update(ctx.deps.storage, |mut config| -> Result<_, ContractError> {
+                config.admin = Some(admin);
+                config.pending_admin = None;
+                Ok(config)
+            })?;
+        }
+
+        self.contract_info.save(
+            ctx.deps.storage,
+            &ContractVersion {
+                contract: CONTRACT_NAME.to_string(),
+                version: CONTRACT_VERSION.to_string(),
+            },
+        )?;
+
+        let mut event = Event::new("migrate")
+            .add_attribute("from_version", stored.version)
+            .add_attribute("to_version", CONTRACT_VERSION)
+            .add_attribute("migrations", applied.join(","));
+        if let Some(admin) = admin {
+            event = event.add_attribute("admin", admin);
+        }
+        Ok(Response::default().add_event(event))
+    }
+
+    #[sv::msg(query)]
+    pub fn get_config(&self, ctx: QueryCtx) -> Result<Config, ContractError> {
+        Ok(self.config.load(ctx.deps.storage)?)
+    }
+
+    // The deposit held for a document, if any
+    #[sv::msg(query)]
+    pub fn get_deposit(&self, ctx: QueryCtx, did: Did) -> Result<Option<Deposit>, ContractError> {
+        Ok(self.deposits.may_load(ctx.deps.storage, did.to_string())?)
+    }
+
+    // What is paused, if anything
+    #[sv::msg(query)]
+    pub fn get_pause_scope(&self, ctx: QueryCtx) -> Result<Option<PauseScope>, ContractError> {
+        Ok(self.paused.may_load(ctx.deps.storage)?)
     }
 
     #[sv::msg(query)]
     pub fn get_did_document(&self, ctx: QueryCtx, did: Did) -> Result<DidDocument, ContractError> {
-        did.ensure_valid()?;
+        let config = self.config.load(ctx.deps.storage)?;
+        did.ensure_valid(&config.did_prefix)?;
         self.get_did_doc(ctx.deps.storage, &did.to_string())
     }
 
+    #[sv::msg(query)]
+    pub fn resolve_did(&self, ctx: QueryCtx, did: Did) -> Result<DidResolutionResult, ContractError> {
+        let config = self.config.load(ctx.deps.storage)?;
+        if let Err(error) = resolution_did_check(&did, &config.did_prefix) {
+            return Ok(DidResolutionResult::error(error));
+        }
+        let Some(did_doc) = self.did_docs.may_load(ctx.deps.storage, did.to_string())? else {
+            return Ok(DidResolutionResult::error(DidResolutionError::NotFound));
+        };
+        let metadata = self
+            .did_metadata
+            .may_load(ctx.deps.storage, did.to_string())?
+            .unwrap_or_default();
+        Ok(DidResolutionResult::resolved(did_doc, metadata))
+    }
+
+    #[sv::msg(query)]
+    pub fn resolve_did_version(
+        &self,
+        ctx: QueryCtx,
+        did: Did,
+        version_id: u64,
+    ) -> Result<DidResolutionResult, ContractError> {
+        let config = self.config.load(ctx.deps.storage)?;
+        if let Err(error) = resolution_did_check(&did, &config.did_prefix) {
+            return Ok(DidResolutionResult::error(error));
+        }
+        let revision = self
+            .did_history
+            .may_load(ctx.deps.storage, (did.to_string(), version_id))?;
+        self.resolve_revision(ctx.deps.storage, revision)
+    }
+
+    #[sv::msg(query)]
+    pub fn resolve_did_at(
+        &self,
+        ctx: QueryCtx,
+        did: Did,
+        version_time: VersionTime,
+    ) -> Result<DidResolutionResult, ContractError> {
+        let config = self.config.load(ctx.deps.storage)?;
+        if let Err(error) = resolution_did_check(&did, &config.did_prefix) {
+            return Ok(DidResolutionResult::error(error));
+        }
+        // The latest revision written at or before the requested block
+        let revision = self
+            .did_history
+            .prefix(did.to_string())
+            .range(ctx.deps.storage, None, None, Order::Descending)
+            .map(|r| r.map(|(_, revision)| revision))
+            .find(|r| match r {
+                Ok(revision) => version_time.includes(&revision.stamp()),
+                Err(_) => true,
+            })
+            .transpose()?;
+        self.resolve_revision(ctx.deps.storage, revision)
+    }
+
+    // Dereferences a DID URL into the document, the service or verification
+    // method named by its fragment, or the URL selected by its `service` and
+    // `relativeRef` parameters
+    #[sv::msg(query)]
+    pub fn dereference(
+        &self,
+        ctx: QueryCtx,
+        did_url: Did,
+    ) -> Result<DidDereferencingResult, ContractError> {
+        let config = self.config.load(ctx.deps.storage)?;
+        let Ok(url) = did_syntax::parse_did_url(did_url.value()) else {
+            return Ok(DidDereferencingResult::error(DidDereferencingError::InvalidDidUrl));
+        };
+        if url.did.ensure_method(&config.did_prefix).is_err() {
+            return Ok(DidDereferencingResult::error(
+                DidDereferencingError::MethodNotSupported,
+            ));
+        }
+        let did = url.did_str(did_url.value());
+        let did_doc = match self.get_did_doc(ctx.deps.storage, did) {
+            Ok(did_doc) => did_doc,
+            Err(ContractError::DidDocumentNotFound(_)) => {
+                return Ok(DidDereferencingResult::error(DidDereferencingError::NotFound))
+            }
+            Err(e) => return Err(e),
+        };
+        let metadata = self
+            .did_metadata
+            .may_load(ctx.deps.storage, did.to_string())?
+            .unwrap_or_default();
+        Ok(match did_doc.dereference(&url) {
+            Ok(content) => DidDereferencingResult::dereferenced(content, metadata),
+            Err(error) => DidDereferencingResult::error(error),
+        })
+    }
+
+    #[sv::msg(query)]
+    pub fn get_did_history(
+        &self,
+        ctx: QueryCtx,
+        did: Did,
+        limit: Option<usize>,
+        start_after: Option<u64>,
+    ) -> Result<Vec<DidDocumentRevision>, ContractError> {
+        let config = self.config.load(ctx.deps.storage)?;
+        did.ensure_valid(&config.did_prefix)?;
+        let start = start_after.map(Bound::exclusive);
+        self.did_history
+            .prefix(did.to_string())
+            .range(ctx.deps.storage, start, None, Order::Ascending)
+            .take(config.pagination.limit(limit))
+            .map(|r| r.map(|(_, revision)| revision))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ContractError::DidDocumentError)
+    }
+
+    #[sv::msg(query)]
+    pub fn list_dids(
+        &self,
+        ctx: QueryCtx,
+        limit: Option<usize>,
+        start_after: Option<String>,
+        order: Option<Order>,
+    ) -> Result<Vec<Did>, ContractError> {
+        let res = self.list_dids_strings(ctx.deps, limit, start_after, order)?;
+        let dids = res.into_iter().map(|s| Did::new(&s)).collect();
+        Ok(dids)
+    }
+
+    #[sv::msg(query)]
+    pub fn list_did_documents(
+        &self,
+        ctx: QueryCtx,
+        limit: Option<usize>,
+        start_after: Option<String>,
+        order: Option<Order>,
+    ) -> Result<Vec<DidDocument>, ContractError> {
+        let config = self.config.load(ctx.deps.storage)?;
+        let order = order.unwrap_or(Order::Ascending);
+        let (min, max) = page_bounds(start_after, order);
+        self.did_docs
+            .range(ctx.deps.storage, min, max, order)
+            .take(config.pagination.limit(limit))
+            .map(|r| r.map(|(_, did_doc)| did_doc))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ContractError::DidDocumentError)
+    }
+
+    #[sv::msg(query)]
+    pub fn count_dids(&self, ctx: QueryCtx) -> Result<u64, ContractError> {
+        Ok(self.did_count.may_load(ctx.deps.storage)?.unwrap_or_default())
+    }
+
+    #[sv::msg(query)]
+    pub fn get_authorization_policy(
+        &self,
+        ctx: QueryCtx,
+        did: Did,
+    ) -> Result<Option<AuthorizationPolicy>, ContractError> {
+        let config = self.config.load(ctx.deps.storage)?;
+        did.ensure_valid(&config.did_prefix)?;
+        Ok(self.policies.may_load(ctx.deps.storage, did.to_string())?)
+    }
+
+    #[sv::msg(query)]
+    pub fn get_proposal(&self, ctx: QueryCtx, proposal_id: u64) -> Result<Proposal, ContractError> {
+        self.get_proposal_internal(ctx.deps.storage, proposal_id)
+    }
+
+    // Pending proposals on the document; expired ones are left out
+    #[sv::msg(query)]
+    pub fn list_proposals(
+        &self,
+        ctx: QueryCtx,
+        did: Did,
+        limit: Option<usize>,
+        start_after: Option<u64>,
+    ) -> Result<Vec<Proposal>, ContractError> {
+        let config = self.config.load(ctx.deps.storage)?;
+        did.ensure_valid(&config.did_prefix)?;
+        self.proposals
+            .idx
+            .did
+            .prefix(did.to_string())
+            .range(
+                ctx.deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .map(|item| item.map(|(_, proposal)| proposal).map_err(ContractError::from))
+            // Expired proposals can only be closed, so they are no longer pending
+            .filter(|item| !matches!(item, Ok(proposal) if proposal.is_expired(&ctx.env.block)))
+            .take(config.pagination.limit(limit))
+            .collect()
+    }
+
+    // The nonce the next signed operation on the document must carry
+    #[sv::msg(query)]
+    pub fn get_nonce(&self, ctx: QueryCtx, did: Did) -> Result<u64, ContractError> {
+        let config = self.config.load(ctx.deps.storage)?;
+        did.ensure_valid(&config.did_prefix)?;
+        Ok(self.nonces.may_load(ctx.deps.storage, did.to_string())?.unwrap_or_default())
+    }
+
+    // The bytes to sign for `execute_signed`
+    #[sv::msg(query)]
+    pub fn get_sign_bytes(
+        &self,
+        ctx: QueryCtx,
+        operation: DidOperation,
+        nonce: u64,
+    ) -> Result<Binary, ContractError> {
+        Ok(sign_bytes(&ctx.env, nonce, &operation)?.into())
+    }
+
     #[sv::msg(query)]
     pub fn is_did_controller(
         &self,
@@ -49,10 +442,77 @@ impl DidContract {
         did: Did,
         controller: Controller,
     ) -> Result<bool, ContractError> {
-        did.ensure_valid()?;
-        controller.ensure_valid(ctx.deps.api)?;
+        let config = self.config.load(ctx.deps.storage)?;
+        did.ensure_valid(&config.did_prefix)?;
+        controller.ensure_valid(ctx.deps.api, &config.did_prefix)?;
         let doc = self.get_did_doc(ctx.deps.storage, did.value())?;
-        doc.is_controlled_by(ctx.deps.storage, &self.did_docs, &controller)
+        doc.is_controlled_by(
+            ctx.deps.storage,
+            &self.did_docs,
+            &config.did_prefix,
+            &config.traversal_limits,
+            &controller,
+        )
+    }
+
+    // Whether the controller could change the document on its own, with the
+    // chain of DID controllers that grants control or the ones explored
+    #[sv::msg(query)]
+    pub fn explain_authorization(
+        &self,
+        ctx: QueryCtx,
+        did: Did,
+        controller: Controller,
+    ) -> Result<AuthorizationExplanation, ContractError> {
+        let config = self.config.load(ctx.deps.storage)?;
+        did.ensure_valid(&config.did_prefix)?;
+        controller.ensure_valid(ctx.deps.api, &config.did_prefix)?;
+        let did_doc = self.get_did_doc(ctx.deps.storage, did.value())?;
+
+        let mut already_checked = HashSet::new();
+        let path = did_doc.controller_path(
+            ctx.deps.storage,
+            &self.did_docs,
+            &config.did_prefix,
+            &config.traversal_limits,
+            &controller,
+            &mut already_checked,
+        );
+        let signers = [controller];
+        let policy = self.policies.may_load(ctx.deps.storage, did.to_string())?;
+        let result = did_doc.ensure_active().and_then(|_| {
+            did_doc.authorize(
+                ctx.deps.storage,
+                &self.did_docs,
+                &config.did_prefix,
+                &config.traversal_limits,
+                policy.as_ref(),
+                &signers,
+            )
+        });
+        if let Err(ContractError::DidDocumentError(e)) = result {
+            return Err(ContractError::DidDocumentError(e));
+        }
+
+        // Hitting a traversal limit leaves the explored frontier, the limit
+        // itself is reported as the reason
+        let (path, frontier) = match path {
+            Ok(Some(mut path)) => {
+                path.insert(0, did.to_string().into());
+                (path, vec![])
+            }
+            Ok(None) | Err(_) => {
+                let mut frontier: Vec<Did> = already_checked.iter().map(|d| Did::new(d)).collect();
+                frontier.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+                (vec![], frontier)
+            }
+        };
+        Ok(AuthorizationExplanation {
+            authorized: result.is_ok(),
+            path,
+            frontier,
+            reason: result.err().map(|e| e.to_string()),
+        })
     }
 
     #[sv::msg(query)]
@@ -62,11 +522,19 @@ impl DidContract {
         controllers: Vec<Controller>,
         controller: Controller,
     ) -> Result<bool, ContractError> {
-        controller.ensure_valid(ctx.deps.api)?;
+        let config = self.config.load(ctx.deps.storage)?;
+        controller.ensure_valid(ctx.deps.api, &config.did_prefix)?;
         for c in &controllers {
-            c.ensure_valid(ctx.deps.api)?;
+            c.ensure_valid(ctx.deps.api, &config.did_prefix)?;
         }
-        state::is_controller_of(ctx.deps.storage, &self.did_docs, &controllers, &controller)
+        state::is_controller_of(
+            ctx.deps.storage,
+            &self.did_docs,
+            &config.did_prefix,
+            &config.traversal_limits,
+            &controllers,
+            &controller,
+        )
     }
 
     #[sv::msg(query)]
@@ -75,13 +543,14 @@ impl DidContract {
         ctx: QueryCtx,
         controllers: Vec<Controller>,
     ) -> Result<bool, ContractError> {
+        let config = self.config.load(ctx.deps.storage)?;
         if controllers.is_empty() {
             return Err(ContractError::NoControllers);
         }
         for c in &controllers {
-            c.ensure_valid(ctx.deps.api)?;
+            c.ensure_valid(ctx.deps.api, &config.did_prefix)?;
         }
-        match controllers.ensure_exist(ctx.deps.storage, &self.did_docs) {
+        match controllers.ensure_exist(ctx.deps.storage, &self.did_docs, &config.did_prefix) {
             Ok(_) => Ok(true),
             Err(_) => Ok(false),
         }
@@ -93,8 +562,9 @@ impl DidContract {
         ctx: QueryCtx,
         controller: Controller,
     ) -> Result<bool, ContractError> {
-        controller.ensure_valid(ctx.deps.api)?;
-        match controller.ensure_exist(ctx.deps.storage, &self.did_docs) {
+        let config = self.config.load(ctx.deps.storage)?;
+        controller.ensure_valid(ctx.deps.api, &config.did_prefix)?;
+        match controller.ensure_exist(ctx.deps.storage, &self.did_docs, &config.did_prefix) {
             Ok(_) => Ok(true),
             Err(_) => Ok(false),
         }
@@ -118,13 +588,119 @@ impl DidContract {
         limit: Option<usize>,
         start_after: Option<String>,
     ) -> Result<Vec<Did>, ContractError> {
-        controller.ensure_valid(ctx.deps.api)?;
+        let config = self.config.load(ctx.deps.storage)?;
+        controller.ensure_valid(ctx.deps.api, &config.did_prefix)?;
         let res =
             self.get_controlled_dids_strings(ctx.deps, controller.value(), limit, start_after)?;
         let dids = res.into_iter().map(|s| Did::new(&s)).collect();
         Ok(dids)
     }
 
+    // Every DID the controller can act on directly or through DID
+    // controllers, in breadth-first order. DIDs reached at `max_depth` are not
+    // followed any further. `start_after` takes the `next` cursor of the
+    // previous page, which carries the state of the walk. A page looks into at
+    // most `max_visited_nodes` documents and may come back short, or empty,
+    // with a `next` cursor to continue from.
+    #[sv::msg(query)]
+    pub fn get_transitively_controlled_dids(
+        &self,
+        ctx: QueryCtx,
+        controller: Controller,
+        max_depth: Option<u32>,
+        limit: Option<usize>,
+        start_after: Option<String>,
+    ) -> Result<ControlledDidsPage, ContractError> {
+        let config = self.config.load(ctx.deps.storage)?;
+        controller.ensure_valid(ctx.deps.api, &config.did_prefix)?;
+        let limit = config.pagination.limit(limit);
+        let max_depth = max_depth.unwrap_or(u32::MAX);
+        let budget = config.traversal_limits.max_visited_nodes;
+
+        let cursor = match start_after {
+            Some(cursor) => ControlledDidsCursor::decode(&cursor)?,
+            None => ControlledDidsCursor::start(&controller.to_string()),
+        };
+        let mut queue = VecDeque::from(cursor.queue);
+        let mut after = cursor.after;
+        let mut visited: BTreeSet<String> = cursor.visited.into_iter().collect();
+
+        let mut dids = Vec::new();
+        let mut looked_into = 0u32;
+        while let Some((node, depth)) = queue.front().cloned() {
+            if looked_into == budget || dids.len() == limit {
+                break;
+            }
+            looked_into += 1;
+            let min = after.take().map(Bound::exclusive);
+            let controlled = self
+                .did_docs
+                .idx
+                .controller
+                .dids(ctx.deps.storage, &node, min, None, Order::Ascending);
+            for did in controlled {
+                let did = did?;
+                if !visited.insert(did.clone()) {
+                    continue;
+                }
+                dids.push(ControlledDid {
+                    did: Did::new(&did),
+                    depth: depth + 1,
+                });
+                if depth + 1 < max_depth {
+                    queue.push_back((did.clone(), depth + 1));
+                }
+                if dids.len() == limit {
+                    after = Some(did);
+                    break;
+                }
+            }
+            if after.is_none() {
+                queue.pop_front();
+            }
+        }
+
+        let next = if queue.is_empty() {
+            None
+        } else {
+            let cursor = ControlledDidsCursor {
+                queue: queue.into(),
+                after,
+                visited: visited.into_iter().collect(),
+            };
+            Some(cursor.encode()?)
+        };
+        Ok(ControlledDidsPage { dids, next })
+    }
+
+    // DIDs with a service of the given type
+    #[sv::msg(query)]
+    pub fn get_dids_by_service_type(
+        &self,
+        ctx: QueryCtx,
+        service_type: String,
+        limit: Option<usize>,
+        start_after: Option<String>,
+    ) -> Result<Vec<Did>, ContractError> {
+        let index = &self.did_docs.idx.service_type;
+        self.get_indexed_dids(ctx.deps, index, &service_type, limit, start_after)
+    }
+
+    // DIDs with a service endpoint on the given host, or on the host of the
+    // given URI
+    #[sv::msg(query)]
+    pub fn get_dids_by_service_endpoint(
+        &self,
+        ctx: QueryCtx,
+        host: String,
+        limit: Option<usize>,
+        start_after: Option<String>,
+    ) -> Result<Vec<Did>, ContractError> {
+        let host = uri_host(&host).unwrap_or_else(|| host.to_ascii_lowercase());
+        let index = &self.did_docs.idx.service_endpoint_host;
+        self.get_indexed_dids(ctx.deps, index, &host, limit, start_after)
+    }
+
     #[sv::msg(query)]
     pub fn get_controlled_did_documents(
         &self,
@@ -133,7 +709,8 @@ impl DidContract {
         limit: Option<usize>,
         start_after: Option<String>,
     ) -> Result<Vec<DidDocument>, ContractError> {
-        controller.ensure_valid(ctx.deps.api)?;
+        let config = self.config.load(ctx.deps.storage)?;
+        controller.ensure_valid(ctx.deps.api, &config.did_prefix)?;
         let res =
             self.get_controlled_dids_strings(ctx.deps, controller.value(), limit, start_after)?;
         let mut docs: Vec<DidDocument> = Vec::new();
@@ -141,81 +718,808 @@ impl DidContract {
             let doc = self.get_did_doc(ctx.deps.storage, did)?;
             docs.push(doc);
         }
-        Ok(docs)
+        Ok(docs)
+    }
+
+    #[sv::msg(exec)]
+    pub fn create_did_document(
+        &self,
+        ctx: ExecCtx,
+        did_doc: DidDocument,
+    ) -> Result<Response, ContractError> {
+        let operation = DidOperation::CreateDidDocument {
+            did_doc: Box::new(did_doc),
+        };
+        self.execute_as_sender(ctx, operation)
+    }
+
+    #[sv::msg(exec)]
+    pub fn update_did_document(
+        &self,
+        ctx: ExecCtx,
+        new_did_doc: DidDocument,
+    ) -> Result<Response, ContractError> {
+        let operation = DidOperation::UpdateDidDocument {
+            new_did_doc: Box::new(new_did_doc),
+        };
+        self.execute_as_sender(ctx, operation)
+    }
+
+    #[sv::msg(exec)]
+    pub fn add_controller(
+        &self,
+        ctx: ExecCtx,
+        did: Did,
+        controller: Controller,
+    ) -> Result<Response, ContractError> {
+        self.execute_as_sender(ctx, DidOperation::AddController { did, controller })
+    }
+
+    #[sv::msg(exec)]
+    pub fn delete_controller(
+        &self,
+        ctx: ExecCtx,
+        did: Did,
+        controller: Controller,
+    ) -> Result<Response, ContractError> {
+        self.execute_as_sender(ctx, DidOperation::DeleteController { did, controller })
+    }
+
+    #[sv::msg(exec)]
+    pub fn add_service(
+        &self,
+        ctx: ExecCtx,
+        did: Did,
+        service: Service,
+    ) -> Result<Response, ContractError> {
+        self.execute_as_sender(ctx, DidOperation::AddService { did, service })
+    }
+
+    #[sv::msg(exec)]
+    pub fn delete_service(
+        &self,
+        ctx: ExecCtx,
+        did: Did,
+        service_did: Did,
+    ) -> Result<Response, ContractError> {
+        self.execute_as_sender(ctx, DidOperation::DeleteService { did, service_did })
+    }
+
+    // Replaces the type and/or endpoint of a service, keeping its id
+    #[sv::msg(exec)]
+    pub fn update_service(
+        &self,
+        ctx: ExecCtx,
+        did: Did,
+        service_did: Did,
+        a_type: Option<ServiceType>,
+        service_endpoint: Option<ServiceEndpoint>,
+    ) -> Result<Response, ContractError> {
+        let operation = DidOperation::UpdateService {
+            did,
+            service_did,
+            a_type,
+            service_endpoint,
+        };
+        self.execute_as_sender(ctx, operation)
+    }
+
+    #[sv::msg(exec)]
+    pub fn add_verification_method(
+        &self,
+        ctx: ExecCtx,
+        did: Did,
+        verification_method: VerificationMethod,
+    ) -> Result<Response, ContractError> {
+        let operation = DidOperation::AddVerificationMethod {
+            did,
+            verification_method,
+        };
+        self.execute_as_sender(ctx, operation)
+    }
+
+    #[sv::msg(exec)]
+    pub fn delete_verification_method(
+        &self,
+        ctx: ExecCtx,
+        did: Did,
+        verification_method_did: Did,
+    ) -> Result<Response, ContractError> {
+        let operation = DidOperation::DeleteVerificationMethod {
+            did,
+            verification_method_did,
+        };
+        self.execute_as_sender(ctx, operation)
+    }
+
+    #[sv::msg(exec)]
+    pub fn add_verification_relationship(
+        &self,
+        ctx: ExecCtx,
+        did: Did,
+        relationship: VerificationRelationship,
+        method: RelationshipMethod,
+    ) -> Result<Response, ContractError> {
+        let operation = DidOperation::AddVerificationRelationship {
+            did,
+            relationship,
+            method,
+        };
+        self.execute_as_sender(ctx, operation)
+    }
+
+    #[sv::msg(exec)]
+    pub fn delete_verification_relationship(
+        &self,
+        ctx: ExecCtx,
+        did: Did,
+        relationship: VerificationRelationship,
+        method_did: Did,
+    ) -> Result<Response, ContractError> {
+        let operation = DidOperation::DeleteVerificationRelationship {
+            did,
+            relationship,
+            method_did,
+        };
+        self.execute_as_sender(ctx, operation)
+    }
+
+    // Every depositor gets their share of the deposit back; the sender's
+    // goes to `refund_address` when one is given
+    #[sv::msg(exec)]
+    pub fn delete_did_document(
+        &self,
+        ctx: ExecCtx,
+        did: Did,
+        refund_address: Option<String>,
+    ) -> Result<Response, ContractError> {
+        self.execute_as_sender(ctx, DidOperation::DeleteDidDocument { did, refund_address })
+    }
+
+    #[sv::msg(exec)]
+    pub fn deactivate_did_document(
+        &self,
+        ctx: ExecCtx,
+        did: Did,
+    ) -> Result<Response, ContractError> {
+        self.execute_as_sender(ctx, DidOperation::DeactivateDidDocument { did })
+    }
+
+    #[sv::msg(exec)]
+    pub fn set_authorization_policy(
+        &self,
+        ctx: ExecCtx,
+        did: Did,
+        policy: Option<AuthorizationPolicy>,
+    ) -> Result<Response, ContractError> {
+        self.execute_as_sender(ctx, DidOperation::SetAuthorizationPolicy { did, policy })
+    }
+
+    #[sv::msg(exec)]
+    pub fn propose(
+        &self,
+        ctx: ExecCtx,
+        operation: DidOperation,
+        expires: Option<Timestamp>,
+    ) -> Result<Response, ContractError> {
+        self.ensure_not_paused(ctx.deps.storage, Some(operation.kind()))?;
+        let config = self.config.load(ctx.deps.storage)?;
+        let did = operation.did().clone();
+        did.ensure_valid(&config.did_prefix)?;
+        let did_doc = self.get_active_did_doc(ctx.deps.storage, did.value())?;
+        let proposer: Controller = ctx.info.sender.to_string().into();
+        self.ensure_approver(ctx.deps.storage, &config, &did_doc, &proposer)?;
+
+        let expires =
+            expires.unwrap_or_else(|| ctx.env.block.time.plus_seconds(DEFAULT_PROPOSAL_TTL));
+        if expires <= ctx.env.block.time {
+            return Err(ContractError::InvalidProposalExpiration);
+        }
+
+        let id = self
+            .proposal_seq
+            .may_load(ctx.deps.storage)?
+            .unwrap_or_default()
+            + 1;
+        self.proposal_seq.save(ctx.deps.storage, &id)?;
+        let proposal = Proposal {
+            id,
+            did,
+            operation,
+            proposer: proposer.clone(),
+            approvals: vec![proposer],
+            expires,
+        };
+        self.proposals.save(ctx.deps.storage, id, &proposal)?;
+
+        let event = Event::new("propose")
+            .add_attribute("proposal_id", id.to_string())
+            .add_attribute("did", proposal.did.to_string())
+            .add_attribute("proposer", proposal.proposer.to_string())
+            .add_attribute("expires", proposal.expires.to_string());
+        Ok(Response::default().add_event(event))
+    }
+
+    #[sv::msg(exec)]
+    pub fn approve_proposal(
+        &self,
+        ctx: ExecCtx,
+        proposal_id: u64,
+    ) -> Result<Response, ContractError> {
+        let config = self.config.load(ctx.deps.storage)?;
+        let mut proposal = self.get_pending_proposal(ctx.deps.storage, &ctx.env, proposal_id)?;
+        self.ensure_not_paused(ctx.deps.storage, Some(proposal.operation.kind()))?;
+        let did_doc = self.get_active_did_doc(ctx.deps.storage, proposal.did.value())?;
+        let approver: Controller = ctx.info.sender.to_string().into();
+        self.ensure_approver(ctx.deps.storage, &config, &did_doc, &approver)?;
+        if proposal.approvals.contains(&approver) {
+            return Err(ContractError::ProposalAlreadyApproved(approver.to_string()));
+        }
+
+        proposal.approvals.push(approver.clone());
+        self.proposals.save(ctx.deps.storage, proposal_id, &proposal)?;
+
+        let event = Event::new("approve_proposal")
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("did", proposal.did.to_string())
+            .add_attribute("approver", approver.to_string());
+        Ok(Response::default().add_event(event))
+    }
+
+    // Anyone may execute a proposal; the approvals are checked against the
+    // authorization policy of the document at execution time
+    #[sv::msg(exec)]
+    pub fn execute_proposal(
+        &self,
+        ctx: ExecCtx,
+        proposal_id: u64,
+    ) -> Result<Response, ContractError> {
+        let proposal = self.get_pending_proposal(ctx.deps.storage, &ctx.env, proposal_id)?;
+        self.proposals.remove(ctx.deps.storage, proposal_id)?;
+
+        let authority = Authority::Controllers(proposal.approvals);
+        let mut funds = AttachedFunds::new(&ctx.info)?;
+        let response =
+            self.execute_operation(ctx.deps, &ctx.env, &authority, &mut funds, proposal.operation)?;
+
+        let event = Event::new("execute_proposal")
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("did", proposal.did.to_string());
+        Ok(funds.refund(response.add_event(event)))
+    }
+
+    // Removes an expired proposal
+    #[sv::msg(exec)]
+    pub fn close_proposal(
+        &self,
+        ctx: ExecCtx,
+        proposal_id: u64,
+    ) -> Result<Response, ContractError> {
+        self.ensure_not_paused(ctx.deps.storage, None)?;
+        let proposal = self.get_proposal_internal(ctx.deps.storage, proposal_id)?;
+        if !proposal.is_expired(&ctx.env.block) {
+            return Err(ContractError::ProposalNotExpired(proposal_id));
+        }
+        self.proposals.remove(ctx.deps.storage, proposal_id)?;
+
+        let event = Event::new("close_proposal")
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("did", proposal.did.to_string());
+        Ok(Response::default().add_event(event))
+    }
+
+    // Applies an operation signed off-chain with a capability invocation
+    // key of the document, so a relayer can pay for it
+    #[sv::msg(exec)]
+    pub fn execute_signed(
+        &self,
+        ctx: ExecCtx,
+        operation: DidOperation,
+        verification_method: Did,
+        nonce: u64,
+        signature: Binary,
+    ) -> Result<Response, ContractError> {
+        let config = self.config.load(ctx.deps.storage)?;
+        let did = operation.did().clone();
+        did.ensure_valid(&config.did_prefix)?;
+        let verification_method = verification_method.to_absolute(&did);
+        verification_method.ensure_valid_url(&config.did_prefix)?;
+        let did_doc = self.get_active_did_doc(ctx.deps.storage, did.value())?;
+
+        let expected = self.nonces.may_load(ctx.deps.storage, did.to_string())?.unwrap_or_default();
+        if nonce != expected {
+            return Err(ContractError::InvalidNonce(did.to_string(), expected, nonce));
+        }
+
+        let method = did_doc
+            .capability_invocation_method(&verification_method)
+            .ok_or_else(|| ContractError::VerificationMethodNotAuthorized(verification_method.to_string()))?;
+        let public_key = PublicKey::from_verification_method(method)?;
+        let message = sign_bytes(&ctx.env, nonce, &operation)?;
+        if !public_key.verify(ctx.deps.api, &message, &signature)? {
+            return Err(ContractError::InvalidSignature(verification_method.to_string()));
+        }
+        // Nonces outlive deleted documents so spent signatures stay spent
+        self.nonces.save(ctx.deps.storage, did.to_string(), &(nonce + 1))?;
+
+        let relayer = ctx.info.sender.to_string();
+        let authority = Authority::VerificationMethod(verification_method.clone());
+        let mut funds = AttachedFunds::new(&ctx.info)?;
+        let response = self.execute_operation(ctx.deps, &ctx.env, &authority, &mut funds, operation)?;
+
+        let event = Event::new("execute_signed")
+            .add_attribute("did", did.to_string())
+            .add_attribute("verification_method", verification_method.to_string())
+            .add_attribute("nonce", nonce.to_string())
+            .add_attribute("relayer", relayer);
+        Ok(funds.refund(response.add_event(event)))
+    }
+
+    // Applies the operations in order on behalf of the sender. Any failure
+    // reverts the whole batch.
+    #[sv::msg(exec)]
+    pub fn batch(
+        &self,
+        mut ctx: ExecCtx,
+        operations: Vec<DidOperation>,
+    ) -> Result<Response, ContractError> {
+        self.ensure_not_paused(ctx.deps.storage, None)?;
+        if operations.is_empty() {
+            return Err(ContractError::EmptyBatch);
+        }
+        let sender: Controller = ctx.info.sender.to_string().into();
+        let authority = Authority::Controllers(vec![sender]);
+        let mut funds = AttachedFunds::new(&ctx.info)?;
+
+        let mut response = Response::default();
+        let count = operations.len();
+        for (index, operation) in operations.into_iter().enumerate() {
+            let operation_response = self
+                .execute_operation(ctx.deps.branch(), &ctx.env, &authority, &mut funds, operation)
+                .map_err(|e| ContractError::BatchOperationFailed(index, Box::new(e)))?;
+            response = response
+                .add_submessages(operation_response.messages)
+                .add_events(operation_response.events);
+        }
+
+        let event = Event::new("batch").add_attribute("operations", count.to_string());
+        Ok(funds.refund(response.add_event(event)))
+    }
+
+    #[sv::msg(exec)]
+    pub fn update_config(&self, ctx: ExecCtx, update: ConfigUpdate) -> Result<Response, ContractError> {
+        let mut config = self.config.load(ctx.deps.storage)?;
+        config.ensure_admin(&ctx.info.sender)?;
+        config.update(update)?;
+        self.config.save(ctx.deps.storage, &config)?;
+
+        let event = Event::new("update_config")
+            .add_attribute(
+                "max_delegation_depth",
+                config.traversal_limits.max_delegation_depth.to_string(),
+            )
+            .add_attribute(
+                "max_visited_nodes",
+                config.traversal_limits.max_visited_nodes.to_string(),
+            )
+            .add_attribute(
+                "max_controllers",
+                config.document_limits.max_controllers.to_string(),
+            )
+            .add_attribute("max_services", config.document_limits.max_services.to_string())
+            .add_attribute(
+                "max_verification_methods",
+                config.document_limits.max_verification_methods.to_string(),
+            )
+            .add_attribute("max_id_length", config.document_limits.max_id_length.to_string())
+            .add_attribute(
+                "max_type_length",
+                config.document_limits.max_type_length.to_string(),
+            )
+            .add_attribute(
+                "max_endpoint_length",
+                config.document_limits.max_endpoint_length.to_string(),
+            )
+            .add_attribute(
+                "max_document_size",
+                config.document_limits.max_document_size.to_string(),
+            )
+            .add_attribute("default_limit", config.pagination.default_limit.to_string())
+            .add_attribute("max_limit", config.pagination.max_limit.to_string());
+        let event = match &config.deposit {
+            Some(deposit) => event
+                .add_attribute("deposit_denom", &deposit.denom)
+                .add_attribute("deposit_amount", deposit.amount.to_string())
+                .add_attribute("deposit_per_byte", deposit.per_byte.to_string()),
+            None => event,
+        };
+        Ok(Response::default().add_event(event))
+    }
+
+    // Proposes a new admin, who takes over on `accept_admin`. A later
+    // proposal replaces an earlier one.
+    #[sv::msg(exec)]
+    pub fn transfer_admin(&self, ctx: ExecCtx, new_admin: String) -> Result<Response, ContractError> {
+        let mut config = self.config.load(ctx.deps.storage)?;
+        config.ensure_admin(&ctx.info.sender)?;
+        let new_admin = ctx.deps.api.addr_validate(&new_admin)?;
+        config.pending_admin = Some(new_admin.clone());
+        self.config.save(ctx.deps.storage, &config)?;
+
+        let event = Event::new("transfer_admin")
+            .add_attribute("admin", ctx.info.sender)
+            .add_attribute("pending_admin", new_admin);
+        Ok(Response::default().add_event(event))
+    }
+
+    #[sv::msg(exec)]
+    pub fn accept_admin(&self, ctx: ExecCtx) -> Result<Response, ContractError> {
+        let mut config = self.config.load(ctx.deps.storage)?;
+        let Some(pending_admin) = config.pending_admin.take() else {
+            return Err(ContractError::NoPendingAdmin);
+        };
+        if pending_admin != ctx.info.sender {
+            return Err(ContractError::Unauthorized(ctx.info.sender.to_string()));
+        }
+        let old_admin = config.admin.replace(pending_admin.clone());
+        self.config.save(ctx.deps.storage, &config)?;
+
+        let mut event = Event::new("accept_admin").add_attribute("new_admin", pending_admin);
+        if let Some(old_admin) = old_admin {
+            event = event.add_attribute("old_admin", old_admin);
+        }
+        Ok(Response::default().add_event(event))
+    }
+
+    // Stops every write, or with `operations` only those of the given kinds.
+    // Pausing again replaces the scope. Admin messages keep working.
+    #[sv::msg(exec)]
+    pub fn pause(
+        &self,
+        ctx: ExecCtx,
+        operations: Option<Vec<OperationKind>>,
+    ) -> Result<Response, ContractError> {
+        let config = self.config.load(ctx.deps.storage)?;
+        config.ensure_admin(&ctx.info.sender)?;
+        let scope = match operations {
+            Some(operations) if operations.is_empty() => return Err(ContractError::EmptyPause),
+            Some(operations) => PauseScope::Operations(operations),
+            None => PauseScope::All,
+        };
+        self.paused.save(ctx.deps.storage, &scope)?;
+
+        let event = Event::new("pause").add_attribute("operations", scope.to_event_data());
+        Ok(Response::default().add_event(event))
+    }
+
+    #[sv::msg(exec)]
+    pub fn unpause(&self, ctx: ExecCtx) -> Result<Response, ContractError> {
+        let config = self.config.load(ctx.deps.storage)?;
+        config.ensure_admin(&ctx.info.sender)?;
+        let scope = self.paused.may_load(ctx.deps.storage)?.ok_or(ContractError::NotPaused)?;
+        self.paused.remove(ctx.deps.storage);
+
+        let event = Event::new("unpause").add_attribute("operations", scope.to_event_data());
+        Ok(Response::default().add_event(event))
+    }
+
+    // Leaves the configuration fixed for good
+    #[sv::msg(exec)]
+    pub fn renounce_admin(&self, ctx: ExecCtx) -> Result<Response, ContractError> {
+        let mut config = self.config.load(ctx.deps.storage)?;
+        config.ensure_admin(&ctx.info.sender)?;
+        config.admin = None;
+        config.pending_admin = None;
+        self.config.save(ctx.deps.storage, &config)?;
+
+        let event = Event::new("renounce_admin").add_attribute("admin", ctx.info.sender);
+        Ok(Response::default().add_event(event))
+    }
+
+    fn execute_as_sender(
+        &self,
+        ctx: ExecCtx,
+        operation: DidOperation,
+    ) -> Result<Response, ContractError> {
+        let sender: Controller = ctx.info.sender.to_string().into();
+        let mut funds = AttachedFunds::new(&ctx.info)?;
+        let response = self.execute_operation(
+            ctx.deps,
+            &ctx.env,
+            &Authority::Controllers(vec![sender]),
+            &mut funds,
+            operation,
+        )?;
+        Ok(funds.refund(response))
+    }
+
+    // Applies an operation on behalf of `authority`, which must be allowed
+    // to change the document
+    fn execute_operation(
+        &self,
+        mut deps: DepsMut,
+        env: &Env,
+        authority: &Authority,
+        funds: &mut AttachedFunds,
+        operation: DidOperation,
+    ) -> Result<Response, ContractError> {
+        self.ensure_not_paused(deps.storage, Some(operation.kind()))?;
+        let did = operation.did().clone();
+        let refund_address = match &operation {
+            DidOperation::DeleteDidDocument {
+                refund_address: Some(refund_address),
+                ..
+            } => Some(deps.api.addr_validate(refund_address)?),
+            _ => None,
+        };
+        let response = match operation {
+            DidOperation::CreateDidDocument { did_doc } => {
+                self.apply_create_did_document(deps.branch(), env, authority, *did_doc)
+            }
+            DidOperation::UpdateDidDocument { new_did_doc } => {
+                self.apply_update_did_document(deps.branch(), env, authority, *new_did_doc)
+            }
+            DidOperation::AddController { did, controller } => {
+                self.apply_add_controller(deps.branch(), env, authority, did, controller)
+            }
+            DidOperation::DeleteController { did, controller } => {
+                self.apply_delete_controller(deps.branch(), env, authority, did, controller)
+            }
+            DidOperation::AddService { did, service } => {
+                self.apply_add_service(deps.branch(), env, authority, did, service)
+            }
+            DidOperation::DeleteService { did, service_did } => {
+                self.apply_delete_service(deps.branch(), env, authority, did, service_did)
+            }
+            DidOperation::UpdateService {
+                did,
+                service_did,
+                a_type,
+                service_endpoint,
+            } => self.apply_update_service(
+                deps.branch(),
+                env,
+                authority,
+                did,
+                service_did,
+                a_type,
+                service_endpoint,
+            ),
+            DidOperation::AddVerificationMethod {
+                did,
+                verification_method,
+            } => self.apply_add_verification_method(
+                deps.branch(),
+                env,
+                authority,
+                did,
+                verification_method,
+            ),
+            DidOperation::DeleteVerificationMethod {
+                did,
+                verification_method_did,
+            } => self.apply_delete_verification_method(
+                deps.branch(),
+                env,
+                authority,
+                did,
+                verification_method_did,
+            ),
+            DidOperation::AddVerificationRelationship {
+                did,
+                relationship,
+                method,
+            } => self.apply_add_verification_relationship(
+                deps.branch(),
+                env,
+                authority,
+                did,
+                relationship,
+                method,
+            ),
+            DidOperation::DeleteVerificationRelationship {
+                did,
+                relationship,
+                method_did,
+            } => self.apply_delete_verification_relationship(
+                deps.branch(),
+                env,
+                authority,
+                did,
+                relationship,
+                method_did,
+            ),
+            DidOperation::DeactivateDidDocument { did } => {
+                self.apply_deactivate_did_document(deps.branch(), env, authority, did)
+            }
+            DidOperation::DeleteDidDocument { did, .. } => {
+                self.apply_delete_did_document(deps.branch(), env, authority, did)
+            }
+            DidOperation::SetAuthorizationPolicy { did, policy } => {
+                self.apply_set_authorization_policy(deps.branch(), authority, did, policy)
+            }
+        }?;
+
+        let deposit_response = self.settle_deposit(deps.storage, &did, funds, refund_address)?;
+        Ok(response
+            .add_submessages(deposit_response.messages)
+            .add_events(deposit_response.events))
+    }
+
+    // Brings the deposit held for `did` in line with the configured deposit
+    // for its stored document. Shortfalls are taken from `funds` and
+    // recorded as paid by the sender, surplus is sent back to the depositors
+    // who paid it. Deactivated and deleted documents need no deposit; on
+    // delete the sender's share may go to `refund_address` instead.
+    fn settle_deposit(
+        &self,
+        storage: &mut dyn Storage,
+        did: &Did,
+        funds: &mut AttachedFunds,
+        refund_address: Option<Addr>,
+    ) -> Result<Response, ContractError> {
+        let config = self.config.load(storage)?;
+        let held = self.deposits.may_load(storage, did.to_string())?;
+        let required = match (&config.deposit, self.did_docs.may_load(storage, did.to_string())?) {
+            (Some(deposit), Some(did_doc)) if !did_doc.deactivated => deposit.required(&did_doc)?,
+            _ => None,
+        };
+
+        let mut released = vec![];
+        let mut deposit = match (held, &required) {
+            (None, None) => return Ok(Response::default()),
+            (None, Some(required)) => Deposit::new(&required.denom),
+            // A deposit in a denom no longer required is returned in full
+            (Some(mut held), Some(required)) if held.amount.denom != required.denom => {
+                let amount = held.amount.amount;
+                released.extend(held.release(amount));
+                Deposit::new(&required.denom)
+            }
+            (Some(held), _) => held,
+        };
+        let target = required.map(|r| r.amount).unwrap_or_default();
+        let current = deposit.amount.amount;
+        if target == current && released.is_empty() {
+            return Ok(Response::default());
+        }
+        if target > current {
+            funds.take(did, Coin::new(target - current, &deposit.amount.denom))?;
+            deposit.add(&funds.sender, target - current);
+        } else {
+            released.extend(deposit.release(current - target));
+        }
+
+        let amount = if deposit.amount.amount.is_zero() {
+            self.deposits.remove(storage, did.to_string());
+            "0".to_string()
+        } else {
+            self.deposits.save(storage, did.to_string(), &deposit)?;
+            deposit.amount.to_string()
+        };
+        let event = Event::new("deposit")
+            .add_attribute("did", did.to_string())
+            .add_attribute("amount", amount);
+        let refunds = released.into_iter().map(|(depositor, coin)| {
+            let to_address = match &refund_address {
+                Some(refund_address) if depositor == funds.sender => refund_address,
+                _ => &depositor,
+            };
+            BankMsg::Send {
+                to_address: to_address.to_string(),
+                amount: vec![coin],
+            }
+        });
+        Ok(Response::default().add_messages(refunds).add_event(event))
     }
 
-    #[sv::msg(exec)]
-    pub fn create_did_document(
+    fn apply_create_did_document(
         &self,
-        ctx: ExecCtx,
-        did_doc: DidDocument,
+        deps: DepsMut,
+        env: &Env,
+        authority: &Authority,
+        mut did_doc: DidDocument,
     ) -> Result<Response, ContractError> {
-        did_doc.ensure_valid(ctx.deps.api)?;
+        let config = self.config.load(deps.storage)?;
+        did_doc.normalize_ids();
+        did_doc.ensure_valid(deps.api, &config.did_prefix)?;
+        did_doc.ensure_ids_owned()?;
         did_doc.ensure_controllers_not_duplicated()?;
         did_doc.ensure_services_not_duplicated()?;
-        if self
+        did_doc.ensure_verification_methods_not_duplicated()?;
+        did_doc.ensure_verification_relationships_consistent()?;
+        did_doc.ensure_active()?;
+        if let Some(existing) = self
             .did_docs
-            .has(ctx.deps.storage, did_doc.id.value().to_string())
+            .may_load(deps.storage, did_doc.id.value().to_string())?
         {
+            existing.ensure_active()?;
             return Err(ContractError::DidDocumentAlreadyExists(
                 did_doc.id.value().to_string(),
             ));
         }
+        // Nothing to authorize against yet, the creators become the
+        // controllers unless the document names its own
+        let Authority::Controllers(creators) = authority else {
+            return Err(ContractError::Unauthorized(did_doc.id.to_string()));
+        };
         let mut new_doc = did_doc;
         if !new_doc.has_any_controller() {
-            // new_doc.controller.mut_controllers().push(Controller::new(&ctx.info.sender.to_string()));
-            new_doc
-                .controller
-                .push(Controller::new(&ctx.info.sender.to_string()));
+            new_doc.controller.extend(creators.iter().cloned());
         }
         new_doc.ensure_not_self_controlled()?;
-        new_doc.ensure_controllers_exist(ctx.deps.storage, &self.did_docs)?;
-        new_doc.ensure_signability(ctx.deps.storage, &self.did_docs)?;
-
-        self.did_docs
-            .save(ctx.deps.storage, new_doc.id.to_string(), &new_doc)
-            .map_err(|e| ContractError::DidDocumentError(e))?;
+        new_doc.ensure_within_limits(&config.document_limits)?;
+        new_doc.ensure_controllers_exist(deps.storage, &self.did_docs, &config.did_prefix)?;
+        new_doc.ensure_signability(
+            deps.storage,
+            &self.did_docs,
+            &config.did_prefix,
+            &config.traversal_limits,
+        )?;
+        self.save_did_doc(deps.storage, &env.block, &new_doc)?;
+        self.ensure_traversal_limits(deps.storage, &config, &new_doc)?;
 
-        self.index_controllers(ctx.deps.storage, &new_doc)?;
         let mut response = Response::default();
 
         let mut event = Event::new("create_did_document")
             .add_attribute("did", new_doc.id.to_string())
             .add_attribute("controllers", new_doc.controller.to_event_data());
 
-        if new_doc.service.len() > 0 {
+        if !new_doc.service.is_empty() {
             event = event.add_attribute("services", new_doc.service.to_event_data());
         }
 
+        if !new_doc.verification_method.is_empty() {
+            event = event.add_attribute(
+                "verification_methods",
+                new_doc.verification_method.to_event_data(),
+            );
+        }
+
+        for relationship in VerificationRelationship::ALL.iter() {
+            let methods = new_doc.relationship(relationship);
+            if !methods.is_empty() {
+                event = event.add_attribute(relationship.as_str(), methods.to_event_data());
+            }
+        }
+
         response = response.add_event(event);
 
         Ok(response)
     }
 
-    #[sv::msg(exec)]
-    pub fn update_did_document(
+    fn apply_update_did_document(
         &self,
-        ctx: ExecCtx,
-        new_did_doc: DidDocument,
+        deps: DepsMut,
+        env: &Env,
+        authority: &Authority,
+        mut new_did_doc: DidDocument,
     ) -> Result<Response, ContractError> {
-        new_did_doc.ensure_valid(ctx.deps.api)?;
+        let config = self.config.load(deps.storage)?;
+        new_did_doc.normalize_ids();
+        new_did_doc.ensure_valid(deps.api, &config.did_prefix)?;
+        new_did_doc.ensure_ids_owned()?;
         new_did_doc.ensure_controller()?;
         new_did_doc.ensure_controllers_not_duplicated()?;
         new_did_doc.ensure_services_not_duplicated()?;
+        new_did_doc.ensure_verification_methods_not_duplicated()?;
+        new_did_doc.ensure_verification_relationships_consistent()?;
         new_did_doc.ensure_not_self_controlled()?;
-        let did_doc = self.get_did_doc(ctx.deps.storage, new_did_doc.id.value())?;
-        let sender: Controller = ctx.info.sender.to_string().into();
-        did_doc.authorize(ctx.deps.storage, &self.did_docs, &sender)?;
-
-        new_did_doc.ensure_controllers_exist(ctx.deps.storage, &self.did_docs)?;
-        new_did_doc.ensure_signability(ctx.deps.storage, &self.did_docs)?; // TODO maybe optimoze by joining with ensure_controllers_exist
-
-        self.did_docs
-            .save(ctx.deps.storage, new_did_doc.id.to_string(), &new_did_doc)
-            .map_err(|e| ContractError::DidDocumentError(e))?;
-
-        self.unindex_controllers(ctx.deps.storage, &did_doc);
-        self.index_controllers(ctx.deps.storage, &new_did_doc)?;
+        new_did_doc.ensure_active()?;
+        new_did_doc.ensure_within_limits(&config.document_limits)?;
+        let did_doc = self.get_active_did_doc(deps.storage, new_did_doc.id.value())?;
+        self.authorize(deps.storage, &config, &did_doc, authority)?;
+
+        new_did_doc.ensure_controllers_exist(deps.storage, &self.did_docs, &config.did_prefix)?;
+        new_did_doc.ensure_signability(
+            deps.storage,
+            &self.did_docs,
+            &config.did_prefix,
+            &config.traversal_limits,
+        )?; // TODO maybe optimoze by joining with ensure_controllers_exist
+        self.save_did_doc(deps.storage, &env.block, &new_did_doc)?;
+        if !new_did_doc.has_same_controllers(&did_doc) {
+            self.ensure_traversal_limits(deps.storage, &config, &new_did_doc)?;
+        }
 
         let mut response = Response::default();
 
@@ -224,29 +1528,58 @@ impl DidContract {
             .add_attribute("old_controllers", did_doc.controller.to_event_data())
             .add_attribute("new_controllers", new_did_doc.controller.to_event_data());
 
-        if did_doc.service.len() > 0 {
+        if !did_doc.service.is_empty() {
             event = event.add_attribute("old_services", did_doc.service.to_event_data());
         }
-        if new_did_doc.service.len() > 0 {
+        if !new_did_doc.service.is_empty() {
             event = event.add_attribute("new_services", new_did_doc.service.to_event_data());
         }
+        if !did_doc.verification_method.is_empty() {
+            event = event.add_attribute(
+                "old_verification_methods",
+                did_doc.verification_method.to_event_data(),
+            );
+        }
+        if !new_did_doc.verification_method.is_empty() {
+            event = event.add_attribute(
+                "new_verification_methods",
+                new_did_doc.verification_method.to_event_data(),
+            );
+        }
+        for relationship in VerificationRelationship::ALL.iter() {
+            let old_methods = did_doc.relationship(relationship);
+            if !old_methods.is_empty() {
+                event = event.add_attribute(
+                    format!("old_{}", relationship),
+                    old_methods.to_event_data(),
+                );
+            }
+            let new_methods = new_did_doc.relationship(relationship);
+            if !new_methods.is_empty() {
+                event = event.add_attribute(
+                    format!("new_{}", relationship),
+                    new_methods.to_event_data(),
+                );
+            }
+        }
 
         response = response.add_event(event);
         Ok(response)
     }
 
-    #[sv::msg(exec)]
-    pub fn add_controller(
+    fn apply_add_controller(
         &self,
-        ctx: ExecCtx,
+        deps: DepsMut,
+        env: &Env,
+        authority: &Authority,
         did: Did,
         controller: Controller,
     ) -> Result<Response, ContractError> {
-        did.ensure_valid()?;
-        controller.ensure_valid(ctx.deps.api)?;
-        let mut did_doc: DidDocument = self.get_did_doc(ctx.deps.storage, did.value())?;
-        let sender: Controller = ctx.info.sender.to_string().into(); // Get sender's address as a string
-        did_doc.authorize(ctx.deps.storage, &self.did_docs, &sender)?;
+        let config = self.config.load(deps.storage)?;
+        did.ensure_valid(&config.did_prefix)?;
+        controller.ensure_valid(deps.api, &config.did_prefix)?;
+        let mut did_doc: DidDocument = self.get_active_did_doc(deps.storage, did.value())?;
+        self.authorize(deps.storage, &config, &did_doc, authority)?;
 
         if did_doc.has_controller(&controller) {
             return Err(ContractError::DidDocumentControllerAlreadyExists(
@@ -256,14 +1589,11 @@ impl DidContract {
 
         did_doc.controller.push(controller.clone());
         did_doc.ensure_not_self_controlled()?;
+        did_doc.ensure_within_limits(&config.document_limits)?;
 
-        controller.ensure_exist(ctx.deps.storage, &self.did_docs)?;
-
-        self.did_docs
-            .save(ctx.deps.storage, did_doc.id.to_string(), &did_doc)
-            .map_err(|e| ContractError::DidDocumentError(e))?;
-
-        self.index_controller(ctx.deps.storage, &did, &controller)?;
+        controller.ensure_exist(deps.storage, &self.did_docs, &config.did_prefix)?;
+        self.save_did_doc(deps.storage, &env.block, &did_doc)?;
+        self.ensure_traversal_limits(deps.storage, &config, &did_doc)?;
 
         let mut response = Response::default();
 
@@ -274,18 +1604,19 @@ impl DidContract {
         Ok(response)
     }
 
-    #[sv::msg(exec)]
-    pub fn delete_controller(
+    fn apply_delete_controller(
         &self,
-        ctx: ExecCtx,
+        deps: DepsMut,
+        env: &Env,
+        authority: &Authority,
         did: Did,
         controller: Controller,
     ) -> Result<Response, ContractError> {
-        did.ensure_valid()?;
-        controller.ensure_valid(ctx.deps.api)?;
-        let mut did_doc: DidDocument = self.get_did_doc(ctx.deps.storage, did.value())?;
-        let sender: Controller = ctx.info.sender.to_string().into(); // Get sender's address as a string
-        did_doc.authorize(ctx.deps.storage, &self.did_docs, &sender)?;
+        let config = self.config.load(deps.storage)?;
+        did.ensure_valid(&config.did_prefix)?;
+        controller.ensure_valid(deps.api, &config.did_prefix)?;
+        let mut did_doc: DidDocument = self.get_active_did_doc(deps.storage, did.value())?;
+        self.authorize(deps.storage, &config, &did_doc, authority)?;
 
         if !did_doc.has_controller(&controller) {
             return Err(ContractError::DidDocumentControllerNotExists(
@@ -296,13 +1627,14 @@ impl DidContract {
         // did_doc.controller.mut_controllers().retain(|s| *s != controller);
         did_doc.controller.retain(|s| *s != controller);
         did_doc.ensure_controller()?;
-        did_doc.ensure_signability(ctx.deps.storage, &self.did_docs)?;
-
-        self.did_docs
-            .save(ctx.deps.storage, did_doc.id.to_string(), &did_doc)
-            .map_err(|e| ContractError::DidDocumentError(e))?;
+        did_doc.ensure_signability(
+            deps.storage,
+            &self.did_docs,
+            &config.did_prefix,
+            &config.traversal_limits,
+        )?;
 
-        self.unindex_controller(ctx.deps.storage, &did, &controller);
+        self.save_did_doc(deps.storage, &env.block, &did_doc)?;
 
         let mut response = Response::default();
 
@@ -313,19 +1645,22 @@ impl DidContract {
         Ok(response)
     }
 
-    #[sv::msg(exec)]
-    pub fn add_service(
+    fn apply_add_service(
         &self,
-        ctx: ExecCtx,
+        deps: DepsMut,
+        env: &Env,
+        authority: &Authority,
         did: Did,
-        service: Service,
+        mut service: Service,
     ) -> Result<Response, ContractError> {
-        did.ensure_valid()?;
-        service.ensure_valid()?;
-        let mut did_doc: DidDocument = self.get_did_doc(ctx.deps.storage, did.value())?;
+        let config = self.config.load(deps.storage)?;
+        did.ensure_valid(&config.did_prefix)?;
+        service.id = service.id.to_absolute(&did);
+        service.ensure_valid(&config.did_prefix)?;
+        service.id.ensure_url_of(&did)?;
+        let mut did_doc: DidDocument = self.get_active_did_doc(deps.storage, did.value())?;
 
-        let sender: Controller = ctx.info.sender.to_string().into();
-        did_doc.authorize(ctx.deps.storage, &self.did_docs, &sender)?;
+        self.authorize(deps.storage, &config, &did_doc, authority)?;
 
         if did_doc.has_service(&service.id) {
             return Err(ContractError::DidDocumentServiceAlreadyExists(
@@ -335,9 +1670,8 @@ impl DidContract {
 
         did_doc.service.push(service.clone());
 
-        self.did_docs
-            .save(ctx.deps.storage, did_doc.id.to_string(), &did_doc)
-            .map_err(|e| ContractError::DidDocumentError(e))?;
+        did_doc.ensure_within_limits(&config.document_limits)?;
+        self.save_did_doc(deps.storage, &env.block, &did_doc)?;
 
         let mut response = Response::default();
 
@@ -348,19 +1682,21 @@ impl DidContract {
         Ok(response)
     }
 
-    #[sv::msg(exec)]
-    pub fn delete_service(
+    fn apply_delete_service(
         &self,
-        ctx: ExecCtx,
+        deps: DepsMut,
+        env: &Env,
+        authority: &Authority,
         did: Did,
         service_did: Did,
     ) -> Result<Response, ContractError> {
-        did.ensure_valid()?;
-        service_did.ensure_valid()?;
-        let mut did_doc: DidDocument = self.get_did_doc(ctx.deps.storage, did.value())?;
+        let config = self.config.load(deps.storage)?;
+        did.ensure_valid(&config.did_prefix)?;
+        let service_did = service_did.to_absolute(&did);
+        service_did.ensure_valid_url(&config.did_prefix)?;
+        let mut did_doc: DidDocument = self.get_active_did_doc(deps.storage, did.value())?;
 
-        let sender: Controller = ctx.info.sender.to_string().into();
-        did_doc.authorize(ctx.deps.storage, &self.did_docs, &sender)?;
+        self.authorize(deps.storage, &config, &did_doc, authority)?;
 
         if !did_doc.has_service(&service_did) {
             return Err(ContractError::DidDocumentServiceNotExists(
@@ -370,9 +1706,7 @@ impl DidContract {
 
         did_doc.service.retain(|s| s.id != service_did);
 
-        self.did_docs
-            .save(ctx.deps.storage, did_doc.id.to_string(), &did_doc)
-            .map_err(|e| ContractError::DidDocumentError(e))?;
+        self.save_did_doc(deps.storage, &env.block, &did_doc)?;
 
         let mut response = Response::default();
 
@@ -383,19 +1717,248 @@ impl DidContract {
         Ok(response)
     }
 
-    #[sv::msg(exec)]
-    pub fn delete_did_document(&self, ctx: ExecCtx, did: Did) -> Result<Response, ContractError> {
-        did.ensure_valid()?;
-        let did_doc: DidDocument = self.get_did_doc(ctx.deps.storage, did.value())?;
-        if !self.controllers.is_empty(ctx.deps.storage, did.value()) {
+    #[allow(clippy::too_many_arguments)]
+    fn apply_update_service(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        authority: &Authority,
+        did: Did,
+        service_did: Did,
+        a_type: Option<ServiceType>,
+        service_endpoint: Option<ServiceEndpoint>,
+    ) -> Result<Response, ContractError> {
+        let config = self.config.load(deps.storage)?;
+        did.ensure_valid(&config.did_prefix)?;
+        let service_did = service_did.to_absolute(&did);
+        service_did.ensure_valid_url(&config.did_prefix)?;
+        if a_type.is_none() && service_endpoint.is_none() {
+            return Err(ContractError::EmptyServiceUpdate(service_did.to_string()));
+        }
+        let mut did_doc: DidDocument = self.get_active_did_doc(deps.storage, did.value())?;
+
+        self.authorize(deps.storage, &config, &did_doc, authority)?;
+
+        let Some(service) = did_doc.service.iter_mut().find(|s| s.id == service_did) else {
+            return Err(ContractError::DidDocumentServiceNotExists(
+                service_did.to_string(),
+            ));
+        };
+        let old_service = service.clone();
+        if let Some(a_type) = a_type {
+            service.a_type = a_type;
+        }
+        if let Some(service_endpoint) = service_endpoint {
+            service.service_endpoint = service_endpoint;
+        }
+        service.ensure_valid(&config.did_prefix)?;
+        let new_service = service.clone();
+
+        did_doc.ensure_within_limits(&config.document_limits)?;
+        self.save_did_doc(deps.storage, &env.block, &did_doc)?;
+
+        let event = Event::new("update_service")
+            .add_attribute("did", did.to_string())
+            .add_attribute("service", service_did.to_string())
+            .add_attribute("old_type", old_service.a_type.to_string())
+            .add_attribute("new_type", new_service.a_type.to_string())
+            .add_attribute("old_service_endpoint", old_service.service_endpoint.to_string())
+            .add_attribute("new_service_endpoint", new_service.service_endpoint.to_string());
+        Ok(Response::default().add_event(event))
+    }
+
+    fn apply_add_verification_method(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        authority: &Authority,
+        did: Did,
+        mut verification_method: VerificationMethod,
+    ) -> Result<Response, ContractError> {
+        let config = self.config.load(deps.storage)?;
+        did.ensure_valid(&config.did_prefix)?;
+        verification_method.id = verification_method.id.to_absolute(&did);
+        verification_method.ensure_valid(&config.did_prefix)?;
+        verification_method.id.ensure_url_of(&did)?;
+        let mut did_doc: DidDocument = self.get_active_did_doc(deps.storage, did.value())?;
+
+        self.authorize(deps.storage, &config, &did_doc, authority)?;
+
+        if did_doc.has_verification_method(&verification_method.id) {
+            return Err(ContractError::DidDocumentVerificationMethodAlreadyExists(
+                verification_method.id.to_string(),
+            ));
+        }
+
+        did_doc.verification_method.push(verification_method.clone());
+        did_doc.ensure_verification_methods_not_duplicated()?;
+
+        did_doc.ensure_within_limits(&config.document_limits)?;
+        self.save_did_doc(deps.storage, &env.block, &did_doc)?;
+
+        let mut response = Response::default();
+
+        let event = Event::new("add_verification_method")
+            .add_attribute("did", did.to_string())
+            .add_attribute("new_verification_method", verification_method.id.to_string());
+        response = response.add_event(event);
+        Ok(response)
+    }
+
+    fn apply_delete_verification_method(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        authority: &Authority,
+        did: Did,
+        verification_method_did: Did,
+    ) -> Result<Response, ContractError> {
+        let config = self.config.load(deps.storage)?;
+        did.ensure_valid(&config.did_prefix)?;
+        let verification_method_did = verification_method_did.to_absolute(&did);
+        verification_method_did.ensure_valid_url(&config.did_prefix)?;
+        let mut did_doc: DidDocument = self.get_active_did_doc(deps.storage, did.value())?;
+
+        self.authorize(deps.storage, &config, &did_doc, authority)?;
+
+        if !did_doc.has_verification_method(&verification_method_did) {
+            return Err(ContractError::DidDocumentVerificationMethodNotExists(
+                verification_method_did.to_string(),
+            ));
+        }
+
+        if did_doc.is_verification_method_referenced(&verification_method_did) {
+            return Err(ContractError::VerificationMethodReferenced(
+                verification_method_did.to_string(),
+            ));
+        }
+
+        did_doc
+            .verification_method
+            .retain(|m| m.id != verification_method_did);
+
+        self.save_did_doc(deps.storage, &env.block, &did_doc)?;
+
+        let mut response = Response::default();
+
+        let event = Event::new("delete_verification_method")
+            .add_attribute("did", did.to_string())
+            .add_attribute("old_verification_method", verification_method_did);
+        response = response.add_event(event);
+        Ok(response)
+    }
+
+    fn apply_add_verification_relationship(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        authority: &Authority,
+        did: Did,
+        relationship: VerificationRelationship,
+        mut method: RelationshipMethod,
+    ) -> Result<Response, ContractError> {
+        let config = self.config.load(deps.storage)?;
+        did.ensure_valid(&config.did_prefix)?;
+        method.normalize_id(&did);
+        method.ensure_valid(&config.did_prefix)?;
+        if let RelationshipMethod::Embedded(embedded) = &method {
+            embedded.id.ensure_url_of(&did)?;
+        }
+        let mut did_doc: DidDocument = self.get_active_did_doc(deps.storage, did.value())?;
+
+        self.authorize(deps.storage, &config, &did_doc, authority)?;
+
+        if did_doc.has_relationship_method(&relationship, method.id()) {
+            return Err(
+                ContractError::DidDocumentVerificationRelationshipMethodAlreadyExists(
+                    method.id().to_string(),
+                ),
+            );
+        }
+
+        did_doc.relationship_mut(&relationship).push(method.clone());
+        did_doc.ensure_verification_methods_not_duplicated()?;
+        did_doc.ensure_verification_relationships_consistent()?;
+
+        did_doc.ensure_within_limits(&config.document_limits)?;
+        self.save_did_doc(deps.storage, &env.block, &did_doc)?;
+
+        let mut response = Response::default();
+
+        let event = Event::new("add_verification_relationship")
+            .add_attribute("did", did.to_string())
+            .add_attribute("relationship", relationship.as_str())
+            .add_attribute("new_method", method.id().to_string());
+        response = response.add_event(event);
+        Ok(response)
+    }
+
+    fn apply_delete_verification_relationship(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        authority: &Authority,
+        did: Did,
+        relationship: VerificationRelationship,
+        method_did: Did,
+    ) -> Result<Response, ContractError> {
+        let config = self.config.load(deps.storage)?;
+        did.ensure_valid(&config.did_prefix)?;
+        let method_did = method_did.to_absolute(&did);
+        method_did.ensure_valid_url(&config.did_prefix)?;
+        let mut did_doc: DidDocument = self.get_active_did_doc(deps.storage, did.value())?;
+
+        self.authorize(deps.storage, &config, &did_doc, authority)?;
+
+        if !did_doc.has_relationship_method(&relationship, &method_did) {
+            return Err(
+                ContractError::DidDocumentVerificationRelationshipMethodNotExists(
+                    method_did.to_string(),
+                ),
+            );
+        }
+
+        did_doc
+            .relationship_mut(&relationship)
+            .retain(|m| m.id() != &method_did);
+
+        self.save_did_doc(deps.storage, &env.block, &did_doc)?;
+
+        let mut response = Response::default();
+
+        let event = Event::new("delete_verification_relationship")
+            .add_attribute("did", did.to_string())
+            .add_attribute("relationship", relationship.as_str())
+            .add_attribute("old_method", method_did);
+        response = response.add_event(event);
+        Ok(response)
+    }
+
+    fn apply_delete_did_document(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        authority: &Authority,
+        did: Did,
+    ) -> Result<Response, ContractError> {
+        let config = self.config.load(deps.storage)?;
+        did.ensure_valid(&config.did_prefix)?;
+        let did_doc: DidDocument = self.get_active_did_doc(deps.storage, did.value())?;
+        if !self.did_docs.idx.controller.is_empty(deps.storage, did.value()) {
             return Err(ContractError::DidDocumentIsController(did.to_string()));
         }
-        let sender: Controller = ctx.info.sender.to_string().into(); // Get sender's address as a string
 
-        did_doc.authorize(ctx.deps.storage, &self.did_docs, &sender)?;
+        self.authorize(deps.storage, &config, &did_doc, authority)?;
 
-        self.did_docs.remove(ctx.deps.storage, did.to_string());
-        self.unindex_controllers(ctx.deps.storage, &did_doc);
+        self.did_docs
+            .remove(deps.storage, did.to_string())
+            .map_err(ContractError::DidDocumentError)?;
+        self.record_deletion(deps.storage, &env.block, &did)?;
+        self.did_metadata.remove(deps.storage, did.to_string());
+        self.did_count
+            .update(deps.storage, |count| -> StdResult<_> { Ok(count.saturating_sub(1)) })?;
+        self.policies.remove(deps.storage, did.to_string());
+        self.clear_proposals(deps.storage, did.value())?;
         let mut response = Response::default();
 
         let event = Event::new("delete_did_document").add_attribute("did", did.to_string());
@@ -403,55 +1966,352 @@ impl DidContract {
         Ok(response)
     }
 
-    fn get_did_doc(&self, store: &dyn Storage, did: &str) -> Result<DidDocument, ContractError> {
-        self.did_docs.load(store, did.into()).map_err(|e| match e {
-            StdError::NotFound { .. } => ContractError::DidDocumentNotFound(e),
-            _ => ContractError::DidDocumentError(e),
-        })
+    fn apply_deactivate_did_document(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        authority: &Authority,
+        did: Did,
+    ) -> Result<Response, ContractError> {
+        let config = self.config.load(deps.storage)?;
+        did.ensure_valid(&config.did_prefix)?;
+        let mut did_doc: DidDocument = self.get_active_did_doc(deps.storage, did.value())?;
+        if !self.did_docs.idx.controller.is_empty(deps.storage, did.value()) {
+            return Err(ContractError::DidDocumentIsController(did.to_string()));
+        }
+        self.authorize(deps.storage, &config, &did_doc, authority)?;
+
+        // The document is kept as a tombstone so the DID cannot be created again
+        did_doc.deactivated = true;
+        self.save_did_doc(deps.storage, &env.block, &did_doc)?;
+        self.clear_proposals(deps.storage, did.value())?;
+
+        let event = Event::new("deactivate_did_document").add_attribute("did", did.to_string());
+        Ok(Response::default().add_event(event))
     }
 
-    fn index_controllers(
+    fn apply_set_authorization_policy(
         &self,
-        store: &mut dyn Storage,
+        deps: DepsMut,
+        authority: &Authority,
+        did: Did,
+        policy: Option<AuthorizationPolicy>,
+    ) -> Result<Response, ContractError> {
+        let config = self.config.load(deps.storage)?;
+        did.ensure_valid(&config.did_prefix)?;
+        let did_doc: DidDocument = self.get_active_did_doc(deps.storage, did.value())?;
+        self.authorize(deps.storage, &config, &did_doc, authority)?;
+
+        let mut event = Event::new("set_authorization_policy").add_attribute("did", did.to_string());
+        match policy {
+            Some(policy) => {
+                policy.ensure_valid(deps.api, &config.did_prefix, &did_doc)?;
+                self.policies.save(deps.storage, did.to_string(), &policy)?;
+                event = event.add_attribute("threshold", policy.threshold.to_string());
+            }
+            None => self.policies.remove(deps.storage, did.to_string()),
+        }
+        Ok(Response::default().add_event(event))
+    }
+
+    fn authorize(
+        &self,
+        store: &dyn Storage,
+        config: &Config,
+        did_doc: &DidDocument,
+        authority: &Authority,
+    ) -> Result<(), ContractError> {
+        let policy = self.policies.may_load(store, did_doc.id.to_string())?;
+        match authority {
+            Authority::Controllers(signers) => {
+                did_doc.authorize(
+                    store,
+                    &self.did_docs,
+                    &config.did_prefix,
+                    &config.traversal_limits,
+                    policy.as_ref(),
+                    signers,
+                )
+            }
+            Authority::VerificationMethod(method_did) => {
+                // A single key cannot bypass a threshold policy
+                if policy.is_some() || did_doc.capability_invocation_method(method_did).is_none() {
+                    return Err(ContractError::VerificationMethodNotAuthorized(
+                        method_did.to_string(),
+                    ));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    // Operations of no particular kind only stop when everything is paused
+    fn ensure_not_paused(
+        &self,
+        store: &dyn Storage,
+        kind: Option<OperationKind>,
+    ) -> Result<(), ContractError> {
+        match self.paused.may_load(store)? {
+            Some(scope) => scope.ensure_allows(kind),
+            None => Ok(()),
+        }
+    }
+
+    // Keeps the controller graph within the traversal limits once the
+    // controllers of `did_doc` change. Documents it controls reach its
+    // controllers through it, so each of them is walked again against the
+    // stored graph; this runs after `did_doc` is saved and a failure reverts
+    // the write. How many documents sit below `did_doc` is not limited, since
+    // anyone may name it as a controller; only the walks up from each of them
+    // are.
+    fn ensure_traversal_limits(
+        &self,
+        store: &dyn Storage,
+        config: &Config,
+        did_doc: &DidDocument,
+    ) -> Result<(), ContractError> {
+        let limits = &config.traversal_limits;
+        did_doc.delegation_depth(store, &self.did_docs, &config.did_prefix, limits)?;
+        let mut visited = HashSet::from([did_doc.id.to_string()]);
+        let mut queue = VecDeque::from([did_doc.id.to_string()]);
+        while let Some(did) = queue.pop_front() {
+            for controlled in self.did_docs.idx.controller.dids(store, &did, None, None, Order::Ascending) {
+                let controlled = controlled?;
+                if !visited.insert(controlled.clone()) {
+                    continue;
+                }
+                let controlled_doc = self.get_did_doc(store, &controlled)?;
+                controlled_doc.delegation_depth(store, &self.did_docs, &config.did_prefix, limits)?;
+                queue.push_back(controlled);
+            }
+        }
+        Ok(())
+    }
+
+    // Approvers must carry weight under the policy, or be a controller if
+    // the document has none
+    fn ensure_approver(
+        &self,
+        store: &dyn Storage,
+        config: &Config,
         did_doc: &DidDocument,
+        approver: &Controller,
     ) -> Result<(), ContractError> {
-        // for c in did_doc.controller.controllers() {
-        for c in &did_doc.controller {
-            let r = self
-                .controllers
-                .save(store, &c.to_string(), &did_doc.id.to_string());
-            if let Err(e) = r {
-                return Err(ContractError::DidDocumentError(e));
+        let approvers = [approver.clone()];
+        let allowed = match self.policies.may_load(store, did_doc.id.to_string())? {
+            Some(policy) => {
+                policy.approved_weight(
+                    store,
+                    &self.did_docs,
+                    &config.did_prefix,
+                    &config.traversal_limits,
+                    did_doc,
+                    &approvers,
+                )? > 0
             }
+            None => did_doc.is_controlled_by(
+                store,
+                &self.did_docs,
+                &config.did_prefix,
+                &config.traversal_limits,
+                approver,
+            )?,
+        };
+        if !allowed {
+            return Err(ContractError::Unauthorized(approver.to_string()));
         }
         Ok(())
     }
 
-    fn unindex_controllers(&self, store: &mut dyn Storage, did_doc: &DidDocument) {
-        // for c in did_doc.controller.controllers() {
-        for c in &did_doc.controller {
-            self.controllers
-                .remove(store, &c.to_string(), &did_doc.id.to_string());
+    fn get_proposal_internal(&self, store: &dyn Storage, proposal_id: u64) -> Result<Proposal, ContractError> {
+        self.proposals
+            .may_load(store, proposal_id)?
+            .ok_or(ContractError::ProposalNotFound(proposal_id))
+    }
+
+    fn get_pending_proposal(
+        &self,
+        store: &dyn Storage,
+        env: &Env,
+        proposal_id: u64,
+    ) -> Result<Proposal, ContractError> {
+        let proposal = self.get_proposal_internal(store, proposal_id)?;
+        if proposal.is_expired(&env.block) {
+            return Err(ContractError::ProposalExpired(proposal_id));
+        }
+        Ok(proposal)
+    }
+
+    // Drops the authorization state of a document that is gone
+    fn clear_proposals(&self, store: &mut dyn Storage, did: &str) -> Result<(), ContractError> {
+        let ids = self
+            .proposals
+            .idx
+            .did
+            .prefix(did.to_string())
+            .keys(store, None, None, Order::Ascending)
+            .collect::<Result<Vec<_>, _>>()?;
+        for id in ids {
+            self.proposals.remove(store, id)?;
+        }
+        Ok(())
+    }
+
+    // Saves the document and bumps its metadata for the current block
+    fn save_did_doc(
+        &self,
+        store: &mut dyn Storage,
+        block: &BlockInfo,
+        did_doc: &DidDocument,
+    ) -> Result<(), ContractError> {
+        let did = did_doc.id.to_string();
+        // A document must never lose the controllers its policy relies on
+        if let Some(policy) = self.policies.may_load(store, did.clone())? {
+            if !did_doc.deactivated {
+                policy.ensure_satisfiable(did_doc)?;
+            }
+        }
+        // A DID created again after a delete continues its version numbering
+        let mut metadata = match self.did_metadata.may_load(store, did.clone())? {
+            Some(metadata) => metadata,
+            None => DidDocumentMetadata {
+                version_id: self.last_version(store, &did)?,
+                ..Default::default()
+            },
+        };
+        if self.did_docs.has(store, did.clone()) {
+            metadata.updated = Some(BlockStamp::from(block));
+        } else {
+            metadata.created = Some(BlockStamp::from(block));
+            self.did_count
+                .update(store, |count| -> StdResult<_> { Ok(count + 1) })?;
         }
+        metadata.version_id = Some(metadata.version_id.unwrap_or_default() + 1);
+        metadata.deactivated = did_doc.deactivated;
+
+        self.did_docs
+            .save(store, did.clone(), did_doc)
+            .map_err(ContractError::DidDocumentError)?;
+        self.did_metadata.save(store, did.clone(), &metadata)?;
+        self.did_history.save(
+            store,
+            (did, metadata.version_id.unwrap_or_default()),
+            &DidDocumentRevision {
+                did_document: did_doc.clone(),
+                did_document_metadata: metadata,
+            },
+        )?;
+        Ok(())
     }
 
-    fn index_controller(
+    // Closes the history of a deleted document with a tombstone revision, so
+    // earlier revisions stay resolvable and the deletion itself is on record
+    fn record_deletion(
         &self,
         store: &mut dyn Storage,
+        block: &BlockInfo,
         did: &Did,
-        controller: &Controller,
     ) -> Result<(), ContractError> {
-        self
-            .controllers
-            .save(store, &controller.to_string(), &did.to_string()).map_err(|e| {
-                ContractError::DidDocumentError(e)
-            })
+        let mut metadata = self.did_metadata.may_load(store, did.to_string())?.unwrap_or_default();
+        metadata.updated = Some(BlockStamp::from(block));
+        metadata.version_id = Some(metadata.version_id.unwrap_or_default() + 1);
+        metadata.deactivated = true;
+        metadata.deleted = true;
+        let tombstone = DidDocument {
+            id: did.clone(),
+            deactivated: true,
+            ..Default::default()
+        };
+        self.did_history.save(
+            store,
+            (did.to_string(), metadata.version_id.unwrap_or_default()),
+            &DidDocumentRevision {
+                did_document: tombstone,
+                did_document_metadata: metadata,
+            },
+        )?;
+        Ok(())
+    }
+
+    fn last_version(&self, store: &dyn Storage, did: &str) -> Result<Option<u64>, ContractError> {
+        Ok(self
+            .did_history
+            .prefix(did.to_string())
+            .keys(store, None, None, Order::Descending)
+            .next()
+            .transpose()?)
+    }
+
+    // Fills `next_update` from the revision that superseded the resolved one
+    fn resolve_revision(
+        &self,
+        store: &dyn Storage,
+        revision: Option<DidDocumentRevision>,
+    ) -> Result<DidResolutionResult, ContractError> {
+        let Some(revision) = revision else {
+            return Ok(DidResolutionResult::error(DidResolutionError::NotFound));
+        };
+        let mut metadata = revision.did_document_metadata;
+        let version_id = metadata.version_id.unwrap_or_default();
+        let next = self.did_history.may_load(
+            store,
+            (revision.did_document.id.to_string(), version_id + 1),
+        )?;
+        metadata.next_update = next.map(|r| r.stamp());
+        Ok(DidResolutionResult::resolved(revision.did_document, metadata))
+    }
+
+    fn get_did_doc(&self, store: &dyn Storage, did: &str) -> Result<DidDocument, ContractError> {
+        self.did_docs.load(store, did.into()).map_err(|e| match e {
+            StdError::NotFound { .. } => ContractError::DidDocumentNotFound(e),
+            _ => ContractError::DidDocumentError(e),
+        })
+    }
+
+    // Loads a document that can still be modified
+    fn get_active_did_doc(
+        &self,
+        store: &dyn Storage,
+        did: &str,
+    ) -> Result<DidDocument, ContractError> {
+        let did_doc = self.get_did_doc(store, did)?;
+        did_doc.ensure_active()?;
+        Ok(did_doc)
+    }
 
+    fn list_dids_strings(
+        &self,
+        deps: Deps,
+        limit: Option<usize>,
+        start_after: Option<String>,
+        order: Option<Order>,
+    ) -> Result<Vec<String>, ContractError> {
+        let config = self.config.load(deps.storage)?;
+        let order = order.unwrap_or(Order::Ascending);
+        let (min, max) = page_bounds(start_after, order);
+        self.did_docs
+            .keys(deps.storage, min, max, order)
+            .take(config.pagination.limit(limit))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ContractError::DidDocumentError)
     }
 
-    fn unindex_controller(&self, store: &mut dyn Storage, did: &Did, controller: &Controller) {
-        self.controllers
-            .remove(store, &controller.to_string(), &did.to_string());
+    fn get_indexed_dids(
+        &self,
+        deps: Deps,
+        index: &ServiceIndex,
+        key: &str,
+        limit: Option<usize>,
+        start_after: Option<String>,
+    ) -> Result<Vec<Did>, ContractError> {
+        let config = self.config.load(deps.storage)?;
+        let start = start_after.map(Bound::exclusive);
+        index
+            .dids(deps.storage, key, start, None, Order::Ascending)
+            .take(config.pagination.limit(limit))
+            .map(|r| r.map(|did| Did::new(&did)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ContractError::DidDocumentError)
     }
 
     fn get_controlled_dids_strings(
@@ -461,23 +2321,15 @@ impl DidContract {
         limit: Option<usize>,
         start_after: Option<String>,
     ) -> Result<Vec<String>, ContractError> {
-        const DEFAULT_LIMIT: usize = 50;
-        const MAX_LIMIT: usize = 200;
-        let mut limit = limit.unwrap_or(DEFAULT_LIMIT);
-        limit = {
-            if limit == 0 {
-                DEFAULT_LIMIT
-            } else {
-                limit
-            }
-        }
-        .min(MAX_LIMIT) as usize;
-
+        let config = self.config.load(deps.storage)?;
+        let limit = config.pagination.limit(limit);
         let start = start_after.map(Bound::exclusive);
 
         let res: Result<Vec<_>, _> = self
-            .controllers
-            .get_values(deps.storage, controller, start, None, Order::Ascending)
+            .did_docs
+            .idx
+            .controller
+            .dids(deps.storage, controller, start, None, Order::Ascending)
             .take(limit)
             .collect();
         match res {
@@ -489,3 +2341,34 @@ impl DidContract {
         }
     }
 }
+
+fn sign_bytes(env: &Env, nonce: u64, operation: &DidOperation) -> StdResult<Vec<u8>> {
+    to_json_vec(&SignDoc {
+        chain_id: env.block.chain_id.clone(),
+        contract: env.contract.address.to_string(),
+        nonce,
+        operation: operation.clone(),
+    })
+}
+
+// `start_after` bounds the page from below or above depending on the order
+fn page_bounds<'a>(
+    start_after: Option<String>,
+    order: Order,
+) -> (Option<Bound<'a, String>>, Option<Bound<'a, String>>) {
+    let start = start_after.map(Bound::exclusive);
+    match order {
+        Order::Ascending => (start, None),
+        Order::Descending => (None, start),
+    }
+}
+
+// Maps DID syntax errors to DID resolution error codes
+fn resolution_did_check(did: &Did, did_prefix: &str) -> Result<(), DidResolutionError> {
+    did_syntax::parse_did(did.value())
+        .and_then(|d| d.ensure_method(did_prefix))
+        .map_err(|e| match e {
+            DidSyntaxError::UnsupportedMethod(_) => DidResolutionError::MethodNotSupported,
+            _ => DidResolutionError::InvalidDid,
+        })
+}
@@ -0,0 +1,390 @@
+// DID and DID URL syntax as defined in W3C DID Core, section 3.
+//
+// did                = "did:" method-name ":" method-specific-id
+// method-name        = 1*method-char
+// method-char        = %x61-7A / DIGIT
+// method-specific-id = *( *idchar ":" ) 1*idchar
+// idchar             = ALPHA / DIGIT / "." / "-" / "_" / pct-encoded
+// did-url            = did path-abempty [ "?" query ] [ "#" fragment ]
+
+use thiserror::Error;
+
+const DID_SCHEME: &str = "did:";
+
+#[derive(Error, Debug, PartialEq)]
+pub enum DidSyntaxError {
+    #[error("missing did scheme")]
+    MissingScheme,
+
+    #[error("empty method name")]
+    EmptyMethodName,
+
+    #[error("invalid method name character '{0}'")]
+    InvalidMethodChar(char),
+
+    #[error("missing method-specific id")]
+    MissingMethodSpecificId,
+
+    #[error("empty method-specific id")]
+    EmptyMethodSpecificId,
+
+    #[error("method-specific id ends with ':'")]
+    TrailingColon,
+
+    #[error("invalid method-specific id character '{0}'")]
+    InvalidIdChar(char),
+
+    #[error("invalid percent-encoding")]
+    InvalidPercentEncoding,
+
+    #[error("invalid path character '{0}'")]
+    InvalidPathChar(char),
+
+    #[error("invalid query character '{0}'")]
+    InvalidQueryChar(char),
+
+    #[error("invalid fragment character '{0}'")]
+    InvalidFragmentChar(char),
+
+    #[error("did url not allowed")]
+    DidUrlNotAllowed,
+
+    #[error("unsupported did method '{0}'")]
+    UnsupportedMethod(String),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParsedDid<'a> {
+    pub method: &'a str,
+    pub method_specific_id: &'a str,
+}
+
+impl ParsedDid<'_> {
+    // Number of bytes of the DID in the parsed input
+    fn len(&self) -> usize {
+        DID_SCHEME.len() + self.method.len() + 1 + self.method_specific_id.len()
+    }
+
+    pub fn ensure_method(&self, did_prefix: &str) -> Result<(), DidSyntaxError> {
+        let expected = did_prefix
+            .strip_prefix(DID_SCHEME)
+            .and_then(|p| p.strip_suffix(':'));
+        if expected != Some(self.method) {
+            return Err(DidSyntaxError::UnsupportedMethod(self.method.to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParsedDidUrl<'a> {
+    pub did: ParsedDid<'a>,
+    pub path: Option<&'a str>,
+    pub query: Option<&'a str>,
+    pub fragment: Option<&'a str>,
+}
+
+impl<'a> ParsedDidUrl<'a> {
+    pub fn did_str(&self, s: &'a str) -> &'a str {
+        &s[..self.did.len()]
+    }
+
+    pub fn is_bare_did(&self) -> bool {
+        self.path.is_none() && self.query.is_none() && self.fragment.is_none()
+    }
+}
+
+/// Parses a bare DID, rejecting any path, query or fragment.
+pub fn parse_did(s: &str) -> Result<ParsedDid<'_>, DidSyntaxError> {
+    let url = parse_did_url(s)?;
+    if !url.is_bare_did() {
+        return Err(DidSyntaxError::DidUrlNotAllowed);
+    }
+    Ok(url.did)
+}
+
+/// Parses a DID URL into the DID and its optional path, query and fragment.
+pub fn parse_did_url(s: &str) -> Result<ParsedDidUrl<'_>, DidSyntaxError> {
+    let rest = s
+        .strip_prefix(DID_SCHEME)
+        .ok_or(DidSyntaxError::MissingScheme)?;
+
+    let method_end = rest
+        .find(':')
+        .ok_or(DidSyntaxError::MissingMethodSpecificId)?;
+    let method = &rest[..method_end];
+    if method.is_empty() {
+        return Err(DidSyntaxError::EmptyMethodName);
+    }
+    if let Some(c) = method
+        .chars()
+        .find(|c| !(c.is_ascii_lowercase() || c.is_ascii_digit()))
+    {
+        return Err(DidSyntaxError::InvalidMethodChar(c));
+    }
+
+    let rest = &rest[method_end + 1..];
+    let id_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let method_specific_id = &rest[..id_end];
+    ensure_method_specific_id(method_specific_id)?;
+
+    let mut rest = &rest[id_end..];
+    let mut fragment = None;
+    if let Some(i) = rest.find('#') {
+        let f = &rest[i + 1..];
+        ensure_chars(f, is_query_or_fragment_char, DidSyntaxError::InvalidFragmentChar)?;
+        fragment = Some(f);
+        rest = &rest[..i];
+    }
+    let mut query = None;
+    if let Some(i) = rest.find('?') {
+        let q = &rest[i + 1..];
+        ensure_chars(q, is_query_or_fragment_char, DidSyntaxError::InvalidQueryChar)?;
+        query = Some(q);
+        rest = &rest[..i];
+    }
+    let mut path = None;
+    if !rest.is_empty() {
+        ensure_chars(rest, |c| c == '/' || is_pchar(c), DidSyntaxError::InvalidPathChar)?;
+        path = Some(rest);
+    }
+
+    Ok(ParsedDidUrl {
+        did: ParsedDid {
+            method,
+            method_specific_id,
+        },
+        path,
+        query,
+        fragment,
+    })
+}
+
+/// Splits a DID URL query into percent-decoded name/value pairs.
+pub fn parse_query(query: &str) -> Result<Vec<(String, String)>, DidSyntaxError> {
+    query
+        .split('&')
+        .filter(|p| !p.is_empty())
+        .map(|p| {
+            let (name, value) = p.split_once('=').unwrap_or((p, ""));
+            Ok((percent_decode(name)?, percent_decode(value)?))
+        })
+        .collect()
+}
+
+fn percent_decode(s: &str) -> Result<String, DidSyntaxError> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        if b == b'%' {
+            let hex = tail
+                .get(..2)
+                .and_then(|h| std::str::from_utf8(h).ok())
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+                .ok_or(DidSyntaxError::InvalidPercentEncoding)?;
+            bytes.push(hex);
+            rest = &tail[2..];
+        } else {
+            bytes.push(b);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| DidSyntaxError::InvalidPercentEncoding)
+}
+
+fn ensure_method_specific_id(id: &str) -> Result<(), DidSyntaxError> {
+    if id.is_empty() {
+        return Err(DidSyntaxError::EmptyMethodSpecificId);
+    }
+    if id.ends_with(':') {
+        return Err(DidSyntaxError::TrailingColon);
+    }
+    ensure_chars(
+        id,
+        |c| c == ':' || is_idchar(c),
+        DidSyntaxError::InvalidIdChar,
+    )
+}
+
+// Checks every character of `s` with `allowed`, validating percent-encoded
+// triplets on the way
+fn ensure_chars(
+    s: &str,
+    allowed: fn(char) -> bool,
+    invalid: fn(char) -> DidSyntaxError,
+) -> Result<(), DidSyntaxError> {
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '%' {
+            let hex_digits = [chars.next(), chars.next()];
+            if !hex_digits
+                .iter()
+                .all(|h| h.is_some_and(|h| h.is_ascii_hexdigit()))
+            {
+                return Err(DidSyntaxError::InvalidPercentEncoding);
+            }
+        } else if !allowed(c) {
+            return Err(invalid(c));
+        }
+    }
+    Ok(())
+}
+
+fn is_idchar(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_')
+}
+
+fn is_unreserved(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~')
+}
+
+fn is_sub_delim(c: char) -> bool {
+    matches!(
+        c,
+        '!' | '$' | '&' | '\'' | '(' | ')' | '*' | '+' | ',' | ';' | '='
+    )
+}
+
+fn is_pchar(c: char) -> bool {
+    is_unreserved(c) || is_sub_delim(c) || matches!(c, ':' | '@')
+}
+
+fn is_query_or_fragment_char(c: char) -> bool {
+    is_pchar(c) || matches!(c, '/' | '?')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_valid_dids() {
+        let did = parse_did("did:c4e:abc").unwrap();
+        assert_eq!(did.method, "c4e");
+        assert_eq!(did.method_specific_id, "abc");
+
+        let did = parse_did("did:c4e:network:sub-id_1.2:X%2Fz").unwrap();
+        assert_eq!(did.method, "c4e");
+        assert_eq!(did.method_specific_id, "network:sub-id_1.2:X%2Fz");
+
+        // Empty segments before the last colon are allowed by the ABNF
+        assert!(parse_did("did:c4e::abc").is_ok());
+    }
+
+    #[test]
+    fn test_parse_invalid_dids() {
+        assert_eq!(parse_did("c4e:abc"), Err(DidSyntaxError::MissingScheme));
+        assert_eq!(parse_did("did::abc"), Err(DidSyntaxError::EmptyMethodName));
+        assert_eq!(
+            parse_did("did:C4E:abc"),
+            Err(DidSyntaxError::InvalidMethodChar('C'))
+        );
+        assert_eq!(
+            parse_did("did:c4e"),
+            Err(DidSyntaxError::MissingMethodSpecificId)
+        );
+        assert_eq!(
+            parse_did("did:c4e:"),
+            Err(DidSyntaxError::EmptyMethodSpecificId)
+        );
+        assert_eq!(parse_did("did:c4e:abc:"), Err(DidSyntaxError::TrailingColon));
+        assert_eq!(
+            parse_did("did:c4e:a b#frag?x"),
+            Err(DidSyntaxError::InvalidIdChar(' '))
+        );
+        assert_eq!(
+            parse_did("did:c4e:abc%2"),
+            Err(DidSyntaxError::InvalidPercentEncoding)
+        );
+        assert_eq!(
+            parse_did("did:c4e:abc%zz"),
+            Err(DidSyntaxError::InvalidPercentEncoding)
+        );
+        assert_eq!(
+            parse_did("did:c4e:abc#key-1"),
+            Err(DidSyntaxError::DidUrlNotAllowed)
+        );
+        assert_eq!(
+            parse_did("did:c4e:abc/path"),
+            Err(DidSyntaxError::DidUrlNotAllowed)
+        );
+    }
+
+    #[test]
+    fn test_parse_did_urls() {
+        let s = "did:c4e:abc/a/b.json?service=files&relativeRef=/x#frag";
+        let url = parse_did_url(s).unwrap();
+        assert_eq!(url.did_str(s), "did:c4e:abc");
+        assert_eq!(url.path, Some("/a/b.json"));
+        assert_eq!(url.query, Some("service=files&relativeRef=/x"));
+        assert_eq!(url.fragment, Some("frag"));
+
+        let url = parse_did_url("did:c4e:abc#key-1").unwrap();
+        assert_eq!(url.path, None);
+        assert_eq!(url.query, None);
+        assert_eq!(url.fragment, Some("key-1"));
+
+        let url = parse_did_url("did:c4e:abc?versionId=1").unwrap();
+        assert_eq!(url.query, Some("versionId=1"));
+        assert_eq!(url.fragment, None);
+
+        // A '?' inside a fragment belongs to the fragment
+        let url = parse_did_url("did:c4e:abc#frag?x").unwrap();
+        assert_eq!(url.query, None);
+        assert_eq!(url.fragment, Some("frag?x"));
+
+        assert!(parse_did_url("did:c4e:abc").unwrap().is_bare_did());
+    }
+
+    #[test]
+    fn test_parse_invalid_did_urls() {
+        assert_eq!(
+            parse_did_url("did:c4e:abc/a b"),
+            Err(DidSyntaxError::InvalidPathChar(' '))
+        );
+        assert_eq!(
+            parse_did_url("did:c4e:abc?a=<b>"),
+            Err(DidSyntaxError::InvalidQueryChar('<'))
+        );
+        assert_eq!(
+            parse_did_url("did:c4e:abc#a#b"),
+            Err(DidSyntaxError::InvalidFragmentChar('#'))
+        );
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(
+            parse_query("service=files&relativeRef=%2Fa%20b.json").unwrap(),
+            vec![
+                ("service".to_string(), "files".to_string()),
+                ("relativeRef".to_string(), "/a b.json".to_string()),
+            ]
+        );
+        assert_eq!(
+            parse_query("flag&&x=").unwrap(),
+            vec![
+                ("flag".to_string(), "".to_string()),
+                ("x".to_string(), "".to_string()),
+            ]
+        );
+        assert_eq!(
+            parse_query("x=%ff"),
+            Err(DidSyntaxError::InvalidPercentEncoding)
+        );
+        assert_eq!(
+            parse_query("x=%f"),
+            Err(DidSyntaxError::InvalidPercentEncoding)
+        );
+    }
+
+    #[test]
+    fn test_ensure_method() {
+        let did = parse_did("did:c4e:abc").unwrap();
+        assert!(did.ensure_method("did:c4e:").is_ok());
+        assert_eq!(
+            did.ensure_method("did:c4etest:"),
+            Err(DidSyntaxError::UnsupportedMethod("c4e".to_string()))
+        );
+    }
+}
@@ -1,13 +1,18 @@
 use cosmwasm_std::StdError;
 use thiserror::Error;
 
+use crate::did_syntax::DidSyntaxError;
+
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
-    #[error("Did format error: {0}")]
-    DidFormatError(String),
+    #[error("Did format error: {0} ({1})")]
+    DidFormatError(String, DidSyntaxError),
+
+    #[error("Did method format error: {0}")]
+    DidMethodFormatError(String),
 
     #[error("Controller format error: {0}")]
     ControllerFormatError(String),
@@ -39,6 +44,9 @@ pub enum ContractError {
     #[error("Did document already exists: {0}")]
     DidDocumentAlreadyExists(String),
 
+    #[error("Did document deactivated: {0}")]
+    DidDocumentDeactivated(String),
+
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
 
@@ -62,4 +70,163 @@ pub enum ContractError {
 
     #[error("Service id format error: {0}")]
     ServiceIdFormatError(String),
-}
\ No newline at end of file
+
+    #[error("Did document verification method already exists: {0}")]
+    DidDocumentVerificationMethodAlreadyExists(String),
+
+    #[error("Did document verification method does not exist: {0}")]
+    DidDocumentVerificationMethodNotExists(String),
+
+    #[error("Duplicated verification method: {0}")]
+    DuplicatedVerificationMethod(String),
+
+    #[error("Verification method id format error: {0}")]
+    VerificationMethodIdFormatError(String),
+
+    #[error("Verification method controller format error: {0}")]
+    VerificationMethodControllerFormatError(String),
+
+    #[error("Verification method type missing: {0}")]
+    VerificationMethodTypeMissing(String),
+
+    #[error("Verification method must have exactly one public key: {0}")]
+    VerificationMethodKeyMaterialError(String),
+
+    #[error("Verification method is referenced by a verification relationship: {0}")]
+    VerificationMethodReferenced(String),
+
+    #[error("Verification relationship references unknown verification method: {0}")]
+    VerificationRelationshipMethodNotFound(String),
+
+    #[error("Duplicated verification relationship method: {0}")]
+    DuplicatedVerificationRelationshipMethod(String),
+
+    #[error("Did document verification relationship method already exists: {0}")]
+    DidDocumentVerificationRelationshipMethodAlreadyExists(String),
+
+    #[error("Did document verification relationship method does not exist: {0}")]
+    DidDocumentVerificationRelationshipMethodNotExists(String),
+
+    #[error("Invalid contract version: {0}")]
+    InvalidContractVersion(String),
+
+    #[error("Cannot migrate from different contract: {0}")]
+    MigrationContractMismatch(String),
+
+    #[error("Cannot migrate from version {0} to older version {1}")]
+    MigrationDowngrade(String, String),
+
+    #[error("Invalid authorization policy: {0}")]
+    InvalidAuthorizationPolicy(String),
+
+    #[error("Authorization policy cannot be satisfied by the controllers of {0}")]
+    AuthorizationPolicyUnsatisfiable(String),
+
+    #[error("Authorization threshold not met for {0}: {1} of {2}")]
+    AuthorizationThresholdNotMet(String, u64, u64),
+
+    #[error("Proposal not found: {0}")]
+    ProposalNotFound(u64),
+
+    #[error("Proposal expired: {0}")]
+    ProposalExpired(u64),
+
+    #[error("Proposal not expired: {0}")]
+    ProposalNotExpired(u64),
+
+    #[error("Proposal already approved by: {0}")]
+    ProposalAlreadyApproved(String),
+
+    #[error("Proposal expiration must be in the future")]
+    InvalidProposalExpiration,
+
+    #[error("Invalid nonce for {0}: expected {1}, got {2}")]
+    InvalidNonce(String, u64, u64),
+
+    #[error("Verification method not authorized for capability invocation: {0}")]
+    VerificationMethodNotAuthorized(String),
+
+    #[error("Unsupported verification method key: {0}")]
+    UnsupportedVerificationMethodKey(String),
+
+    #[error("Invalid signature for verification method: {0}")]
+    InvalidSignature(String),
+
+    #[error("Batch contains no operations")]
+    EmptyBatch,
+
+    #[error("Batch operation {0} failed: {1}")]
+    BatchOperationFailed(usize, Box<ContractError>),
+
+    #[error("Service type missing: {0}")]
+    ServiceTypeMissing(String),
+
+    #[error("Invalid service endpoint for {0}: {1}")]
+    ServiceEndpointInvalid(String, String),
+
+    #[error("Service update changes nothing: {0}")]
+    EmptyServiceUpdate(String),
+
+    #[error("Did url {0} does not reference did document {1}")]
+    DidUrlNotOwned(String, String),
+
+    #[error("Invalid traversal limits: {0}")]
+    InvalidTraversalLimits(String),
+
+    #[error("Delegation depth of {0} exceeds the maximum of {1}")]
+    DelegationDepthExceeded(String, u32),
+
+    #[error("Controller traversal of {0} visits more than {1} documents")]
+    TraversalBudgetExceeded(String, u32),
+
+    #[error("Invalid cursor: {0}")]
+    InvalidCursor(String),
+
+    #[error("Invalid document limits: {0}")]
+    InvalidDocumentLimits(String),
+
+    #[error("Invalid pagination: default limit {0} must be positive and at most the maximum {1}")]
+    InvalidPagination(u32, u32),
+
+    #[error("Did document {0} has more than {1} controllers")]
+    TooManyControllers(String, u32),
+
+    #[error("Did document {0} has more than {1} services")]
+    TooManyServices(String, u32),
+
+    #[error("Did document {0} has more than {1} verification methods")]
+    TooManyVerificationMethods(String, u32),
+
+    #[error("Id longer than {1} bytes: {0}")]
+    IdTooLong(String, u32),
+
+    #[error("Type longer than {1} bytes: {0}")]
+    TypeTooLong(String, u32),
+
+    #[error("Service endpoint of {0} longer than {1} bytes")]
+    ServiceEndpointTooLong(String, u32),
+
+    #[error("Did document {0} is larger than {1} bytes")]
+    DidDocumentTooLarge(String, u32),
+
+    #[error("No pending admin")]
+    NoPendingAdmin,
+
+    #[error("Contract is paused")]
+    Paused,
+
+    #[error("Operation paused: {0}")]
+    OperationPaused(String),
+
+    #[error("No operations to pause")]
+    EmptyPause,
+
+    #[error("Contract is not paused")]
+    NotPaused,
+
+    #[error("Invalid deposit: {0}")]
+    InvalidDeposit(String),
+
+    #[error("Insufficient deposit for {0}: {1} more required")]
+    InsufficientDeposit(String, String),
+}
@@ -5,7 +5,9 @@
 pub mod contract;
 pub mod state;
 pub mod error;
-mod multiset;
+pub mod did_syntax;
+mod signature;
+mod migration;
 
 #[cfg(test)]
 mod test;
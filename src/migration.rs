@@ -0,0 +1,185 @@
+use std::collections::HashSet;
+
+use cosmwasm_std::{Empty, Order, StdResult, Storage};
+use cw_storage_plus::{Index, Map};
+use semver::Version;
+
+use crate::contract::DidContract;
+use crate::error::ContractError;
+use crate::state::{Config, Did};
+
+// Instances instantiated before contract info was stored
+pub(crate) const LEGACY_CONTRACT_VERSION: &str = "0.1.0";
+// DID method hard-coded in legacy instances
+const LEGACY_DID_METHOD: &str = "c4e";
+// Namespace of the legacy MultiSet controller index. It kept the set of
+// controllers under this namespace and the DIDs of every controller under
+// the namespace concatenated with the controller.
+const LEGACY_CONTROLLERS_NAMESPACE: &str = "controllers";
+
+type MigrationFn = fn(&DidContract, &mut dyn Storage) -> Result<(), ContractError>;
+
+pub(crate) struct Migration {
+    pub version: &'static str,
+    pub name: &'static str,
+    pub run: MigrationFn,
+}
+
+// Storage migrations in ascending version order. A migration runs when its
+// version is newer than the stored one and not newer than the new code.
+pub(crate) const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: "0.2.0",
+        name: "backfill_config",
+        run: backfill_config,
+    },
+    Migration {
+        version: "0.2.0",
+        name: "reindex_controllers",
+        run: reindex_controllers,
+    },
+    Migration {
+        version: "0.2.0",
+        name: "backfill_did_count",
+        run: backfill_did_count,
+    },
+    Migration {
+        version: "0.2.0",
+        name: "index_services",
+        run: index_services,
+    },
+    Migration {
+        version: "0.2.0",
+        name: "normalize_service_ids",
+        run: normalize_service_ids,
+    },
+];
+
+pub(crate) fn parse_version(version: &str) -> Result<Version, ContractError> {
+    Version::parse(version).map_err(|_| ContractError::InvalidContractVersion(version.to_string()))
+}
+
+pub(crate) fn run_migrations(
+    contract: &DidContract,
+    store: &mut dyn Storage,
+    from: &Version,
+    to: &Version,
+) -> Result<Vec<&'static str>, ContractError> {
+    let mut applied = Vec::new();
+    for migration in MIGRATIONS {
+        let version = parse_version(migration.version)?;
+        if &version > from && &version <= to {
+            (migration.run)(contract, store)?;
+            applied.push(migration.name);
+        }
+    }
+    Ok(applied)
+}
+
+fn backfill_config(contract: &DidContract, store: &mut dyn Storage) -> Result<(), ContractError> {
+    if !contract.config.exists(store) {
+        contract
+            .config
+            .save(store, &Config::new(LEGACY_DID_METHOD)?)?;
+    }
+    Ok(())
+}
+
+// Replaces the legacy MultiSet controller index with the controller index of
+// `did_docs`, rebuilt from the stored documents
+fn reindex_controllers(contract: &DidContract, store: &mut dyn Storage) -> Result<(), ContractError> {
+    let legacy_controllers: Map<String, Empty> = Map::new(LEGACY_CONTROLLERS_NAMESPACE);
+    let controllers = legacy_controllers
+        .keys(store, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for controller in controllers {
+        let legacy_dids: Map<String, Empty> =
+            Map::new_dyn(format!("{}{}", LEGACY_CONTROLLERS_NAMESPACE, controller));
+        let dids = legacy_dids
+            .keys(store, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for did in dids {
+            legacy_dids.remove(store, did);
+        }
+        legacy_controllers.remove(store, controller);
+    }
+
+    let did_docs = contract
+        .did_docs
+        .range(store, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (did, did_doc) in did_docs {
+        contract
+            .did_docs
+            .idx
+            .controller
+            .save(store, did.as_bytes(), &did_doc)?;
+    }
+    Ok(())
+}
+
+fn backfill_did_count(contract: &DidContract, store: &mut dyn Storage) -> Result<(), ContractError> {
+    let count = contract
+        .did_docs
+        .keys_raw(store, None, None, Order::Ascending)
+        .count();
+    contract.did_count.save(store, &(count as u64))?;
+    Ok(())
+}
+
+// Builds the service type and endpoint host indexes of `did_docs` from the
+// stored documents
+fn index_services(contract: &DidContract, store: &mut dyn Storage) -> Result<(), ContractError> {
+    let did_docs = contract
+        .did_docs
+        .range(store, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let idx = &contract.did_docs.idx;
+    for (did, did_doc) in did_docs {
+        idx.service_type.save(store, did.as_bytes(), &did_doc)?;
+        idx.service_endpoint_host
+            .save(store, did.as_bytes(), &did_doc)?;
+    }
+    Ok(())
+}
+
+// Rewrites service ids that are not URLs into their own document, which
+// legacy instances accepted, so the documents can be updated again. A
+// fragment is kept where there is one; bare DIDs get `#service-<n>`.
+fn normalize_service_ids(contract: &DidContract, store: &mut dyn Storage) -> Result<(), ContractError> {
+    let did_docs = contract
+        .did_docs
+        .range(store, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (did, mut did_doc) in did_docs {
+        if did_doc.service.iter().all(|s| s.id.ensure_url_of(&did_doc.id).is_ok()) {
+            continue;
+        }
+        let mut taken: HashSet<String> = did_doc
+            .service
+            .iter()
+            .map(|s| s.id.to_string())
+            .chain(did_doc.verification_method.iter().map(|m| m.id.to_string()))
+            .collect();
+        let mut next = 1;
+        for service in did_doc.service.iter_mut() {
+            if service.id.ensure_url_of(&did_doc.id).is_ok() {
+                continue;
+            }
+            let mut id = match service.id.value().split_once('#') {
+                Some((_, fragment)) if !fragment.is_empty() => {
+                    format!("{}#{}", did_doc.id, fragment)
+                }
+                _ => String::new(),
+            };
+            while id.is_empty() || taken.contains(&id) {
+                id = format!("{}#service-{}", did_doc.id, next);
+                next += 1;
+            }
+            taken.insert(id.clone());
+            service.id = Did::new(&id);
+        }
+        contract.did_docs.save(store, did, &did_doc)?;
+    }
+    Ok(())
+}
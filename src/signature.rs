@@ -0,0 +1,215 @@
+// Signature verification for operations signed with a verification method.
+//
+// Supported key encodings:
+//   publicKeyMultibase  base58btc ("z"), either multicodec-prefixed
+//                       (ed25519-pub 0xed01, secp256k1-pub 0xe701) or raw bytes
+//                       for the Ed25519 and EcdsaSecp256k1 key types
+//   publicKeyJwk        OKP/Ed25519 and EC/secp256k1
+//
+// Ed25519 signatures are over the sign bytes, secp256k1 signatures are over
+// their SHA-256 hash, as produced by Cosmos SDK signers.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use cosmwasm_std::Api;
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+use crate::state::{PublicKeyJwk, VerificationMethod};
+
+const MULTIBASE_BASE58_BTC: char = 'z';
+const MULTICODEC_ED25519_PUB: [u8; 2] = [0xed, 0x01];
+const MULTICODEC_SECP256K1_PUB: [u8; 2] = [0xe7, 0x01];
+
+const ED25519_KEY_TYPES: [&str; 2] = ["Ed25519VerificationKey2018", "Ed25519VerificationKey2020"];
+const SECP256K1_KEY_TYPES: [&str; 1] = ["EcdsaSecp256k1VerificationKey2019"];
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum PublicKey {
+    Ed25519(Vec<u8>),
+    Secp256k1(Vec<u8>),
+}
+
+impl PublicKey {
+    pub(crate) fn from_verification_method(
+        method: &VerificationMethod,
+    ) -> Result<Self, ContractError> {
+        let unsupported = || ContractError::UnsupportedVerificationMethodKey(method.id.to_string());
+        if let Some(multibase) = &method.public_key_multibase {
+            return from_multibase(&method.a_type, multibase).ok_or_else(unsupported);
+        }
+        if let Some(jwk) = &method.public_key_jwk {
+            return from_jwk(jwk).ok_or_else(unsupported);
+        }
+        Err(unsupported())
+    }
+
+    pub(crate) fn verify(
+        &self,
+        api: &dyn Api,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, ContractError> {
+        let verified = match self {
+            PublicKey::Ed25519(key) => api.ed25519_verify(message, signature, key),
+            PublicKey::Secp256k1(key) => {
+                let hash = Sha256::digest(message);
+                api.secp256k1_verify(&hash, signature, key)
+            }
+        };
+        // Malformed signatures are reported by the api as errors
+        Ok(verified.unwrap_or(false))
+    }
+
+    fn ensure_length(self) -> Option<Self> {
+        let valid = match &self {
+            PublicKey::Ed25519(key) => key.len() == 32,
+            PublicKey::Secp256k1(key) => key.len() == 33 || key.len() == 65,
+        };
+        valid.then_some(self)
+    }
+}
+
+fn from_multibase(key_type: &str, multibase: &str) -> Option<PublicKey> {
+    let encoded = multibase.strip_prefix(MULTIBASE_BASE58_BTC)?;
+    let bytes = bs58::decode(encoded).into_vec().ok()?;
+    let key = if let Some(key) = bytes.strip_prefix(&MULTICODEC_ED25519_PUB) {
+        PublicKey::Ed25519(key.to_vec())
+    } else if let Some(key) = bytes.strip_prefix(&MULTICODEC_SECP256K1_PUB) {
+        PublicKey::Secp256k1(key.to_vec())
+    } else if ED25519_KEY_TYPES.contains(&key_type) {
+        PublicKey::Ed25519(bytes)
+    } else if SECP256K1_KEY_TYPES.contains(&key_type) {
+        PublicKey::Secp256k1(bytes)
+    } else {
+        return None;
+    };
+    key.ensure_length()
+}
+
+fn from_jwk(jwk: &PublicKeyJwk) -> Option<PublicKey> {
+    let decode = |v: &Option<String>| URL_SAFE_NO_PAD.decode(v.as_ref()?).ok();
+    let key = match (jwk.kty.as_str(), jwk.crv.as_deref()) {
+        ("OKP", Some("Ed25519")) => PublicKey::Ed25519(decode(&jwk.x)?),
+        ("EC", Some("secp256k1")) => {
+            let mut key = vec![0x04];
+            key.extend(decode(&jwk.x)?);
+            key.extend(decode(&jwk.y)?);
+            PublicKey::Secp256k1(key)
+        }
+        _ => return None,
+    };
+    key.ensure_length()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Did;
+
+    fn key_method(a_type: &str) -> VerificationMethod {
+        VerificationMethod {
+            id: Did::new("did:c4e:abc#key-1"),
+            a_type: a_type.to_string(),
+            controller: Did::new("did:c4e:abc"),
+            public_key_multibase: None,
+            public_key_jwk: None,
+            blockchain_account_id: None,
+        }
+    }
+
+    fn multibase(bytes: &[u8]) -> String {
+        format!("z{}", bs58::encode(bytes).into_string())
+    }
+
+    #[test]
+    fn test_multicodec_multibase_keys() {
+        let mut ed25519 = MULTICODEC_ED25519_PUB.to_vec();
+        ed25519.extend([1u8; 32]);
+        let method = VerificationMethod {
+            public_key_multibase: Some(multibase(&ed25519)),
+            ..key_method("Multikey")
+        };
+        assert_eq!(
+            Ok(PublicKey::Ed25519(vec![1u8; 32])),
+            PublicKey::from_verification_method(&method)
+        );
+
+        let mut secp256k1 = MULTICODEC_SECP256K1_PUB.to_vec();
+        secp256k1.extend([2u8; 33]);
+        let method = VerificationMethod {
+            public_key_multibase: Some(multibase(&secp256k1)),
+            ..key_method("Multikey")
+        };
+        assert_eq!(
+            Ok(PublicKey::Secp256k1(vec![2u8; 33])),
+            PublicKey::from_verification_method(&method)
+        );
+    }
+
+    #[test]
+    fn test_raw_multibase_keys() {
+        let method = VerificationMethod {
+            public_key_multibase: Some(multibase(&[1u8; 32])),
+            ..key_method("Ed25519VerificationKey2020")
+        };
+        assert_eq!(
+            Ok(PublicKey::Ed25519(vec![1u8; 32])),
+            PublicKey::from_verification_method(&method)
+        );
+
+        // Raw keys need a key type to be interpreted
+        let method = VerificationMethod {
+            public_key_multibase: Some(multibase(&[1u8; 32])),
+            ..key_method("Multikey")
+        };
+        assert!(PublicKey::from_verification_method(&method).is_err());
+
+        // Only base58btc is supported
+        let method = VerificationMethod {
+            public_key_multibase: Some("m".to_string() + &"A".repeat(43)),
+            ..key_method("Ed25519VerificationKey2020")
+        };
+        assert!(PublicKey::from_verification_method(&method).is_err());
+    }
+
+    #[test]
+    fn test_jwk_keys() {
+        let method = VerificationMethod {
+            public_key_jwk: Some(PublicKeyJwk {
+                kty: "EC".to_string(),
+                crv: Some("secp256k1".to_string()),
+                x: Some(URL_SAFE_NO_PAD.encode([3u8; 32])),
+                y: Some(URL_SAFE_NO_PAD.encode([4u8; 32])),
+                n: None,
+                e: None,
+            }),
+            ..key_method("JsonWebKey2020")
+        };
+        let mut expected = vec![0x04];
+        expected.extend([3u8; 32]);
+        expected.extend([4u8; 32]);
+        assert_eq!(
+            Ok(PublicKey::Secp256k1(expected)),
+            PublicKey::from_verification_method(&method)
+        );
+
+        let method = VerificationMethod {
+            public_key_jwk: Some(PublicKeyJwk {
+                kty: "RSA".to_string(),
+                crv: None,
+                x: None,
+                y: None,
+                n: Some("n".to_string()),
+                e: Some("AQAB".to_string()),
+            }),
+            ..key_method("JsonWebKey2020")
+        };
+        assert_eq!(
+            Err(ContractError::UnsupportedVerificationMethodKey(
+                "did:c4e:abc#key-1".to_string()
+            )),
+            PublicKey::from_verification_method(&method)
+        );
+    }
+}
@@ -1,9 +1,11 @@
 use core::fmt;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Api, StdError, StdResult, Storage};
-use cw_storage_plus::{Index, IndexList, IndexedMap, Key, Map, MultiIndex, Prefixer, PrimaryKey};
+use cosmwasm_std::{from_json, to_json_string, to_json_vec, Addr, Api, Binary, BlockInfo, Coin, Empty, Order, StdError, StdResult, Storage, Timestamp, Uint128};
+use cw_storage_plus::{
+    Bound, Index, IndexList, IndexedMap, Key, KeyDeserialize, Map, MultiIndex, Prefixer, PrimaryKey,
+};
 use schemars::JsonSchema;
 use serde::{
     de::{self, SeqAccess, Visitor},
@@ -11,12 +13,324 @@ use serde::{
     Deserialize, Deserializer, Serialize, Serializer,
 };
 
+use crate::did_syntax::{self, DidSyntaxError};
 use crate::error::ContractError;
 
-pub const DID_PREFIX: &str = "did:c4e:"; // TODO make configurable on contract instatiating
-                                         // const ADDRESS_DID_PREFIX: &str = constcat!(DID_PREFIX, "address:");
+// const ADDRESS_DID_PREFIX: &str = constcat!(DID_PREFIX, "address:");
+
+pub const DEFAULT_MAX_DELEGATION_DEPTH: u32 = 8;
+pub const DEFAULT_MAX_VISITED_NODES: u32 = 64;
+pub const DEFAULT_MAX_CONTROLLERS: u32 = 32;
+pub const DEFAULT_MAX_SERVICES: u32 = 64;
+pub const DEFAULT_MAX_VERIFICATION_METHODS: u32 = 32;
+pub const DEFAULT_MAX_DOCUMENT_SIZE: u32 = 32 * 1024;
+pub const DEFAULT_MAX_ID_LENGTH: u32 = 256;
+pub const DEFAULT_MAX_TYPE_LENGTH: u32 = 128;
+pub const DEFAULT_MAX_ENDPOINT_LENGTH: u32 = 2048;
+pub const DEFAULT_PAGE_LIMIT: u32 = 50;
+pub const DEFAULT_MAX_PAGE_LIMIT: u32 = 200;
+
+#[cw_serde]
+pub struct Config {
+    // Can change the parameters below; nobody can once it is renounced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admin: Option<Addr>,
+    // Proposed by the admin, becomes the admin on accepting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_admin: Option<Addr>,
+    // Fixed at instantiation, stored documents depend on it
+    pub did_prefix: String,
+    // Instances instantiated before the parameters were configurable use the defaults
+    #[serde(default)]
+    pub traversal_limits: TraversalLimits,
+    #[serde(default)]
+    pub document_limits: DocumentLimits,
+    #[serde(default)]
+    pub pagination: Pagination,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deposit: Option<DepositConfig>,
+}
+
+// Parameters the admin can change. Unset fields keep their current value.
+#[cw_serde]
+#[derive(Default)]
+pub struct ConfigUpdate {
+    pub traversal_limits: Option<TraversalLimits>,
+    pub document_limits: Option<DocumentLimits>,
+    pub pagination: Option<Pagination>,
+    // Zero amounts stop requiring a deposit
+    pub deposit: Option<DepositConfig>,
+}
+
+// Funds locked while a document is stored: `amount` plus `per_byte` for
+// every byte of the document serialized to JSON
+#[cw_serde]
+pub struct DepositConfig {
+    pub denom: String,
+    pub amount: Uint128,
+    pub per_byte: Uint128,
+}
+
+impl DepositConfig {
+    pub fn ensure_valid(&self) -> Result<(), ContractError> {
+        if self.denom.is_empty() {
+            return Err(ContractError::InvalidDeposit("denom must not be empty".to_string()));
+        }
+        Ok(())
+    }
+
+    // None when nothing is required
+    pub fn required(&self, did_doc: &DidDocument) -> Result<Option<Coin>, ContractError> {
+        let size = Uint128::from(to_json_vec(did_doc)?.len() as u128);
+        let amount = self
+            .per_byte
+            .checked_mul(size)
+            .and_then(|amount| amount.checked_add(self.amount))
+            .map_err(StdError::from)?;
+        if amount.is_zero() {
+            return Ok(None);
+        }
+        Ok(Some(Coin::new(amount, &self.denom)))
+    }
+}
+
+// Funds held for a stored document, with the share each depositor paid.
+// Surplus goes back to the latest depositors first.
+#[cw_serde]
+pub struct Deposit {
+    pub amount: Coin,
+    pub shares: Vec<DepositShare>,
+}
+
+#[cw_serde]
+pub struct DepositShare {
+    pub depositor: Addr,
+    pub amount: Uint128,
+}
+
+impl Deposit {
+    pub fn new(denom: &str) -> Self {
+        Deposit {
+            amount: Coin::new(0u128, denom),
+            shares: vec![],
+        }
+    }
+
+    // A depositor paying again becomes the latest one
+    pub fn add(&mut self, depositor: &Addr, amount: Uint128) {
+        self.amount.amount += amount;
+        let mut share = match self.shares.iter().position(|s| s.depositor == *depositor) {
+            Some(i) => self.shares.remove(i),
+            None => DepositShare {
+                depositor: depositor.clone(),
+                amount: Uint128::zero(),
+            },
+        };
+        share.amount += amount;
+        self.shares.push(share);
+    }
 
+    // Takes `amount` back from the latest depositors and returns what each
+    // of them is owed
+    pub fn release(&mut self, mut amount: Uint128) -> Vec<(Addr, Coin)> {
+        let mut released = vec![];
+        while let Some(share) = self.shares.last_mut() {
+            if amount.is_zero() {
+                break;
+            }
+            let taken = share.amount.min(amount);
+            share.amount -= taken;
+            amount -= taken;
+            self.amount.amount -= taken;
+            released.push((share.depositor.clone(), Coin::new(taken, &self.amount.denom)));
+            if share.amount.is_zero() {
+                self.shares.pop();
+            }
+        }
+        released
+    }
+}
+
+// Bounds on a single document, checked whenever it grows. Verification
+// methods include the ones embedded in relationships. Ids cover the document,
+// its controllers, services and methods; endpoints are measured as
+// displayed, a map or a set as JSON. The size is the length of the document
+// serialized to JSON. Missing fields take the default.
 #[cw_serde]
+#[derive(Copy)]
+#[serde(default)]
+pub struct DocumentLimits {
+    pub max_controllers: u32,
+    pub max_services: u32,
+    pub max_verification_methods: u32,
+    pub max_id_length: u32,
+    pub max_type_length: u32,
+    pub max_endpoint_length: u32,
+    pub max_document_size: u32,
+}
+
+impl Default for DocumentLimits {
+    fn default() -> Self {
+        DocumentLimits {
+            max_controllers: DEFAULT_MAX_CONTROLLERS,
+            max_services: DEFAULT_MAX_SERVICES,
+            max_verification_methods: DEFAULT_MAX_VERIFICATION_METHODS,
+            max_id_length: DEFAULT_MAX_ID_LENGTH,
+            max_type_length: DEFAULT_MAX_TYPE_LENGTH,
+            max_endpoint_length: DEFAULT_MAX_ENDPOINT_LENGTH,
+            max_document_size: DEFAULT_MAX_DOCUMENT_SIZE,
+        }
+    }
+}
+
+impl DocumentLimits {
+    pub fn ensure_valid(&self) -> Result<(), ContractError> {
+        for (name, value) in [
+            ("max_controllers", self.max_controllers),
+            ("max_id_length", self.max_id_length),
+            ("max_type_length", self.max_type_length),
+            ("max_endpoint_length", self.max_endpoint_length),
+            ("max_document_size", self.max_document_size),
+        ] {
+            if value == 0 {
+                return Err(ContractError::InvalidDocumentLimits(format!(
+                    "{} must be positive",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Page sizes of list queries. A missing or zero limit means `default_limit`,
+// larger ones are capped at `max_limit`.
+#[cw_serde]
+#[derive(Copy)]
+pub struct Pagination {
+    pub default_limit: u32,
+    pub max_limit: u32,
+}
+
+impl Default for Pagination {
+    fn default() -> Self {
+        Pagination {
+            default_limit: DEFAULT_PAGE_LIMIT,
+            max_limit: DEFAULT_MAX_PAGE_LIMIT,
+        }
+    }
+}
+
+impl Pagination {
+    pub fn ensure_valid(&self) -> Result<(), ContractError> {
+        if self.default_limit == 0 || self.default_limit > self.max_limit {
+            return Err(ContractError::InvalidPagination(
+                self.default_limit,
+                self.max_limit,
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn limit(&self, limit: Option<usize>) -> usize {
+        match limit {
+            None | Some(0) => self.default_limit as usize,
+            Some(limit) => limit.min(self.max_limit as usize),
+        }
+    }
+}
+
+// Bounds on walking the controller graph. The delegation depth counts the
+// controller links between a document and the controllers it is checked
+// against; the visited nodes are the DID documents looked into on the way.
+#[cw_serde]
+#[derive(Copy)]
+pub struct TraversalLimits {
+    pub max_delegation_depth: u32,
+    pub max_visited_nodes: u32,
+}
+
+impl Default for TraversalLimits {
+    fn default() -> Self {
+        TraversalLimits {
+            max_delegation_depth: DEFAULT_MAX_DELEGATION_DEPTH,
+            max_visited_nodes: DEFAULT_MAX_VISITED_NODES,
+        }
+    }
+}
+
+impl TraversalLimits {
+    pub fn ensure_valid(&self) -> Result<(), ContractError> {
+        if self.max_delegation_depth == 0 {
+            return Err(ContractError::InvalidTraversalLimits(
+                "max_delegation_depth must be positive".to_string(),
+            ));
+        }
+        if self.max_visited_nodes == 0 {
+            return Err(ContractError::InvalidTraversalLimits(
+                "max_visited_nodes must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cw_serde]
+pub struct ContractVersion {
+    pub contract: String,
+    pub version: String,
+}
+
+impl Config {
+    pub fn new(did_method: &str) -> Result<Self, ContractError> {
+        if did_method.is_empty()
+            || !did_method
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        {
+            return Err(ContractError::DidMethodFormatError(did_method.to_string()));
+        }
+        Ok(Config {
+            admin: None,
+            pending_admin: None,
+            did_prefix: format!("did:{}:", did_method),
+            traversal_limits: TraversalLimits::default(),
+            document_limits: DocumentLimits::default(),
+            pagination: Pagination::default(),
+            deposit: None,
+        })
+    }
+
+    pub fn update(&mut self, update: ConfigUpdate) -> Result<(), ContractError> {
+        if let Some(traversal_limits) = update.traversal_limits {
+            traversal_limits.ensure_valid()?;
+            self.traversal_limits = traversal_limits;
+        }
+        if let Some(document_limits) = update.document_limits {
+            document_limits.ensure_valid()?;
+            self.document_limits = document_limits;
+        }
+        if let Some(pagination) = update.pagination {
+            pagination.ensure_valid()?;
+            self.pagination = pagination;
+        }
+        if let Some(deposit) = update.deposit {
+            deposit.ensure_valid()?;
+            self.deposit = Some(deposit);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn ensure_admin(&self, sender: &Addr) -> Result<(), ContractError> {
+        if self.admin.as_ref() != Some(sender) {
+            return Err(ContractError::Unauthorized(sender.to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
 pub struct DidDocument {
     pub id: Did,
     #[serde(
@@ -26,13 +340,229 @@ pub struct DidDocument {
     pub controller: Vec<Controller>,
     // pub controller: Controllers,
     pub service: Vec<Service>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub verification_method: Vec<VerificationMethod>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authentication: Vec<RelationshipMethod>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assertion_method: Vec<RelationshipMethod>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub key_agreement: Vec<RelationshipMethod>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub capability_invocation: Vec<RelationshipMethod>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub capability_delegation: Vec<RelationshipMethod>,
+    // Set when the document is deactivated; the document is kept as a tombstone
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub deactivated: bool,
+}
+
+fn is_false(b: &bool) -> bool {
+    !b
+}
+
+pub const DID_JSON_CONTENT_TYPE: &str = "application/did+json";
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+pub const URI_LIST_CONTENT_TYPE: &str = "text/uri-list";
+
+#[cw_serde]
+pub struct BlockStamp {
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+impl From<&BlockInfo> for BlockStamp {
+    fn from(block: &BlockInfo) -> Self {
+        BlockStamp {
+            height: block.height,
+            time: block.time,
+        }
+    }
+}
+
+// Document metadata as defined in DID Core, section 7.1.3. Documents stored
+// before metadata was tracked have no `created` stamp.
+#[cw_serde]
+#[derive(Default)]
+pub struct DidDocumentMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<BlockStamp>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated: Option<BlockStamp>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_id: Option<u64>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub deactivated: bool,
+    // Only set on the tombstone revision recorded when a document is deleted
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub deleted: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_update: Option<BlockStamp>,
+}
+
+// A document as it was after a single write, with the metadata at that time
+#[cw_serde]
+pub struct DidDocumentRevision {
+    pub did_document: DidDocument,
+    pub did_document_metadata: DidDocumentMetadata,
+}
+
+impl DidDocumentRevision {
+    // Block at which the revision was written
+    pub fn stamp(&self) -> BlockStamp {
+        let metadata = &self.did_document_metadata;
+        metadata
+            .updated
+            .clone()
+            .or_else(|| metadata.created.clone())
+            .unwrap_or(BlockStamp {
+                height: 0,
+                time: Timestamp::default(),
+            })
+    }
+}
+
+#[cw_serde]
+pub enum VersionTime {
+    Height(u64),
+    Time(Timestamp),
+}
+
+impl VersionTime {
+    // Whether a revision written at `stamp` was already in effect
+    pub fn includes(&self, stamp: &BlockStamp) -> bool {
+        match self {
+            VersionTime::Height(height) => stamp.height <= *height,
+            VersionTime::Time(time) => stamp.time <= *time,
+        }
+    }
+}
+
+// Serialized as the DID Core error codes, e.g. `notFound`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum DidResolutionError {
+    InvalidDid,
+    MethodNotSupported,
+    NotFound,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct DidResolutionMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<DidResolutionError>,
+}
+
+#[cw_serde]
+pub struct DidResolutionResult {
+    pub did_document: Option<DidDocument>,
+    pub did_document_metadata: DidDocumentMetadata,
+    pub did_resolution_metadata: DidResolutionMetadata,
+}
+
+impl DidResolutionResult {
+    pub fn resolved(did_document: DidDocument, did_document_metadata: DidDocumentMetadata) -> Self {
+        DidResolutionResult {
+            did_document: Some(did_document),
+            did_document_metadata,
+            did_resolution_metadata: DidResolutionMetadata {
+                content_type: Some(DID_JSON_CONTENT_TYPE.to_string()),
+                error: None,
+            },
+        }
+    }
+
+    pub fn error(error: DidResolutionError) -> Self {
+        DidResolutionResult {
+            did_document: None,
+            did_document_metadata: DidDocumentMetadata::default(),
+            did_resolution_metadata: DidResolutionMetadata {
+                content_type: None,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum DidDereferencingError {
+    InvalidDidUrl,
+    MethodNotSupported,
+    NotFound,
+}
+
+// What a DID URL points to: the whole document, a resource selected by the
+// fragment or a URL built from a service endpoint
+#[cw_serde]
+pub enum DereferencedContent {
+    DidDocument(DidDocument),
+    Service(Service),
+    VerificationMethod(VerificationMethod),
+    Url(String),
+}
+
+impl DereferencedContent {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            DereferencedContent::DidDocument(_) => DID_JSON_CONTENT_TYPE,
+            DereferencedContent::Service(_) | DereferencedContent::VerificationMethod(_) => {
+                JSON_CONTENT_TYPE
+            }
+            DereferencedContent::Url(_) => URI_LIST_CONTENT_TYPE,
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct DidDereferencingMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<DidDereferencingError>,
+}
+
+#[cw_serde]
+pub struct DidDereferencingResult {
+    pub content_stream: Option<DereferencedContent>,
+    pub content_metadata: DidDocumentMetadata,
+    pub dereferencing_metadata: DidDereferencingMetadata,
+}
+
+impl DidDereferencingResult {
+    pub fn dereferenced(content: DereferencedContent, content_metadata: DidDocumentMetadata) -> Self {
+        DidDereferencingResult {
+            dereferencing_metadata: DidDereferencingMetadata {
+                content_type: Some(content.content_type().to_string()),
+                error: None,
+            },
+            content_stream: Some(content),
+            content_metadata,
+        }
+    }
+
+    pub fn error(error: DidDereferencingError) -> Self {
+        DidDereferencingResult {
+            content_stream: None,
+            content_metadata: DidDocumentMetadata::default(),
+            dereferencing_metadata: DidDereferencingMetadata {
+                content_type: None,
+                error: Some(error),
+            },
+        }
+    }
 }
 
 pub(crate) trait Controllers {
     fn ensure_exist(
         &self,
         store: &dyn Storage,
-        did_docs: &Map<String, DidDocument>,
+        did_docs: &DidDocuments,
+        did_prefix: &str,
     ) -> Result<(), ContractError>;
 }
 
@@ -40,10 +570,11 @@ impl Controllers for Vec<Controller> {
     fn ensure_exist(
         &self,
         store: &dyn Storage,
-        did_docs: &Map<String, DidDocument>,
+        did_docs: &DidDocuments,
+        did_prefix: &str,
     ) -> Result<(), ContractError> {
         for c in self {
-            c.ensure_exist(store, did_docs)?;
+            c.ensure_exist(store, did_docs, did_prefix)?;
         }
         Ok(())
     }
@@ -56,6 +587,12 @@ impl DidDocument {
             .any(|service| &service.id == service_did)
     }
 
+    pub(crate) fn has_verification_method(&self, verification_method_did: &Did) -> bool {
+        self.verification_method
+            .iter()
+            .any(|method| &method.id == verification_method_did)
+    }
+
     // pub fn has_controller(&self, controller: &Controller) -> bool {
     //     self.controller.controllers().contains(controller)
     // }
@@ -68,6 +605,12 @@ impl DidDocument {
         self.controller.contains(controller)
     }
 
+    // Controllers are never duplicated, so equal lengths mean equal sets
+    pub(crate) fn has_same_controllers(&self, other: &DidDocument) -> bool {
+        self.controller.len() == other.controller.len()
+            && other.controller.iter().all(|c| self.has_controller(c))
+    }
+
     pub(crate) fn has_any_controller(&self) -> bool {
         !self.controller.is_empty()
     }
@@ -79,9 +622,16 @@ impl DidDocument {
         Ok(())
     }
 
+    pub(crate) fn ensure_active(&self) -> Result<(), ContractError> {
+        if self.deactivated {
+            return Err(ContractError::DidDocumentDeactivated(self.id.to_string()));
+        }
+        Ok(())
+    }
+
     pub(crate) fn ensure_not_self_controlled(&self) -> Result<(), ContractError> {
         for c in &self.controller {
-            if self.id.to_string() == c.to_string() {
+            if self.id.to_string() == *c {
                 return Err(ContractError::SelfControlledDidDocumentNotAllowed(self.id.to_string()));
             }
         }
@@ -108,123 +658,437 @@ impl DidDocument {
         Ok(())
     }
 
+    pub(crate) fn relationship(&self, relationship: &VerificationRelationship) -> &Vec<RelationshipMethod> {
+        match relationship {
+            VerificationRelationship::Authentication => &self.authentication,
+            VerificationRelationship::AssertionMethod => &self.assertion_method,
+            VerificationRelationship::KeyAgreement => &self.key_agreement,
+            VerificationRelationship::CapabilityInvocation => &self.capability_invocation,
+            VerificationRelationship::CapabilityDelegation => &self.capability_delegation,
+        }
+    }
+
+    pub(crate) fn relationship_mut(
+        &mut self,
+        relationship: &VerificationRelationship,
+    ) -> &mut Vec<RelationshipMethod> {
+        match relationship {
+            VerificationRelationship::Authentication => &mut self.authentication,
+            VerificationRelationship::AssertionMethod => &mut self.assertion_method,
+            VerificationRelationship::KeyAgreement => &mut self.key_agreement,
+            VerificationRelationship::CapabilityInvocation => &mut self.capability_invocation,
+            VerificationRelationship::CapabilityDelegation => &mut self.capability_delegation,
+        }
+    }
+
+    pub(crate) fn has_relationship_method(
+        &self,
+        relationship: &VerificationRelationship,
+        method_did: &Did,
+    ) -> bool {
+        self.relationship(relationship)
+            .iter()
+            .any(|m| m.id() == method_did)
+    }
+
+    // Selects the part of the document a DID URL of this document points to,
+    // following the DID Core dereferencing of fragments and of the `service`
+    // and `relativeRef` parameters
+    pub(crate) fn dereference(
+        &self,
+        url: &did_syntax::ParsedDidUrl,
+    ) -> Result<DereferencedContent, DidDereferencingError> {
+        // Paths are method specific and this method defines none
+        if url.path.is_some() {
+            return Err(DidDereferencingError::NotFound);
+        }
+        let params = did_syntax::parse_query(url.query.unwrap_or_default())
+            .map_err(|_| DidDereferencingError::InvalidDidUrl)?;
+        let mut service = None;
+        let mut relative_ref = None;
+        for (name, value) in params {
+            let param = match name.as_str() {
+                "service" => &mut service,
+                "relativeRef" => &mut relative_ref,
+                _ => return Err(DidDereferencingError::InvalidDidUrl),
+            };
+            if param.replace(value).is_some() {
+                return Err(DidDereferencingError::InvalidDidUrl);
+            }
+        }
+
+        let Some(service) = service else {
+            if relative_ref.is_some() {
+                return Err(DidDereferencingError::InvalidDidUrl);
+            }
+            return match url.fragment {
+                Some(fragment) => self.dereference_fragment(fragment),
+                None => Ok(DereferencedContent::DidDocument(self.clone())),
+            };
+        };
+        let service_did = Did::new(&format!("{}#{}", self.id, service));
+        let endpoint = self
+            .service
+            .iter()
+            .find(|s| s.id == service_did)
+            .and_then(|s| s.service_endpoint.uri())
+            .ok_or(DidDereferencingError::NotFound)?;
+        let mut url_string = match relative_ref {
+            Some(relative_ref) => resolve_relative_ref(endpoint, &relative_ref),
+            None => endpoint.to_string(),
+        };
+        // The fragment of the DID URL carries over unless the resolved URL has one
+        if let Some(fragment) = url.fragment {
+            if !url_string.contains('#') {
+                url_string = format!("{}#{}", url_string, fragment);
+            }
+        }
+        Ok(DereferencedContent::Url(url_string))
+    }
+
+    fn dereference_fragment(&self, fragment: &str) -> Result<DereferencedContent, DidDereferencingError> {
+        let id = Did::new(&format!("{}#{}", self.id, fragment));
+        if let Some(service) = self.service.iter().find(|s| s.id == id) {
+            return Ok(DereferencedContent::Service(service.clone()));
+        }
+        self.verification_method
+            .iter()
+            .chain(self.embedded_verification_methods())
+            .find(|m| m.id == id)
+            .map(|m| DereferencedContent::VerificationMethod(m.clone()))
+            .ok_or(DidDereferencingError::NotFound)
+    }
+
+    // Methods allowed to sign operations on the document itself
+    pub(crate) fn capability_invocation_method(&self, method_did: &Did) -> Option<&VerificationMethod> {
+        match self
+            .capability_invocation
+            .iter()
+            .find(|m| m.id() == method_did)?
+        {
+            RelationshipMethod::Embedded(method) => Some(method),
+            RelationshipMethod::Reference(id) => {
+                self.verification_method.iter().find(|m| &m.id == id)
+            }
+        }
+    }
+
+    fn embedded_verification_methods(&self) -> impl Iterator<Item = &VerificationMethod> {
+        VerificationRelationship::ALL
+            .iter()
+            .flat_map(|r| self.relationship(r).iter())
+            .filter_map(|m| match m {
+                RelationshipMethod::Embedded(method) => Some(method.as_ref()),
+                RelationshipMethod::Reference(_) => None,
+            })
+    }
+
+    pub(crate) fn is_verification_method_referenced(&self, method_did: &Did) -> bool {
+        VerificationRelationship::ALL.iter().any(|r| {
+            self.relationship(r)
+                .iter()
+                .any(|m| matches!(m, RelationshipMethod::Reference(id) if id == method_did))
+        })
+    }
+
+    // Resolves relative service and verification method ids against the
+    // document id, so only absolute DID URLs are stored
+    pub(crate) fn normalize_ids(&mut self) {
+        let did = self.id.clone();
+        for service in self.service.iter_mut() {
+            service.id = service.id.to_absolute(&did);
+        }
+        for method in self.verification_method.iter_mut() {
+            method.id = method.id.to_absolute(&did);
+        }
+        for relationship in VerificationRelationship::ALL.iter() {
+            for method in self.relationship_mut(relationship).iter_mut() {
+                method.normalize_id(&did);
+            }
+        }
+    }
+
+    // Services and verification methods can only be defined by the document
+    // they belong to
+    pub(crate) fn ensure_ids_owned(&self) -> Result<(), ContractError> {
+        for service in &self.service {
+            service.id.ensure_url_of(&self.id)?;
+        }
+        for method in self
+            .verification_method
+            .iter()
+            .chain(self.embedded_verification_methods())
+        {
+            method.id.ensure_url_of(&self.id)?;
+        }
+        Ok(())
+    }
+
+    // Verification method ids must be unique across the document, including
+    // the methods embedded in verification relationships
+    pub(crate) fn ensure_verification_methods_not_duplicated(&self) -> Result<(), ContractError> {
+        let mut seen = HashSet::new();
+        for method in self
+            .verification_method
+            .iter()
+            .chain(self.embedded_verification_methods())
+        {
+            if !seen.insert(method.id.to_string()) {
+                return Err(ContractError::DuplicatedVerificationMethod(
+                    method.id.to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn ensure_verification_relationships_consistent(&self) -> Result<(), ContractError> {
+        for relationship in VerificationRelationship::ALL.iter() {
+            let mut seen = HashSet::new();
+            for method in self.relationship(relationship) {
+                if !seen.insert(method.id().to_string()) {
+                    return Err(ContractError::DuplicatedVerificationRelationshipMethod(
+                        method.id().to_string(),
+                    ));
+                }
+                if let RelationshipMethod::Reference(id) = method {
+                    if !self.has_verification_method(id) {
+                        return Err(ContractError::VerificationRelationshipMethodNotFound(
+                            id.to_string(),
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Checks the document against every limit in one pass, counts and
+    // lengths first so the serialized size is only computed for documents
+    // that pass them
+    pub(crate) fn ensure_within_limits(&self, limits: &DocumentLimits) -> Result<(), ContractError> {
+        if self.controller.len() > limits.max_controllers as usize {
+            return Err(ContractError::TooManyControllers(
+                self.id.to_string(),
+                limits.max_controllers,
+            ));
+        }
+        if self.service.len() > limits.max_services as usize {
+            return Err(ContractError::TooManyServices(
+                self.id.to_string(),
+                limits.max_services,
+            ));
+        }
+        let methods: Vec<&VerificationMethod> = self
+            .verification_method
+            .iter()
+            .chain(self.embedded_verification_methods())
+            .collect();
+        if methods.len() > limits.max_verification_methods as usize {
+            return Err(ContractError::TooManyVerificationMethods(
+                self.id.to_string(),
+                limits.max_verification_methods,
+            ));
+        }
+
+        let ensure_id = |id: &str| {
+            if id.len() > limits.max_id_length as usize {
+                return Err(ContractError::IdTooLong(id.to_string(), limits.max_id_length));
+            }
+            Ok(())
+        };
+        let ensure_type = |t: &str| {
+            if t.len() > limits.max_type_length as usize {
+                return Err(ContractError::TypeTooLong(t.to_string(), limits.max_type_length));
+            }
+            Ok(())
+        };
+        ensure_id(self.id.as_str())?;
+        for c in &self.controller {
+            ensure_id(&c.to_string())?;
+        }
+        for s in &self.service {
+            ensure_id(s.id.as_str())?;
+            for t in s.a_type.types() {
+                ensure_type(t)?;
+            }
+            if s.service_endpoint.to_string().len() > limits.max_endpoint_length as usize {
+                return Err(ContractError::ServiceEndpointTooLong(
+                    s.id.to_string(),
+                    limits.max_endpoint_length,
+                ));
+            }
+        }
+        for m in methods {
+            ensure_id(m.id.as_str())?;
+            ensure_id(m.controller.as_str())?;
+            ensure_type(&m.a_type)?;
+        }
+
+        if to_json_vec(self)?.len() > limits.max_document_size as usize {
+            return Err(ContractError::DidDocumentTooLarge(
+                self.id.to_string(),
+                limits.max_document_size,
+            ));
+        }
+        Ok(())
+    }
+
     pub(crate) fn ensure_controllers_exist(
         &self,
         store: &mut dyn Storage,
-        did_docs: &Map<String, DidDocument>,
+        did_docs: &DidDocuments,
+        did_prefix: &str,
     ) -> Result<(), ContractError> {
-        self.controller.ensure_exist(store, did_docs)
+        self.controller.ensure_exist(store, did_docs, did_prefix)
     }
 
     pub(crate) fn ensure_signability(
         &self,
         store: &dyn Storage,
-        did_docs: &Map<String, DidDocument>,
+        did_docs: &DidDocuments,
+        did_prefix: &str,
+        limits: &TraversalLimits,
     ) -> Result<(), ContractError> {
-        let mut already_checked: HashSet<String> = HashSet::new();
-        self.can_be_signed(store, did_docs, &mut already_checked)
+        let mut visited: HashSet<String> = HashSet::new();
+        let (path, _) = walk_controllers(
+            store,
+            did_docs,
+            did_prefix,
+            limits,
+            self.id.as_str(),
+            &self.controller,
+            &mut visited,
+            |c| c.is_signable(did_prefix),
+        )?;
+        match path {
+            Some(_) => Ok(()),
+            None => Err(ContractError::DidDocumentUnsignable(self.id.to_string())),
+        }
     }
 
-    fn can_be_signed(
+    // The longest chain of controller links above the document, failing if
+    // walking all of them exceeds the traversal limits
+    pub(crate) fn delegation_depth(
         &self,
         store: &dyn Storage,
-        did_docs: &Map<String, DidDocument>,
-        already_checked: &mut HashSet<String>,
-    ) -> Result<(), ContractError> {
-        for c in &self.controller {
-            // for c in self.controller.controllers() {
-            if c.is_signable() {
-                return Ok(());
-            } else {
-                if already_checked.insert(c.to_string()) {
-                    let did_doc_result: Result<DidDocument, StdError> =
-                        did_docs.load(store, c.to_string());
-                    match did_doc_result {
-                        Ok(did_document) => {
-                            if let Ok(_) =
-                                did_document.can_be_signed(store, did_docs, already_checked)
-                            {
-                                return Ok(());
-                            }
-                        }
-                        Err(e) => match e {
-                            StdError::NotFound { .. } => (),
-                            _ => {
-                                return Err(ContractError::DidDocumentError(e));
-                            }
-                        },
-                    }
-                }
-            }
-        }
-        Err(ContractError::DidDocumentUnsignable(self.id.to_string()))
+        did_docs: &DidDocuments,
+        did_prefix: &str,
+        limits: &TraversalLimits,
+    ) -> Result<u32, ContractError> {
+        let mut visited: HashSet<String> = HashSet::new();
+        let (_, depth) = walk_controllers(
+            store,
+            did_docs,
+            did_prefix,
+            limits,
+            self.id.as_str(),
+            &self.controller,
+            &mut visited,
+            |_| false,
+        )?;
+        Ok(depth)
     }
 
+    // Without an authorization policy any single controller is enough,
+    // otherwise the signers together must reach the policy threshold
     pub(crate) fn authorize(
         &self,
         store: &dyn Storage,
-        did_docs: &Map<String, DidDocument>,
-        sender: &Controller,
+        did_docs: &DidDocuments,
+        did_prefix: &str,
+        limits: &TraversalLimits,
+        policy: Option<&AuthorizationPolicy>,
+        signers: &[Controller],
     ) -> Result<(), ContractError> {
-        if !self.is_controlled_by(store, did_docs, sender)? {
-            return Err(ContractError::Unauthorized(sender.to_string()));
+        if let Some(policy) = policy {
+            return policy.authorize(store, did_docs, did_prefix, limits, self, signers);
         }
-        Ok(())
+        for signer in signers {
+            if self.is_controlled_by(store, did_docs, did_prefix, limits, signer)? {
+                return Ok(());
+            }
+        }
+        Err(ContractError::Unauthorized(signers.to_event_data()))
     }
 
     pub(crate) fn is_controlled_by(
         &self,
         store: &dyn Storage,
-        did_docs: &Map<String, DidDocument>,
+        did_docs: &DidDocuments,
+        did_prefix: &str,
+        limits: &TraversalLimits,
         controller: &Controller,
     ) -> Result<bool, ContractError> {
         let mut already_checked: HashSet<String> = HashSet::new();
-        is_controller_of_internal(
+        let path = self.controller_path(
             store,
             did_docs,
-            &self.controller,
+            did_prefix,
+            limits,
             controller,
             &mut already_checked,
-        )
-        // self.is_controller_internal(store, did_docs, controller, &mut already_checked)
+        )?;
+        Ok(path.is_some())
     }
 
-    fn is_controller_internal(
+    // The shortest chain of controllers through which `controller` controls
+    // the document, starting with one of its own controllers and ending with
+    // `controller`. DID controllers looked into are added to `already_checked`.
+    pub(crate) fn controller_path(
         &self,
         store: &dyn Storage,
-        did_docs: &Map<String, DidDocument>,
+        did_docs: &DidDocuments,
+        did_prefix: &str,
+        limits: &TraversalLimits,
         controller: &Controller,
         already_checked: &mut HashSet<String>,
-    ) -> Result<bool, ContractError> {
-        is_controller_of_internal(
+    ) -> Result<Option<Vec<Controller>>, ContractError> {
+        let (path, _) = walk_controllers(
             store,
             did_docs,
+            did_prefix,
+            limits,
+            self.id.as_str(),
             &self.controller,
-            controller,
             already_checked,
-        )
+            |c| c == controller,
+        )?;
+        Ok(path)
     }
 
-    pub fn is_valid(&self, api: &dyn Api) -> bool {
-        self.id.is_valid() &&
+    pub fn is_valid(&self, api: &dyn Api, did_prefix: &str) -> bool {
+        self.id.is_valid(did_prefix) &&
             // !self.controller.controllers().iter().any(|c| !c.is_valid(api)) &&
-            !self.controller.iter().any(|c| !c.is_valid(api)) &&
-            !self.service.iter().any(|c| !c.is_valid())
+            !self.controller.iter().any(|c| !c.is_valid(api, did_prefix)) &&
+            !self.service.iter().any(|c| !c.is_valid(did_prefix)) &&
+            !self.verification_method.iter().any(|m| !m.is_valid(did_prefix)) &&
+            !VerificationRelationship::ALL
+                .iter()
+                .any(|r| self.relationship(r).iter().any(|m| !m.is_valid(did_prefix)))
     }
 
-    pub fn ensure_valid(&self, api: &dyn Api) -> Result<(), ContractError> {
-        self.id.ensure_valid()?;
+    pub fn ensure_valid(&self, api: &dyn Api, did_prefix: &str) -> Result<(), ContractError> {
+        self.id.ensure_valid(did_prefix)?;
         for c in &self.controller {
-            c.ensure_valid(api)?
+            c.ensure_valid(api, did_prefix)?
         }
         for s in &self.service {
-            s.ensure_valid()?
+            s.ensure_valid(did_prefix)?
+        }
+        for m in &self.verification_method {
+            m.ensure_valid(did_prefix)?
+        }
+        for r in VerificationRelationship::ALL.iter() {
+            for m in self.relationship(r) {
+                m.ensure_valid(did_prefix)?
+            }
         }
         Ok(())
     }
 }
 
-// pub fn ensure_controller_exist(store: &mut dyn Storage, did_docs: &Map<String, DidDocument>, controller: &Controller) -> Result<(), ContractError> {
+// pub fn ensure_controller_exist(store: &mut dyn Storage, did_docs: &DidDocuments, controller: &Controller) -> Result<(), ContractError> {
 //     if controller.is_did() {
 //         if !did_docs.has(store, controller.to_string()) {
 //             return Err(ContractError::DidControllerNotFound());
@@ -235,59 +1099,91 @@ impl DidDocument {
 
 pub fn is_controller_of(
     store: &dyn Storage,
-    did_docs: &Map<String, DidDocument>,
-    controllers: &Vec<Controller>,
+    did_docs: &DidDocuments,
+    did_prefix: &str,
+    limits: &TraversalLimits,
+    controllers: &[Controller],
     controller: &Controller,
 ) -> Result<bool, ContractError> {
     let mut already_checked: HashSet<String> = HashSet::new();
-    is_controller_of_internal(
+    let (path, _) = walk_controllers(
         store,
         did_docs,
+        did_prefix,
+        limits,
+        &controllers.to_event_data(),
         controllers,
-        controller,
         &mut already_checked,
-    )
+        |c| c == controller,
+    )?;
+    Ok(path.is_some())
 }
 
-fn is_controller_of_internal(
+// Breadth-first walk up the controller graph from `controllers`, the
+// controllers of `from`, until `found` accepts a controller. Returns the path
+// to that controller and the deepest level reached. Every level is checked
+// before the next one is entered, so a match is never hidden behind a limit
+// that only deeper levels hit.
+#[allow(clippy::too_many_arguments)]
+fn walk_controllers(
     store: &dyn Storage,
-    did_docs: &Map<String, DidDocument>,
-    controllers: &Vec<Controller>,
-    controller: &Controller,
-    already_checked: &mut HashSet<String>,
-) -> Result<bool, ContractError> {
-    for c in controllers {
-        // for c in self.controller.controllers() {
-        if c == controller {
-            return Ok(true);
-        }
-        if c.is_did() {
-            if already_checked.insert(c.to_string()) {
-                let did_doc_result: Result<DidDocument, StdError> =
-                    did_docs.load(store, c.to_string());
-                match did_doc_result {
-                    Ok(did_document) => {
-                        let is_controller = did_document.is_controller_internal(
-                            store,
-                            did_docs,
-                            controller,
-                            already_checked,
-                        )?;
-                        if is_controller {
-                            return Ok(true);
-                        }
-                    }
-                    Err(e) => match e {
-                        StdError::NotFound { .. } => (),
-                        _ => {
-                            return Err(ContractError::DidDocumentError(e));
-                        }
-                    },
+    did_docs: &DidDocuments,
+    did_prefix: &str,
+    limits: &TraversalLimits,
+    from: &str,
+    controllers: &[Controller],
+    visited: &mut HashSet<String>,
+    found: impl Fn(&Controller) -> bool,
+) -> Result<(Option<Vec<Controller>>, u32), ContractError> {
+    let mut queue: VecDeque<Vec<Controller>> =
+        controllers.iter().map(|c| vec![c.clone()]).collect();
+    let mut depth = 0;
+    while let Some(path) = queue.pop_front() {
+        let c = &path[path.len() - 1];
+        let is_did = c.is_did(did_prefix);
+        if is_did && visited.contains(&c.to_string()) {
+            continue;
+        }
+        if path.len() as u32 > limits.max_delegation_depth {
+            return Err(ContractError::DelegationDepthExceeded(
+                from.to_string(),
+                limits.max_delegation_depth,
+            ));
+        }
+        depth = depth.max(path.len() as u32);
+        if found(c) {
+            return Ok((Some(path), depth));
+        }
+        if !is_did {
+            continue;
+        }
+        visited.insert(c.to_string());
+        if visited.len() as u32 > limits.max_visited_nodes {
+            return Err(ContractError::TraversalBudgetExceeded(
+                from.to_string(),
+                limits.max_visited_nodes,
+            ));
+        }
+        let did_doc_result: Result<DidDocument, StdError> = did_docs.load(store, c.to_string());
+        match did_doc_result {
+            // Deactivated documents no longer control anything
+            Ok(did_document) if did_document.deactivated => (),
+            Ok(did_document) => {
+                for next in did_document.controller {
+                    let mut next_path = path.clone();
+                    next_path.push(next);
+                    queue.push_back(next_path);
                 }
             }
+            Err(e) => match e {
+                StdError::NotFound { .. } => (),
+                _ => {
+                    return Err(ContractError::DidDocumentError(e));
+                }
+            },
         }
     }
-    Ok(false)
+    Ok((None, depth))
 }
 
 // Custom serialization for controller field
@@ -341,63 +1237,614 @@ where
         }
     }
 
-    // Deserialize the input either as a single element or a sequence
-    deserializer.deserialize_any(ControllerVisitor)
+    // Deserialize the input either as a single element or a sequence
+    deserializer.deserialize_any(ControllerVisitor)
+}
+pub type DidDocuments = IndexedMap<String, DidDocument, DidDocumentIndexes>;
+
+pub struct DidDocumentIndexes {
+    pub controller: ControllerIndex,
+    pub service_type: ServiceIndex,
+    pub service_endpoint_host: ServiceIndex,
+}
+
+impl IndexList<DidDocument> for DidDocumentIndexes {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<DidDocument>> + '_> {
+        let v: Vec<&dyn Index<DidDocument>> = vec![
+            &self.controller,
+            &self.service_type,
+            &self.service_endpoint_host,
+        ];
+        Box::new(v.into_iter())
+    }
+}
+
+pub const fn did_documents() -> DidDocuments {
+    let indexes = DidDocumentIndexes {
+        controller: ControllerIndex::new("controller_index"),
+        service_type: ServiceIndex::new("service_type_index", service_types),
+        service_endpoint_host: ServiceIndex::new("service_endpoint_host_index", service_endpoint_hosts),
+    };
+    IndexedMap::new("dids", indexes)
+}
+
+// Why a controller can or cannot act on a document. `path` runs from the
+// document through its DID controllers to the candidate; without a path,
+// `frontier` lists the DID controllers that were looked into.
+#[cw_serde]
+pub struct AuthorizationExplanation {
+    pub authorized: bool,
+    pub path: Vec<Controller>,
+    pub frontier: Vec<Did>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+// A DID a controller can act on, reached through `depth` controller links
+#[cw_serde]
+pub struct ControlledDid {
+    pub did: Did,
+    pub depth: u32,
+}
+
+// A page of DIDs a controller can act on. Passing `next` as `start_after`
+// continues the walk; it is missing once every DID was returned.
+#[cw_serde]
+pub struct ControlledDidsPage {
+    pub dids: Vec<ControlledDid>,
+    pub next: Option<String>,
+}
+
+// Where a walk over controlled DIDs stopped: the documents still to be looked
+// into with their depth, the last DID taken from the first of them and every
+// DID reached so far. Handed out base64 encoded, so pages never walk the graph
+// again from the controller.
+#[cw_serde]
+pub struct ControlledDidsCursor {
+    pub queue: Vec<(String, u32)>,
+    pub after: Option<String>,
+    pub visited: Vec<String>,
+}
+
+impl ControlledDidsCursor {
+    pub fn start(controller: &str) -> Self {
+        ControlledDidsCursor {
+            queue: vec![(controller.to_string(), 0)],
+            after: None,
+            visited: vec![controller.to_string()],
+        }
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, ContractError> {
+        Binary::from_base64(cursor)
+            .and_then(from_json)
+            .map_err(|_| ContractError::InvalidCursor(cursor.to_string()))
+    }
+
+    pub fn encode(&self) -> StdResult<String> {
+        Ok(Binary::new(to_json_vec(self)?).to_base64())
+    }
+}
+
+// Indexes a document under every one of its controllers. Entries are keyed by
+// (controller, did), so both parts are length-prefixed and cannot collide.
+// Deactivated documents are not indexed.
+pub struct ControllerIndex {
+    dids: Map<(String, String), Empty>,
+}
+
+impl ControllerIndex {
+    pub const fn new(namespace: &'static str) -> Self {
+        ControllerIndex {
+            dids: Map::new(namespace),
+        }
+    }
+
+    pub fn dids<'c>(
+        &self,
+        store: &'c dyn Storage,
+        controller: &str,
+        min: Option<Bound<'_, String>>,
+        max: Option<Bound<'_, String>>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = StdResult<String>> + 'c> {
+        self.dids
+            .prefix(controller.to_string())
+            .keys(store, min, max, order)
+    }
+
+    pub fn is_empty(&self, store: &dyn Storage, controller: &str) -> bool {
+        self.dids(store, controller, None, None, Order::Ascending)
+            .next()
+            .is_none()
+    }
+}
+
+impl Index<DidDocument> for ControllerIndex {
+    fn save(&self, store: &mut dyn Storage, pk: &[u8], data: &DidDocument) -> StdResult<()> {
+        if data.deactivated {
+            return Ok(());
+        }
+        let did = String::from_vec(pk.to_vec())?;
+        for c in &data.controller {
+            self.dids.save(store, (c.to_string(), did.clone()), &Empty {})?;
+        }
+        Ok(())
+    }
+
+    fn remove(&self, store: &mut dyn Storage, pk: &[u8], old_data: &DidDocument) -> StdResult<()> {
+        let did = String::from_vec(pk.to_vec())?;
+        for c in &old_data.controller {
+            self.dids.remove(store, (c.to_string(), did.clone()));
+        }
+        Ok(())
+    }
+}
+
+// Indexes a document under every key derived from its services, like service
+// types or endpoint hosts. Entries are keyed by (key, did) as in the
+// controller index. Deactivated documents are not indexed.
+pub struct ServiceIndex {
+    dids: Map<(String, String), Empty>,
+    keys: fn(&Service) -> Vec<String>,
+}
+
+impl ServiceIndex {
+    pub const fn new(namespace: &'static str, keys: fn(&Service) -> Vec<String>) -> Self {
+        ServiceIndex {
+            dids: Map::new(namespace),
+            keys,
+        }
+    }
+
+    pub fn dids<'c>(
+        &self,
+        store: &'c dyn Storage,
+        key: &str,
+        min: Option<Bound<'_, String>>,
+        max: Option<Bound<'_, String>>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = StdResult<String>> + 'c> {
+        self.dids
+            .prefix(key.to_string())
+            .keys(store, min, max, order)
+    }
+
+    // A document with several services of the same key is indexed once
+    fn document_keys(&self, did_doc: &DidDocument) -> BTreeSet<String> {
+        did_doc.service.iter().flat_map(self.keys).collect()
+    }
+}
+
+impl Index<DidDocument> for ServiceIndex {
+    fn save(&self, store: &mut dyn Storage, pk: &[u8], data: &DidDocument) -> StdResult<()> {
+        if data.deactivated {
+            return Ok(());
+        }
+        let did = String::from_vec(pk.to_vec())?;
+        for key in self.document_keys(data) {
+            self.dids.save(store, (key, did.clone()), &Empty {})?;
+        }
+        Ok(())
+    }
+
+    fn remove(&self, store: &mut dyn Storage, pk: &[u8], old_data: &DidDocument) -> StdResult<()> {
+        let did = String::from_vec(pk.to_vec())?;
+        for key in self.document_keys(old_data) {
+            self.dids.remove(store, (key, did.clone()));
+        }
+        Ok(())
+    }
+}
+
+fn service_types(service: &Service) -> Vec<String> {
+    service.a_type.types().into_iter().map(String::from).collect()
+}
+
+fn service_endpoint_hosts(service: &Service) -> Vec<String> {
+    service.service_endpoint.hosts()
+}
+
+pub const SERVICE_ENDPOINT_SCHEMES: [&str; 6] = ["https", "http", "wss", "ws", "did", "ipfs"];
+// Keys of an endpoint map whose values are URIs, as in DIDComm endpoints
+pub const SERVICE_ENDPOINT_URI_KEYS: [&str; 2] = ["uri", "routingKeys"];
+
+#[cw_serde]
+pub struct Service {
+    pub id: Did,
+    #[serde(rename = "type")]
+    pub a_type: ServiceType,
+    pub service_endpoint: ServiceEndpoint,
+}
+
+impl Service {
+    pub fn is_valid(&self, did_prefix: &str) -> bool {
+        self.ensure_valid(did_prefix).is_ok()
+    }
+
+    pub fn ensure_valid(&self, did_prefix: &str) -> Result<(), ContractError> {
+        self.id.ensure_valid_url(did_prefix).map_err(|e| {
+            ContractError::ServiceIdFormatError(e.to_string())
+        })?;
+        if !self.a_type.is_valid() {
+            return Err(ContractError::ServiceTypeMissing(self.id.to_string()));
+        }
+        self.service_endpoint.ensure_valid().map_err(|e| {
+            ContractError::ServiceEndpointInvalid(self.id.to_string(), e)
+        })
+    }
+}
+
+// A single type or a set of types, as allowed by DID Core
+#[cw_serde]
+#[serde(untagged)]
+pub enum ServiceType {
+    Single(String),
+    Set(Vec<String>),
+}
+
+impl ServiceType {
+    pub fn is_valid(&self) -> bool {
+        match self {
+            ServiceType::Single(t) => !t.is_empty(),
+            ServiceType::Set(types) => !types.is_empty() && types.iter().all(|t| !t.is_empty()),
+        }
+    }
+
+    pub fn types(&self) -> Vec<&str> {
+        match self {
+            ServiceType::Single(t) => vec![t.as_str()],
+            ServiceType::Set(types) => types.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+impl From<&str> for ServiceType {
+    fn from(s: &str) -> Self {
+        ServiceType::Single(s.to_string())
+    }
+}
+
+impl From<String> for ServiceType {
+    fn from(s: String) -> Self {
+        ServiceType::Single(s)
+    }
+}
+
+impl fmt::Display for ServiceType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.types().join(","))
+    }
+}
+
+// A URI, a map or a set of URIs and maps, as allowed by DID Core. Plain
+// strings deserialize as `Uri`, so documents stored with string endpoints
+// read back unchanged.
+#[cw_serde]
+#[serde(untagged)]
+pub enum ServiceEndpoint {
+    Uri(String),
+    Map(BTreeMap<String, EndpointValue>),
+    Set(Vec<ServiceEndpoint>),
+}
+
+// Values inside an endpoint map, e.g. DIDComm `accept` or `routingKeys`
+#[cw_serde]
+#[serde(untagged)]
+pub enum EndpointValue {
+    String(String),
+    List(Vec<EndpointValue>),
+    Map(BTreeMap<String, EndpointValue>),
+}
+
+impl ServiceEndpoint {
+    pub fn ensure_valid(&self) -> Result<(), String> {
+        match self {
+            ServiceEndpoint::Uri(uri) => ensure_valid_endpoint_uri(uri),
+            ServiceEndpoint::Map(map) => ensure_valid_endpoint_map(map),
+            ServiceEndpoint::Set(endpoints) => {
+                if endpoints.is_empty() {
+                    return Err("empty set".to_string());
+                }
+                for endpoint in endpoints {
+                    if let ServiceEndpoint::Set(_) = endpoint {
+                        return Err("nested set".to_string());
+                    }
+                    endpoint.ensure_valid()?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ServiceEndpoint {
+    // Hosts of the URIs with an authority, lowercased and without user
+    // info or port
+    pub fn hosts(&self) -> Vec<String> {
+        match self {
+            ServiceEndpoint::Uri(uri) => uri_host(uri).into_iter().collect(),
+            ServiceEndpoint::Map(_) => vec![],
+            ServiceEndpoint::Set(endpoints) => endpoints.iter().flat_map(|e| e.hosts()).collect(),
+        }
+    }
+
+    // The URI a service URL is built from; the first one of a set
+    pub fn uri(&self) -> Option<&str> {
+        match self {
+            ServiceEndpoint::Uri(uri) => Some(uri),
+            ServiceEndpoint::Map(_) => None,
+            ServiceEndpoint::Set(endpoints) => endpoints.iter().find_map(|e| e.uri()),
+        }
+    }
+}
+
+impl From<&str> for ServiceEndpoint {
+    fn from(s: &str) -> Self {
+        ServiceEndpoint::Uri(s.to_string())
+    }
+}
+
+impl From<String> for ServiceEndpoint {
+    fn from(s: String) -> Self {
+        ServiceEndpoint::Uri(s)
+    }
+}
+
+// URIs as themselves, maps and sets as JSON
+impl fmt::Display for ServiceEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceEndpoint::Uri(uri) => f.write_str(uri),
+            _ => f.write_str(&to_json_string(self).map_err(|_| fmt::Error)?),
+        }
+    }
+}
+
+// URIs in a map are validated like a single URI endpoint; other values only
+// need to be non-empty
+fn ensure_valid_endpoint_map(map: &BTreeMap<String, EndpointValue>) -> Result<(), String> {
+    if map.is_empty() {
+        return Err("empty map".to_string());
+    }
+    for (key, value) in map {
+        ensure_valid_endpoint_value(value, SERVICE_ENDPOINT_URI_KEYS.contains(&key.as_str()))
+            .map_err(|e| format!("{} in {}", e, key))?;
+    }
+    Ok(())
+}
+
+fn ensure_valid_endpoint_value(value: &EndpointValue, is_uri: bool) -> Result<(), String> {
+    match value {
+        EndpointValue::String(s) if is_uri => ensure_valid_endpoint_uri(s),
+        EndpointValue::String(s) if s.is_empty() => Err("empty value".to_string()),
+        EndpointValue::String(_) => Ok(()),
+        EndpointValue::List(values) => values
+            .iter()
+            .try_for_each(|value| ensure_valid_endpoint_value(value, is_uri)),
+        EndpointValue::Map(map) => ensure_valid_endpoint_map(map),
+    }
 }
-pub struct DidDocumentIndexes<'a> {
-    pub controller: MultiIndex<'a, String, DidDocument, String>,
+
+// scheme ":" hier-part, with an allowed scheme and, for the web schemes,
+// a non-empty authority. The length is bound by the configured document
+// limits.
+fn ensure_valid_endpoint_uri(uri: &str) -> Result<(), String> {
+    if uri.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(format!("invalid character in {}", uri));
+    }
+    let Some((scheme, rest)) = uri.split_once(':') else {
+        return Err(format!("missing scheme in {}", uri));
+    };
+    if !SERVICE_ENDPOINT_SCHEMES.contains(&scheme.to_ascii_lowercase().as_str()) {
+        return Err(format!("scheme not allowed: {}", scheme));
+    }
+    if rest.is_empty() {
+        return Err(format!("empty uri after scheme: {}", uri));
+    }
+    if matches!(scheme.to_ascii_lowercase().as_str(), "https" | "http" | "wss" | "ws") {
+        let authority = rest
+            .strip_prefix("//")
+            .map(|r| r.split(['/', '?', '#']).next().unwrap_or_default())
+            .unwrap_or_default();
+        if authority.is_empty() {
+            return Err(format!("missing host in {}", uri));
+        }
+    }
+    Ok(())
 }
 
-impl<'a> IndexList<DidDocument> for DidDocumentIndexes<'a> {
-    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<DidDocument>> + '_> {
-        let v: Vec<&dyn Index<DidDocument>> = vec![&self.controller];
-        Box::new(v.into_iter())
+pub(crate) fn uri_host(uri: &str) -> Option<String> {
+    let (_, rest) = uri.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host_port = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+    let host = match host_port.strip_prefix('[') {
+        // IPv6 literal
+        Some(ipv6) => ipv6.split(']').next().unwrap_or_default(),
+        None => host_port.split(':').next().unwrap_or_default(),
+    };
+    if host.is_empty() {
+        return None;
     }
+    Some(host.to_ascii_lowercase())
 }
 
-pub fn did_documents<'a>() -> IndexedMap<&'a str, DidDocument, DidDocumentIndexes<'a>> {
-    let indexes = DidDocumentIndexes {
-        controller: MultiIndex::new(
-            // |_pk, d: &DidDocument| d.controller.controllers()[0].to_string(),
-            |_pk, d: &DidDocument| d.controller[0].to_string(),
-            "dids",
-            "did_controller",
-        ),
+// Resolves a relative reference against a service endpoint as in RFC 3986,
+// section 5.2, without removing dot segments
+fn resolve_relative_ref(base: &str, reference: &str) -> String {
+    let base = base.split('#').next().unwrap_or_default();
+    if reference.is_empty() {
+        return base.to_string();
+    }
+    if reference.starts_with('#') {
+        return format!("{}{}", base, reference);
+    }
+    let base = base.split('?').next().unwrap_or_default();
+    if reference.starts_with('?') {
+        return format!("{}{}", base, reference);
+    }
+    let scheme_end = base.find(':').map(|i| i + 1).unwrap_or_default();
+    if reference.starts_with("//") {
+        return format!("{}{}", &base[..scheme_end], reference);
+    }
+    let path_start = match base[scheme_end..].strip_prefix("//") {
+        Some(rest) => scheme_end + 2 + rest.find('/').unwrap_or(rest.len()),
+        None => scheme_end,
     };
-    IndexedMap::new("escrows", indexes)
+    let (authority, path) = base.split_at(path_start);
+    if reference.starts_with('/') {
+        return format!("{}{}", authority, reference);
+    }
+    match path.rfind('/') {
+        Some(i) => format!("{}{}{}", authority, &path[..=i], reference),
+        None if path_start > scheme_end => format!("{}/{}", authority, reference),
+        None => format!("{}{}", authority, reference),
+    }
 }
 
 #[cw_serde]
-pub struct Authentication {
+pub struct VerificationMethod {
     pub id: Did,
     #[serde(rename = "type")]
     pub a_type: String,
     pub controller: Did,
-    // pub c4e_address: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key_multibase: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key_jwk: Option<PublicKeyJwk>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blockchain_account_id: Option<String>,
+}
+
+impl VerificationMethod {
+    pub fn is_valid(&self, did_prefix: &str) -> bool {
+        self.ensure_valid(did_prefix).is_ok()
+    }
+
+    pub fn ensure_valid(&self, did_prefix: &str) -> Result<(), ContractError> {
+        self.id.ensure_valid_url(did_prefix).map_err(|e| {
+            ContractError::VerificationMethodIdFormatError(e.to_string())
+        })?;
+        self.controller.ensure_valid(did_prefix).map_err(|e| {
+            ContractError::VerificationMethodControllerFormatError(e.to_string())
+        })?;
+        if self.a_type.is_empty() {
+            return Err(ContractError::VerificationMethodTypeMissing(self.id.to_string()));
+        }
+        self.ensure_key_material()
+    }
+
+    // Exactly one non-empty public key representation is allowed
+    fn ensure_key_material(&self) -> Result<(), ContractError> {
+        let multibase = self.public_key_multibase.as_ref().map(|k| !k.is_empty());
+        let jwk = self.public_key_jwk.as_ref().map(|k| k.is_valid());
+        let account_id = self.blockchain_account_id.as_ref().map(|k| !k.is_empty());
+        let materials: Vec<bool> = [multibase, jwk, account_id].into_iter().flatten().collect();
+        if materials.len() != 1 || !materials[0] {
+            return Err(ContractError::VerificationMethodKeyMaterialError(
+                self.id.to_string(),
+            ));
+        }
+        Ok(())
+    }
 }
 
 #[cw_serde]
-pub struct Service {
-    pub id: Did,
-    #[serde(rename = "type")]
-    pub a_type: String,
-    pub service_endpoint: String,
+pub enum VerificationRelationship {
+    Authentication,
+    AssertionMethod,
+    KeyAgreement,
+    CapabilityInvocation,
+    CapabilityDelegation,
 }
 
-impl Service {
-    pub fn is_valid(&self) -> bool {
-        self.id.is_valid()
+impl VerificationRelationship {
+    pub const ALL: [VerificationRelationship; 5] = [
+        VerificationRelationship::Authentication,
+        VerificationRelationship::AssertionMethod,
+        VerificationRelationship::KeyAgreement,
+        VerificationRelationship::CapabilityInvocation,
+        VerificationRelationship::CapabilityDelegation,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerificationRelationship::Authentication => "authentication",
+            VerificationRelationship::AssertionMethod => "assertion_method",
+            VerificationRelationship::KeyAgreement => "key_agreement",
+            VerificationRelationship::CapabilityInvocation => "capability_invocation",
+            VerificationRelationship::CapabilityDelegation => "capability_delegation",
+        }
     }
+}
 
-    pub fn ensure_valid(&self) -> Result<(), ContractError> {
-        self.id.ensure_valid().map_err(|e| {
-            ContractError::ServiceIdFormatError(e.to_string())
-        })
+impl fmt::Display for VerificationRelationship {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// A verification relationship entry is either a reference to a method from
+// `verification_method` or a method embedded directly in the relationship
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum RelationshipMethod {
+    Reference(Did),
+    Embedded(Box<VerificationMethod>),
+}
+
+impl RelationshipMethod {
+    pub fn id(&self) -> &Did {
+        match self {
+            RelationshipMethod::Reference(id) => id,
+            RelationshipMethod::Embedded(method) => &method.id,
+        }
+    }
+
+    pub(crate) fn normalize_id(&mut self, did: &Did) {
+        match self {
+            RelationshipMethod::Reference(id) => *id = id.to_absolute(did),
+            RelationshipMethod::Embedded(method) => method.id = method.id.to_absolute(did),
+        }
+    }
+
+    pub fn is_valid(&self, did_prefix: &str) -> bool {
+        self.ensure_valid(did_prefix).is_ok()
+    }
+
+    pub fn ensure_valid(&self, did_prefix: &str) -> Result<(), ContractError> {
+        match self {
+            RelationshipMethod::Reference(id) => id.ensure_valid_url(did_prefix).map_err(|e| {
+                ContractError::VerificationMethodIdFormatError(e.to_string())
+            }),
+            RelationshipMethod::Embedded(method) => method.ensure_valid(did_prefix),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct PublicKeyJwk {
+    pub kty: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+}
+
+impl PublicKeyJwk {
+    pub fn is_valid(&self) -> bool {
+        !self.kty.is_empty()
     }
 }
 
 // #[derive(PartialEq, Debug, Clone, JsonSchema)]
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, JsonSchema)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, JsonSchema)]
 pub struct Did(String);
 
 impl cw_storage_plus::KeyDeserialize for Did {
@@ -428,13 +1875,13 @@ impl<'a> PrimaryKey<'a> for Did {
     type Suffix = Self;
     type SuperSuffix = Self;
 
-    fn key(&self) -> Vec<Key> {
+    fn key(&self) -> Vec<Key<'_>> {
         vec![Key::Ref(self.as_bytes())]
     }
 }
 
 impl<'a> Prefixer<'a> for Did {
-    fn prefix(&self) -> Vec<Key> {
+    fn prefix(&self) -> Vec<Key<'_>> {
         vec![Key::Ref(self.as_bytes())]
     }
 }
@@ -553,19 +2000,50 @@ impl Did {
         &self.0
     }
 
-    pub fn is_valid(&self) -> bool {
-        Did::is_did(&self.0)
+    pub fn is_valid(&self, did_prefix: &str) -> bool {
+        Did::is_did(&self.0, did_prefix)
     }
 
-    pub fn ensure_valid(&self) -> Result<(), ContractError> {
-        if !self.is_valid() {
-            return Err(ContractError::DidFormatError(self.0.clone()));
+    pub fn ensure_valid(&self, did_prefix: &str) -> Result<(), ContractError> {
+        Did::parse(&self.0, did_prefix)
+            .map_err(|e| ContractError::DidFormatError(self.0.clone(), e))
+    }
+
+    // Ids of services and verification methods may be DID URLs
+    pub fn ensure_valid_url(&self, did_prefix: &str) -> Result<(), ContractError> {
+        let url = did_syntax::parse_did_url(&self.0)
+            .map_err(|e| ContractError::DidFormatError(self.0.clone(), e))?;
+        url.did
+            .ensure_method(did_prefix)
+            .map_err(|e| ContractError::DidFormatError(self.0.clone(), e))
+    }
+
+    // A relative DID URL like `#key-1` is resolved against the document
+    pub fn to_absolute(&self, did: &Did) -> Did {
+        if self.0.starts_with('#') {
+            Did(format!("{}{}", did, self.0))
+        } else {
+            self.clone()
+        }
+    }
+
+    // The DID URL must point into the given document, not be the document
+    // itself nor belong to any other DID
+    pub fn ensure_url_of(&self, did: &Did) -> Result<(), ContractError> {
+        let url = did_syntax::parse_did_url(&self.0)
+            .map_err(|e| ContractError::DidFormatError(self.0.clone(), e))?;
+        if url.is_bare_did() || url.did_str(&self.0) != did.as_str() {
+            return Err(ContractError::DidUrlNotOwned(self.0.clone(), did.to_string()));
         }
         Ok(())
     }
 
-    pub fn is_did(s: &str) -> bool {
-        s.starts_with(DID_PREFIX)
+    pub fn is_did(s: &str, did_prefix: &str) -> bool {
+        Did::parse(s, did_prefix).is_ok()
+    }
+
+    fn parse(s: &str, did_prefix: &str) -> Result<(), DidSyntaxError> {
+        did_syntax::parse_did(s)?.ensure_method(did_prefix)
     }
 }
 
@@ -649,28 +2127,28 @@ impl Controller {
         &self.0
     }
 
-    pub fn is_valid(&self, api: &dyn Api) -> bool {
-        Controller::is_controller(api, &self.0)
+    pub fn is_valid(&self, api: &dyn Api, did_prefix: &str) -> bool {
+        Controller::is_controller(api, &self.0, did_prefix)
     }
 
-    pub fn ensure_valid(&self, api: &dyn Api) -> Result<(), ContractError> {
-        if !self.is_valid(api) {
+    pub fn ensure_valid(&self, api: &dyn Api, did_prefix: &str) -> Result<(), ContractError> {
+        if !self.is_valid(api, did_prefix) {
             return Err(ContractError::ControllerFormatError(self.0.clone()));
         }
         Ok(())
     }
 
-    pub fn is_did(&self) -> bool {
-        Did::is_did(&self.0)
+    pub fn is_did(&self, did_prefix: &str) -> bool {
+        Did::is_did(&self.0, did_prefix)
     }
 
-    pub fn is_signable(&self) -> bool {
-        !self.is_did()
+    pub fn is_signable(&self, did_prefix: &str) -> bool {
+        !self.is_did(did_prefix)
     }
 
-    pub fn is_controller(api: &dyn Api, s: &str) -> bool {
-        if let Err(_) = api.addr_validate(s) {
-            return Did::is_did(s);
+    pub fn is_controller(api: &dyn Api, s: &str, did_prefix: &str) -> bool {
+        if api.addr_validate(s).is_err() {
+            return Did::is_did(s, did_prefix);
         }
         true
     }
@@ -678,23 +2156,397 @@ impl Controller {
     pub(crate) fn ensure_exist(
         &self,
         store: &dyn Storage,
-        did_docs: &Map<String, DidDocument>,
+        did_docs: &DidDocuments,
+        did_prefix: &str,
+    ) -> Result<(), ContractError> {
+        if !self.is_did(did_prefix) {
+            return Ok(());
+        }
+        match did_docs.may_load(store, self.to_string()) {
+            Ok(Some(did_doc)) if did_doc.deactivated => {
+                Err(ContractError::DidDocumentDeactivated(self.0.clone()))
+            }
+            Ok(Some(_)) => Ok(()),
+            Ok(None) => Err(ContractError::DidControllerNotFound(self.0.clone())),
+            Err(e) => Err(ContractError::DidDocumentError(e)),
+        }
+    }
+
+}
+
+// M-of-N control over a document: the controllers approving a change must
+// together reach `threshold`. Only direct controllers of the document can be
+// weighted; an approver counts for every weighted controller it controls.
+#[cw_serde]
+pub struct AuthorizationPolicy {
+    pub threshold: u64,
+    pub weights: Vec<ControllerWeight>,
+}
+
+#[cw_serde]
+pub struct ControllerWeight {
+    pub controller: Controller,
+    pub weight: u64,
+}
+
+impl AuthorizationPolicy {
+    pub fn ensure_valid(
+        &self,
+        api: &dyn Api,
+        did_prefix: &str,
+        did_doc: &DidDocument,
     ) -> Result<(), ContractError> {
-        if self.is_did() {
-            if !did_docs.has(store, self.to_string()) {
-                return Err(ContractError::DidControllerNotFound(self.0.clone()));
+        if self.threshold == 0 {
+            return Err(ContractError::InvalidAuthorizationPolicy(
+                "threshold must be greater than zero".to_string(),
+            ));
+        }
+        let mut seen = HashSet::new();
+        self.total_weight(self.weights.iter())?;
+        for w in &self.weights {
+            w.controller.ensure_valid(api, did_prefix)?;
+            if w.weight == 0 {
+                return Err(ContractError::InvalidAuthorizationPolicy(format!(
+                    "zero weight for {}",
+                    w.controller
+                )));
+            }
+            if !seen.insert(w.controller.to_string()) {
+                return Err(ContractError::DuplicatedController(w.controller.to_string()));
+            }
+            if !did_doc.has_controller(&w.controller) {
+                return Err(ContractError::DidDocumentControllerNotExists(
+                    w.controller.to_string(),
+                ));
+            }
+        }
+        self.ensure_satisfiable(did_doc)
+    }
+
+    // Weighted controllers that were removed from the document no longer
+    // count, so the remaining ones must still be able to reach the threshold
+    pub(crate) fn ensure_satisfiable(&self, did_doc: &DidDocument) -> Result<(), ContractError> {
+        let total = self.total_weight(
+            self.weights
+                .iter()
+                .filter(|w| did_doc.has_controller(&w.controller)),
+        )?;
+        if total < self.threshold {
+            return Err(ContractError::AuthorizationPolicyUnsatisfiable(
+                did_doc.id.to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    // Weights are user supplied, so their sum must not overflow
+    fn total_weight<'a>(
+        &self,
+        mut weights: impl Iterator<Item = &'a ControllerWeight>,
+    ) -> Result<u64, ContractError> {
+        weights.try_fold(0u64, |total, w| {
+            total.checked_add(w.weight).ok_or_else(|| {
+                ContractError::InvalidAuthorizationPolicy("total weight overflows".to_string())
+            })
+        })
+    }
+
+    pub(crate) fn approved_weight(
+        &self,
+        store: &dyn Storage,
+        did_docs: &DidDocuments,
+        did_prefix: &str,
+        limits: &TraversalLimits,
+        did_doc: &DidDocument,
+        approvers: &[Controller],
+    ) -> Result<u64, ContractError> {
+        let mut approved = vec![];
+        for w in &self.weights {
+            if !did_doc.has_controller(&w.controller) {
+                continue;
             }
+            let weighted = vec![w.controller.clone()];
+            for approver in approvers {
+                if is_controller_of(
+                    store,
+                    did_docs,
+                    did_prefix,
+                    limits,
+                    &weighted,
+                    approver,
+                )? {
+                    approved.push(w);
+                    break;
+                }
+            }
+        }
+        self.total_weight(approved.into_iter())
+    }
+
+    pub(crate) fn authorize(
+        &self,
+        store: &dyn Storage,
+        did_docs: &DidDocuments,
+        did_prefix: &str,
+        limits: &TraversalLimits,
+        did_doc: &DidDocument,
+        approvers: &[Controller],
+    ) -> Result<(), ContractError> {
+        let weight = self.approved_weight(store, did_docs, did_prefix, limits, did_doc, approvers)?;
+        if weight < self.threshold {
+            return Err(ContractError::AuthorizationThresholdNotMet(
+                did_doc.id.to_string(),
+                weight,
+                self.threshold,
+            ));
         }
         Ok(())
     }
+}
+
+// Changes to a document that can be batched, proposed or signed. Fields
+// match the corresponding exec messages. Only `batch` can create documents,
+// the other paths need an existing one to authorize against.
+#[cw_serde]
+pub enum DidOperation {
+    CreateDidDocument {
+        did_doc: Box<DidDocument>,
+    },
+    UpdateDidDocument {
+        new_did_doc: Box<DidDocument>,
+    },
+    AddController {
+        did: Did,
+        controller: Controller,
+    },
+    DeleteController {
+        did: Did,
+        controller: Controller,
+    },
+    AddService {
+        did: Did,
+        service: Service,
+    },
+    DeleteService {
+        did: Did,
+        service_did: Did,
+    },
+    UpdateService {
+        did: Did,
+        service_did: Did,
+        a_type: Option<ServiceType>,
+        service_endpoint: Option<ServiceEndpoint>,
+    },
+    AddVerificationMethod {
+        did: Did,
+        verification_method: VerificationMethod,
+    },
+    DeleteVerificationMethod {
+        did: Did,
+        verification_method_did: Did,
+    },
+    AddVerificationRelationship {
+        did: Did,
+        relationship: VerificationRelationship,
+        method: RelationshipMethod,
+    },
+    DeleteVerificationRelationship {
+        did: Did,
+        relationship: VerificationRelationship,
+        method_did: Did,
+    },
+    DeactivateDidDocument {
+        did: Did,
+    },
+    DeleteDidDocument {
+        did: Did,
+        // Receives the share of the deposit the sender paid
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        refund_address: Option<String>,
+    },
+    SetAuthorizationPolicy {
+        did: Did,
+        policy: Option<AuthorizationPolicy>,
+    },
+}
+
+impl DidOperation {
+    // The document the operation changes
+    pub fn did(&self) -> &Did {
+        match self {
+            DidOperation::CreateDidDocument { did_doc } => &did_doc.id,
+            DidOperation::UpdateDidDocument { new_did_doc } => &new_did_doc.id,
+            DidOperation::AddController { did, .. }
+            | DidOperation::DeleteController { did, .. }
+            | DidOperation::AddService { did, .. }
+            | DidOperation::DeleteService { did, .. }
+            | DidOperation::UpdateService { did, .. }
+            | DidOperation::AddVerificationMethod { did, .. }
+            | DidOperation::DeleteVerificationMethod { did, .. }
+            | DidOperation::AddVerificationRelationship { did, .. }
+            | DidOperation::DeleteVerificationRelationship { did, .. }
+            | DidOperation::DeactivateDidDocument { did }
+            | DidOperation::DeleteDidDocument { did, .. }
+            | DidOperation::SetAuthorizationPolicy { did, .. } => did,
+        }
+    }
+
+    pub fn kind(&self) -> OperationKind {
+        match self {
+            DidOperation::CreateDidDocument { .. } => OperationKind::CreateDidDocument,
+            DidOperation::UpdateDidDocument { .. } => OperationKind::UpdateDidDocument,
+            DidOperation::AddController { .. } => OperationKind::AddController,
+            DidOperation::DeleteController { .. } => OperationKind::DeleteController,
+            DidOperation::AddService { .. } => OperationKind::AddService,
+            DidOperation::DeleteService { .. } => OperationKind::DeleteService,
+            DidOperation::UpdateService { .. } => OperationKind::UpdateService,
+            DidOperation::AddVerificationMethod { .. } => OperationKind::AddVerificationMethod,
+            DidOperation::DeleteVerificationMethod { .. } => {
+                OperationKind::DeleteVerificationMethod
+            }
+            DidOperation::AddVerificationRelationship { .. } => {
+                OperationKind::AddVerificationRelationship
+            }
+            DidOperation::DeleteVerificationRelationship { .. } => {
+                OperationKind::DeleteVerificationRelationship
+            }
+            DidOperation::DeactivateDidDocument { .. } => OperationKind::DeactivateDidDocument,
+            DidOperation::DeleteDidDocument { .. } => OperationKind::DeleteDidDocument,
+            DidOperation::SetAuthorizationPolicy { .. } => OperationKind::SetAuthorizationPolicy,
+        }
+    }
+}
+
+// The kinds of `DidOperation`, named like the corresponding exec messages
+#[cw_serde]
+#[derive(Copy)]
+pub enum OperationKind {
+    CreateDidDocument,
+    UpdateDidDocument,
+    AddController,
+    DeleteController,
+    AddService,
+    DeleteService,
+    UpdateService,
+    AddVerificationMethod,
+    DeleteVerificationMethod,
+    AddVerificationRelationship,
+    DeleteVerificationRelationship,
+    DeactivateDidDocument,
+    DeleteDidDocument,
+    SetAuthorizationPolicy,
+}
+
+impl OperationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OperationKind::CreateDidDocument => "create_did_document",
+            OperationKind::UpdateDidDocument => "update_did_document",
+            OperationKind::AddController => "add_controller",
+            OperationKind::DeleteController => "delete_controller",
+            OperationKind::AddService => "add_service",
+            OperationKind::DeleteService => "delete_service",
+            OperationKind::UpdateService => "update_service",
+            OperationKind::AddVerificationMethod => "add_verification_method",
+            OperationKind::DeleteVerificationMethod => "delete_verification_method",
+            OperationKind::AddVerificationRelationship => "add_verification_relationship",
+            OperationKind::DeleteVerificationRelationship => "delete_verification_relationship",
+            OperationKind::DeactivateDidDocument => "deactivate_did_document",
+            OperationKind::DeleteDidDocument => "delete_did_document",
+            OperationKind::SetAuthorizationPolicy => "set_authorization_policy",
+        }
+    }
+}
+
+impl fmt::Display for OperationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// Writes stopped by the admin: all of them, or only operations of the
+// listed kinds wherever they are applied, proposed or approved
+#[cw_serde]
+pub enum PauseScope {
+    All,
+    Operations(Vec<OperationKind>),
+}
+
+impl PauseScope {
+    pub(crate) fn ensure_allows(&self, kind: Option<OperationKind>) -> Result<(), ContractError> {
+        match (self, kind) {
+            (PauseScope::All, _) => Err(ContractError::Paused),
+            (PauseScope::Operations(kinds), Some(kind)) if kinds.contains(&kind) => {
+                Err(ContractError::OperationPaused(kind.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn to_event_data(&self) -> String {
+        match self {
+            PauseScope::All => "all".to_string(),
+            PauseScope::Operations(kinds) => kinds
+                .iter()
+                .map(|k| k.as_str())
+                .collect::<Vec<&str>>()
+                .join(","),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct Proposal {
+    pub id: u64,
+    pub did: Did,
+    pub operation: DidOperation,
+    pub proposer: Controller,
+    pub approvals: Vec<Controller>,
+    pub expires: Timestamp,
+}
+
+impl Proposal {
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        block.time >= self.expires
+    }
+}
+
+// The canonical message signed for `execute_signed`, serialized as JSON in
+// field order. Chain id and contract address keep a signature from being
+// replayed on another deployment, the nonce from being replayed on this one.
+#[cw_serde]
+pub struct SignDoc {
+    pub chain_id: String,
+    pub contract: String,
+    pub nonce: u64,
+    pub operation: DidOperation,
+}
+
+pub type Proposals = IndexedMap<u64, Proposal, ProposalIndexes>;
+
+pub struct ProposalIndexes {
+    pub did: MultiIndex<'static, String, Proposal, u64>,
+}
+
+impl IndexList<Proposal> for ProposalIndexes {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Proposal>> + '_> {
+        let v: Vec<&dyn Index<Proposal>> = vec![&self.did];
+        Box::new(v.into_iter())
+    }
+}
 
+pub const fn proposals() -> Proposals {
+    let indexes = ProposalIndexes {
+        did: MultiIndex::new(|_pk, p: &Proposal| p.did.to_string(), "proposals", "proposals__did"),
+    };
+    IndexedMap::new("proposals", indexes)
 }
 
 pub trait ToEventData {
     fn to_event_data(&self) -> String;
 }
 
-impl ToEventData for Vec<Controller> {
+impl ToEventData for [Controller] {
     fn to_event_data(&self) -> String {
         self.iter()
             .map(|c| c.to_string())
@@ -712,6 +2564,24 @@ impl ToEventData for Vec<Service> {
     }
 }
 
+impl ToEventData for Vec<RelationshipMethod> {
+    fn to_event_data(&self) -> String {
+        self.iter()
+            .map(|m| m.id().to_string())
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+}
+
+impl ToEventData for Vec<VerificationMethod> {
+    fn to_event_data(&self) -> String {
+        self.iter()
+            .map(|m| m.id.to_string())
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -722,8 +2592,8 @@ mod tests {
         // Create a sample Service struct
         let service = Service {
             id: Did::new("service1"),
-            a_type: "SomeServiceType".to_string(),
-            service_endpoint: "https://example.com".to_string(),
+            a_type: "SomeServiceType".into(),
+            service_endpoint: "https://example.com".into(),
         };
 
         // Serialize to JSON
@@ -742,42 +2612,250 @@ mod tests {
         // Deserialize back to struct
         let deserialized: Service = from_str(&serialized).unwrap();
         assert_eq!(deserialized.id, Did::new("service1"));
-        assert_eq!(deserialized.a_type, "SomeServiceType");
-        assert_eq!(deserialized.service_endpoint, "https://example.com");
+        assert_eq!(deserialized.a_type, "SomeServiceType".into());
+        assert_eq!(deserialized.service_endpoint, "https://example.com".into());
+    }
+
+    #[test]
+    fn test_structured_service_serialization() {
+        let json = r#"{
+            "id": "did:c4e:abc#didcomm",
+            "type": ["DIDCommMessaging", "LinkedDomains"],
+            "service_endpoint": [
+                "https://example.com/path",
+                {"uri": "wss://example.com", "accept": ["didcomm/v2"]}
+            ]
+        }"#;
+        let service: Service = cosmwasm_std::from_json(json).unwrap();
+        assert_eq!(
+            ServiceType::Set(vec!["DIDCommMessaging".to_string(), "LinkedDomains".to_string()]),
+            service.a_type
+        );
+        assert_eq!(
+            ServiceEndpoint::Set(vec![
+                ServiceEndpoint::Uri("https://example.com/path".to_string()),
+                ServiceEndpoint::Map(BTreeMap::from([
+                    (
+                        "accept".to_string(),
+                        EndpointValue::List(vec![EndpointValue::String("didcomm/v2".to_string())])
+                    ),
+                    (
+                        "uri".to_string(),
+                        EndpointValue::String("wss://example.com".to_string())
+                    ),
+                ])),
+            ]),
+            service.service_endpoint
+        );
+        assert!(service.is_valid("did:c4e:"));
+
+        // Stored string values read back unchanged
+        let stored = to_json_string(&Service {
+            id: Did::new("did:c4e:abc#service"),
+            a_type: "Service".into(),
+            service_endpoint: "https://example.com".into(),
+        })
+        .unwrap();
+        assert_eq!(
+            r#"{"id":"did:c4e:abc#service","type":"Service","service_endpoint":"https://example.com"}"#,
+            stored
+        );
     }
 
     #[test]
-    fn test_authentocation_serialization() {
-        // Create a sample Authentocation struct
-        let authentocation = Authentication {
-            id: Did::new("auth1"),
-            a_type: "SomeAuthType".to_string(),
+    fn test_service_validation() {
+        let service = |a_type: ServiceType, service_endpoint: ServiceEndpoint| Service {
+            id: Did::new("did:c4e:abc#service"),
+            a_type,
+            service_endpoint,
+        };
+
+        assert!(service("Service".into(), "https://example.com".into()).is_valid("did:c4e:"));
+        assert!(service("Service".into(), "did:c4e:other".into()).is_valid("did:c4e:"));
+        assert_eq!(
+            Err(ContractError::ServiceTypeMissing("did:c4e:abc#service".to_string())),
+            service("".into(), "https://example.com".into()).ensure_valid("did:c4e:")
+        );
+        assert!(!service(ServiceType::Set(vec![]), "https://example.com".into()).is_valid("did:c4e:"));
+
+        let invalid = |endpoint: ServiceEndpoint| {
+            match service("Service".into(), endpoint).ensure_valid("did:c4e:") {
+                Err(ContractError::ServiceEndpointInvalid(_, reason)) => reason,
+                other => panic!("unexpected result: {:?}", other),
+            }
+        };
+        assert_eq!("missing scheme in example.com", invalid("example.com".into()));
+        assert_eq!("scheme not allowed: ftp", invalid("ftp://example.com".into()));
+        assert_eq!("missing host in https:///path", invalid("https:///path".into()));
+        assert_eq!("invalid character in https://a b", invalid("https://a b".into()));
+        assert_eq!("empty map", invalid(ServiceEndpoint::Map(BTreeMap::new())));
+        let map = |key: &str, value: EndpointValue| {
+            ServiceEndpoint::Map(BTreeMap::from([(key.to_string(), value)]))
+        };
+        assert_eq!(
+            "scheme not allowed: ftp in uri",
+            invalid(map("uri", EndpointValue::String("ftp://example.com".to_string())))
+        );
+        assert_eq!(
+            "missing scheme in key-1 in routingKeys",
+            invalid(map(
+                "routingKeys",
+                EndpointValue::List(vec![EndpointValue::String("key-1".to_string())])
+            ))
+        );
+        assert_eq!(
+            "missing host in https:// in uri in nested",
+            invalid(map(
+                "nested",
+                EndpointValue::Map(BTreeMap::from([(
+                    "uri".to_string(),
+                    EndpointValue::String("https://".to_string())
+                )]))
+            ))
+        );
+        assert_eq!(
+            "empty value in accept",
+            invalid(map(
+                "accept",
+                EndpointValue::List(vec![EndpointValue::String("".to_string())])
+            ))
+        );
+        assert!(service(
+            "DIDCommMessaging".into(),
+            map(
+                "routingKeys",
+                EndpointValue::List(vec![EndpointValue::String("did:c4e:mediator#key-1".to_string())])
+            )
+        )
+        .is_valid("did:c4e:"));
+        assert_eq!("empty set", invalid(ServiceEndpoint::Set(vec![])));
+        assert_eq!(
+            "nested set",
+            invalid(ServiceEndpoint::Set(vec![ServiceEndpoint::Set(vec![
+                "https://example.com".into()
+            ])]))
+        );
+        assert_eq!(
+            "scheme not allowed: ftp",
+            invalid(ServiceEndpoint::Set(vec![
+                "https://example.com".into(),
+                "ftp://example.com".into()
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_service_endpoint_hosts() {
+        let endpoint: ServiceEndpoint = "https://User@Files.Example.com:8443/a?b#c".into();
+        assert_eq!(vec!["files.example.com".to_string()], endpoint.hosts());
+        let endpoint = ServiceEndpoint::Set(vec![
+            "wss://[::1]:9000/ws".into(),
+            "did:c4e:abc".into(),
+            "ipfs://bafy".into(),
+        ]);
+        assert_eq!(vec!["::1".to_string(), "bafy".to_string()], endpoint.hosts());
+        assert!(ServiceEndpoint::Map(BTreeMap::new()).hosts().is_empty());
+    }
+
+    #[test]
+    fn test_resolve_relative_ref() {
+        let base = "https://example.com/a/b?x=1#f";
+        assert_eq!("https://example.com/a/b?x=1", resolve_relative_ref(base, ""));
+        assert_eq!("https://example.com/a/b?x=1#g", resolve_relative_ref(base, "#g"));
+        assert_eq!("https://example.com/a/b?y=2", resolve_relative_ref(base, "?y=2"));
+        assert_eq!("https://example.com/c.json", resolve_relative_ref(base, "/c.json"));
+        assert_eq!("https://example.com/a/c.json", resolve_relative_ref(base, "c.json"));
+        assert_eq!("https://other.org/c", resolve_relative_ref(base, "//other.org/c"));
+        assert_eq!(
+            "https://example.com/c.json",
+            resolve_relative_ref("https://example.com", "c.json")
+        );
+        assert_eq!("ipfs:c.json", resolve_relative_ref("ipfs:Qm", "c.json"));
+    }
+
+    #[test]
+    fn test_verification_method_serialization() {
+        let method = VerificationMethod {
+            id: Did::new("key1"),
+            a_type: "Ed25519VerificationKey2020".to_string(),
             controller: Did::new("controller1"),
-            // c4e_address: "address1".to_string(),
+            public_key_multibase: Some("z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH".to_string()),
+            public_key_jwk: None,
+            blockchain_account_id: None,
         };
 
-        // Serialize to JSON
-        let serialized = serde_json::to_string(&authentocation).unwrap();
+        // Absent key representations are not serialized
+        let serialized = to_string(&method).unwrap();
         let expected_json = json!({
-            "id": "auth1",
-            "type": "SomeAuthType",
+            "id": "key1",
+            "type": "Ed25519VerificationKey2020",
             "controller": "controller1",
-            // "c4e_address": "address1"
+            "public_key_multibase": "z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH"
         });
 
-        // Deserialize both to serde_json::Value to compare unordered JSON
         let serialized_value: serde_json::Value = serde_json::from_str(&serialized).unwrap();
-        // let expected_value: serde_json::Value = expected_json;
+        assert_eq!(serialized_value, expected_json);
+
+        let deserialized: VerificationMethod = from_str(&serialized).unwrap();
+        assert_eq!(deserialized, method);
+    }
+
+    #[test]
+    fn test_verification_relationship_serialization() {
+        let method = VerificationMethod {
+            id: Did::new("key2"),
+            a_type: "Ed25519VerificationKey2020".to_string(),
+            controller: Did::new("did1"),
+            public_key_multibase: Some("z6Mk".to_string()),
+            public_key_jwk: None,
+            blockchain_account_id: None,
+        };
+        let did = DidDocument {
+            id: Did::new("did1"),
+            controller: vec![Controller::new("controller1")],
+            service: vec![],
+            authentication: vec![
+                RelationshipMethod::Reference(Did::new("key1")),
+                RelationshipMethod::Embedded(Box::new(method)),
+            ],
+            ..Default::default()
+        };
+
+        // References are plain strings, embedded methods are objects
+        let serialized = to_string(&did).unwrap();
+        let expected_json = json!({
+            "id": "did1",
+            "controller": "controller1",
+            "service": [],
+            "authentication": [
+                "key1",
+                {
+                    "id": "key2",
+                    "type": "Ed25519VerificationKey2020",
+                    "controller": "did1",
+                    "public_key_multibase": "z6Mk"
+                }
+            ]
+        });
 
-        // Compare the values, which ignores order of keys
+        let serialized_value: serde_json::Value = serde_json::from_str(&serialized).unwrap();
         assert_eq!(serialized_value, expected_json);
 
-        // Deserialize back to struct
-        let deserialized: Authentication = serde_json::from_str(&serialized).unwrap();
-        assert_eq!(deserialized.id, Did::new("auth1"));
-        assert_eq!(deserialized.a_type, "SomeAuthType");
-        assert_eq!(deserialized.controller, Did::new("controller1"));
-        // assert_eq!(deserialized.c4e_address, "address1");
+        let deserialized: DidDocument = from_str(&serialized).unwrap();
+        assert_eq!(deserialized, did);
+    }
+
+    #[test]
+    fn test_did_document_without_verification_method_deserialization() {
+        // Documents stored before verification methods were introduced
+        let stored = json!({
+            "id": "did1",
+            "controller": "controller1",
+            "service": []
+        });
+
+        let deserialized: DidDocument = from_str(&stored.to_string()).unwrap();
+        assert!(deserialized.verification_method.is_empty());
     }
 
     #[test]
@@ -785,19 +2863,20 @@ mod tests {
         // Create a sample Did struct with multiple services
         let service1 = Service {
             id: Did::new("service1"),
-            a_type: "ServiceType1".to_string(),
-            service_endpoint: "https://service1.com".to_string(),
+            a_type: "ServiceType1".into(),
+            service_endpoint: "https://service1.com".into(),
         };
         let service2 = Service {
             id: Did::new("service2"),
-            a_type: "ServiceType2".to_string(),
-            service_endpoint: "https://service2.com".to_string(),
+            a_type: "ServiceType2".into(),
+            service_endpoint: "https://service2.com".into(),
         };
         let did = DidDocument {
             id: Did::new("did1"),
             // controller: Controllers(vec![Controller::new("controller1")]),
             controller: vec![Controller::new("controller1")],
             service: vec![service1, service2],
+            ..Default::default()
         };
 
         // Serialize to JSON
@@ -835,16 +2914,16 @@ mod tests {
         );
         assert_eq!(deserialized.service.len(), 2);
         assert_eq!(deserialized.service[0].id, Did::new("service1"));
-        assert_eq!(deserialized.service[0].a_type, "ServiceType1");
+        assert_eq!(deserialized.service[0].a_type, "ServiceType1".into());
         assert_eq!(
             deserialized.service[0].service_endpoint,
-            "https://service1.com"
+            "https://service1.com".into()
         );
         assert_eq!(deserialized.service[1].id, Did::new("service2"));
-        assert_eq!(deserialized.service[1].a_type, "ServiceType2");
+        assert_eq!(deserialized.service[1].a_type, "ServiceType2".into());
         assert_eq!(
             deserialized.service[1].service_endpoint,
-            "https://service2.com"
+            "https://service2.com".into()
         );
     }
 
@@ -853,13 +2932,13 @@ mod tests {
         // Create a sample Did struct with multiple services
         let service1 = Service {
             id: Did::new("service1"),
-            a_type: "ServiceType1".to_string(),
-            service_endpoint: "https://service1.com".to_string(),
+            a_type: "ServiceType1".into(),
+            service_endpoint: "https://service1.com".into(),
         };
         let service2 = Service {
             id: Did::new("service2"),
-            a_type: "ServiceType2".to_string(),
-            service_endpoint: "https://service2.com".to_string(),
+            a_type: "ServiceType2".into(),
+            service_endpoint: "https://service2.com".into(),
         };
         let did = DidDocument {
             id: Did::new("did1"),
@@ -869,6 +2948,7 @@ mod tests {
                 Controller::new("controller2"),
             ],
             service: vec![service1, service2],
+            ..Default::default()
         };
 
         // Serialize to JSON
@@ -910,16 +2990,16 @@ mod tests {
         );
         assert_eq!(deserialized.service.len(), 2);
         assert_eq!(deserialized.service[0].id, Did::new("service1"));
-        assert_eq!(deserialized.service[0].a_type, "ServiceType1");
+        assert_eq!(deserialized.service[0].a_type, "ServiceType1".into());
         assert_eq!(
             deserialized.service[0].service_endpoint,
-            "https://service1.com"
+            "https://service1.com".into()
         );
         assert_eq!(deserialized.service[1].id, Did::new("service2"));
-        assert_eq!(deserialized.service[1].a_type, "ServiceType2");
+        assert_eq!(deserialized.service[1].a_type, "ServiceType2".into());
         assert_eq!(
             deserialized.service[1].service_endpoint,
-            "https://service2.com"
+            "https://service2.com".into()
         );
     }
 }
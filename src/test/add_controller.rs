@@ -3,7 +3,8 @@ use sylvia::multitest::App;
 
 use crate::{
     contract::sv::mt::{CodeId, DidContractProxy},
-    state::{Controller, Did, DidDocument, DID_PREFIX},
+    state::{Controller, Did, DidDocument},
+    test::{DID_METHOD, DID_PREFIX},
 };
 
 #[test]
@@ -14,13 +15,14 @@ fn add_valid_address_controller() {
     let owner = "owner".into_addr();
     let new_controller = "new_controller".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "add_controller_did");
     let mut original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -76,7 +78,7 @@ fn add_valid_did_controller() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the first DID Document (controller DID)
     let controller_did = format!("{}{}", DID_PREFIX, "controller_did");
@@ -84,6 +86,7 @@ fn add_valid_did_controller() {
         id: Did::new(&controller_did),
         controller: vec![owner.to_string().into()], // Normal address as controller
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -97,6 +100,7 @@ fn add_valid_did_controller() {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -150,13 +154,14 @@ fn add_duplicate_controller() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "duplicate_controller_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -171,7 +176,7 @@ fn add_duplicate_controller() {
         .call(&owner);
     assert!(result.is_err(), "Expected Err, but got an Ok");
     assert_eq!(
-        format!("Did document controller already exists: {}", owner.to_string()),
+        format!("Did document controller already exists: {}", owner),
         result.err().unwrap().to_string()
     );
 }
@@ -184,13 +189,14 @@ fn add_nonexistent_did_as_controller() {
     let owner = "owner".into_addr();
     let nonexistent_did = format!("{}{}", DID_PREFIX, "nonexistent_did");
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "add_nonexistent_controller_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -217,13 +223,14 @@ fn add_self_controlled_did_as_controller() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "self_controlled_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -251,7 +258,7 @@ fn add_controller_to_non_existing_did_document() {
     let owner = "owner".into_addr();
     let new_controller = "new_controller".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Define a non-existing DID
     let non_existing_did = format!("{}{}", DID_PREFIX, "non_existing_did");
@@ -277,7 +284,7 @@ fn add_controller_to_did_with_invalid_format() {
     let owner = "owner".into_addr();
     let new_controller = "new_controller".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Define a DID with an invalid format
     let invalid_did = "invalid_did_format";
@@ -290,7 +297,7 @@ fn add_controller_to_did_with_invalid_format() {
 
     // Verify the error message
     assert_eq!(
-        format!("Did format error: {}", invalid_did),
+        format!("Did format error: {} (missing did scheme)", invalid_did),
         result.err().unwrap().to_string()
     );
 }
@@ -302,13 +309,14 @@ fn add_invalid_format_controller() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "valid_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -340,13 +348,14 @@ fn add_controller_unauthorized() {
     let unauthorized_user = "unauthorized_user".into_addr();
     let new_controller = "new_controller".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "unauthorized_add_controller_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -363,7 +372,7 @@ fn add_controller_unauthorized() {
 
     // Verify the error message
     assert_eq!(
-        format!("Unauthorized: {}", unauthorized_user.to_string()),
+        format!("Unauthorized: {}", unauthorized_user),
         result.err().unwrap().to_string()
     );
 
@@ -383,13 +392,14 @@ fn add_controller_with_index_verification() {
     let owner = "owner".into_addr();
     let new_controller = "new_controller".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "indexed_did");
     let mut original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -461,7 +471,7 @@ fn add_as_did_controller_success() {
     let owner = "owner".into_addr();
     let owner2 = "owner2".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the first DID Document (controller DID)
     let controller_did = format!("{}{}", DID_PREFIX, "controller_did");
@@ -469,6 +479,7 @@ fn add_as_did_controller_success() {
         id: Did::new(&controller_did),
         controller: vec![owner.to_string().into()], // Normal address as controller
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -482,6 +493,7 @@ fn add_as_did_controller_success() {
         id: Did::new(&did),
         controller: vec![controller_did.clone().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -490,7 +502,7 @@ fn add_as_did_controller_success() {
     assert!(result.is_ok(), "Expected Ok, but got an Err");
 
     let result = contract
-        .add_controller(Did::new(&did), Controller::new(&owner2.to_string()))
+        .add_controller(Did::new(&did), Controller::new(owner2.as_ref()))
         .call(&owner);
     // if result.is_err() {
     //     println!("Error: {}", result.err().unwrap());
@@ -526,7 +538,7 @@ fn add_as_did_controller_success() {
     assert_eq!(res.events[1].attributes[2].value, owner2.to_string());
 
     // Verify the updated DID Document
-    original_did_doc.controller.push(Controller::new(&owner2.to_string()));
+    original_did_doc.controller.push(Controller::new(owner2.as_ref()));
     let updated_did_doc = contract.get_did_document(Did::new(&did)).unwrap();
     assert_eq!(original_did_doc, updated_did_doc, "DID Document was not updated correctly");
 }
\ No newline at end of file
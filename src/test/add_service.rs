@@ -1,9 +1,12 @@
+use std::collections::BTreeMap;
+
 use sylvia::cw_multi_test::IntoAddr;
 use sylvia::multitest::App;
 
 use crate::{
     contract::sv::mt::{CodeId, DidContractProxy},
-    state::{Did, DidDocument, Service, DID_PREFIX},
+    state::{Did, DidDocument, EndpointValue, Service, ServiceEndpoint, ServiceType},
+    test::{DID_METHOD, DID_PREFIX},
 };
 
 #[test]
@@ -13,13 +16,14 @@ fn add_valid_service() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "valid_service_did");
     let mut original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -30,9 +34,9 @@ fn add_valid_service() {
 
     // Add a valid service
     let new_service = Service {
-        id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-        a_type: "ServiceType".to_string(),
-        service_endpoint: "http://example.com".to_string(),
+        id: Did::new(&format!("{}#{}", did, "service1")),
+        a_type: "ServiceType".into(),
+        service_endpoint: "http://example.com".into(),
     };
 
     let result = contract
@@ -56,17 +60,18 @@ fn add_duplicate_service() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "duplicate_service_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            a_type: "ServiceType".to_string(),
-            service_endpoint: "http://example.com".to_string(),
+            id: Did::new(&format!("{}#{}", did, "service1")),
+            a_type: "ServiceType".into(),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -106,13 +111,14 @@ fn add_service_with_invalid_id_format() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "invalid_service_id_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -124,8 +130,8 @@ fn add_service_with_invalid_id_format() {
     // Attempt to add a service with an invalid ID format
     let invalid_service = Service {
         id: Did::new("invalid_service_id"),
-        a_type: "ServiceType".to_string(),
-        service_endpoint: "http://example.com".to_string(),
+        a_type: "ServiceType".into(),
+        service_endpoint: "http://example.com".into(),
     };
 
     let result = contract
@@ -135,7 +141,7 @@ fn add_service_with_invalid_id_format() {
 
     // Verify the error message
     assert_eq!(
-        "Service id format error: Did format error: invalid_service_id",
+        "Service id format error: Did format error: invalid_service_id (missing did scheme)",
         result.err().unwrap().to_string()
     );
 
@@ -154,15 +160,15 @@ fn add_service_to_non_existing_did_document() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let non_existing_did = format!("{}{}", DID_PREFIX, "non_existing_did");
 
     // Attempt to add a service to a non-existing DID Document
     let new_service = Service {
-        id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-        a_type: "ServiceType".to_string(),
-        service_endpoint: "http://example.com".to_string(),
+        id: Did::new(&format!("{}#{}", non_existing_did, "service1")),
+        a_type: "ServiceType".into(),
+        service_endpoint: "http://example.com".into(),
     };
 
     let result = contract
@@ -186,13 +192,14 @@ fn add_service_unauthorized() {
     let owner = "owner".into_addr();
     let unauthorized_user = "unauthorized_user".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "unauthorized_service_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -203,9 +210,9 @@ fn add_service_unauthorized() {
 
     // Attempt to add a service by an unauthorized user
     let new_service = Service {
-        id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-        a_type: "ServiceType".to_string(),
-        service_endpoint: "http://example.com".to_string(),
+        id: Did::new(&format!("{}#{}", did, "service1")),
+        a_type: "ServiceType".into(),
+        service_endpoint: "http://example.com".into(),
     };
 
     let result = contract
@@ -215,7 +222,7 @@ fn add_service_unauthorized() {
 
     // Verify the error message
     assert_eq!(
-        format!("Unauthorized: {}", unauthorized_user.to_string()),
+        format!("Unauthorized: {}", unauthorized_user),
         result.err().unwrap().to_string()
     );
 
@@ -234,13 +241,14 @@ fn add_service_with_event_verification() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "event_service_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -251,9 +259,9 @@ fn add_service_with_event_verification() {
 
     // Add a valid service
     let new_service = Service {
-        id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-        a_type: "ServiceType".to_string(),
-        service_endpoint: "http://example.com".to_string(),
+        id: Did::new(&format!("{}#{}", did, "service1")),
+        a_type: "ServiceType".into(),
+        service_endpoint: "http://example.com".into(),
     };
 
     let result = contract
@@ -300,13 +308,14 @@ fn add_service_to_did_with_multiple_controllers() {
     let owner = "owner".into_addr();
     let controller2 = "controller2".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "multi_controller_service_did");
     let mut original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into(), controller2.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -317,9 +326,9 @@ fn add_service_to_did_with_multiple_controllers() {
 
     // Add a valid service
     let new_service = Service {
-        id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-        a_type: "ServiceType".to_string(),
-        service_endpoint: "http://example.com".to_string(),
+        id: Did::new(&format!("{}#{}", did, "service1")),
+        a_type: "ServiceType".into(),
+        service_endpoint: "http://example.com".into(),
     };
 
     let result = contract
@@ -343,7 +352,7 @@ fn add_service_to_invalid_did() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Define a DID with an invalid format
     let invalid_did = "invalid_did_format";
@@ -351,8 +360,8 @@ fn add_service_to_invalid_did() {
     // Attempt to add a service to the invalid DID
     let new_service = Service {
         id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-        a_type: "ServiceType".to_string(),
-        service_endpoint: "http://example.com".to_string(),
+        a_type: "ServiceType".into(),
+        service_endpoint: "http://example.com".into(),
     };
 
     let result = contract
@@ -362,7 +371,7 @@ fn add_service_to_invalid_did() {
 
     // Verify the error message
     assert_eq!(
-        format!("Did format error: {}", invalid_did),
+        format!("Did format error: {} (missing did scheme)", invalid_did),
         result.err().unwrap().to_string()
     );
 }
@@ -374,7 +383,7 @@ fn add_service_when_controller_is_did() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the controller DID Document
     let controller_did = format!("{}{}", DID_PREFIX, "controller_did");
@@ -382,6 +391,7 @@ fn add_service_when_controller_is_did() {
         id: Did::new(&controller_did),
         controller: vec![owner.to_string().into()], // Normal address as controller
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -395,6 +405,7 @@ fn add_service_when_controller_is_did() {
         id: Did::new(&did),
         controller: vec![controller_did.clone().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -404,9 +415,9 @@ fn add_service_when_controller_is_did() {
 
     // Add a service to the main DID Document
     let new_service = Service {
-        id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-        a_type: "ServiceType".to_string(),
-        service_endpoint: "http://example.com".to_string(),
+        id: Did::new(&format!("{}#{}", did, "service1")),
+        a_type: "ServiceType".into(),
+        service_endpoint: "http://example.com".into(),
     };
 
     let result = contract
@@ -422,3 +433,205 @@ fn add_service_when_controller_is_did() {
         "DID Document was not updated correctly"
     );
 }
+
+#[test]
+fn add_service_with_structured_endpoint() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "structured_service_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let new_service = Service {
+        id: Did::new(&format!("{}#didcomm", did)),
+        a_type: ServiceType::Set(vec!["DIDCommMessaging".to_string(), "LinkedDomains".to_string()]),
+        service_endpoint: ServiceEndpoint::Set(vec![
+            "https://example.com".into(),
+            ServiceEndpoint::Map(BTreeMap::from([(
+                "uri".to_string(),
+                EndpointValue::String("wss://example.com".to_string()),
+            )])),
+        ]),
+    };
+    let result = contract
+        .add_service(Did::new(&did), new_service.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let did_document = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(vec![new_service], did_document.service);
+}
+
+#[test]
+fn add_service_with_invalid_endpoint() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "invalid_endpoint_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let service_id = format!("{}#service", did);
+    let result = contract
+        .add_service(
+            Did::new(&did),
+            Service {
+                id: Did::new(&service_id),
+                a_type: "Service".into(),
+                service_endpoint: "javascript:alert(1)".into(),
+            },
+        )
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!(
+            "Invalid service endpoint for {}: scheme not allowed: javascript",
+            service_id
+        ),
+        result.err().unwrap().to_string()
+    );
+
+    let result = contract
+        .add_service(
+            Did::new(&did),
+            Service {
+                id: Did::new(&service_id),
+                a_type: "".into(),
+                service_endpoint: "https://example.com".into(),
+            },
+        )
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Service type missing: {}", service_id),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn add_service_with_relative_id() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "relative_service_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let res = contract
+        .add_service(
+            Did::new(&did),
+            Service {
+                id: Did::new("#linked-domain"),
+                a_type: "LinkedDomains".into(),
+                service_endpoint: "https://example.com".into(),
+            },
+        )
+        .call(&owner)
+        .expect("Failed to get result");
+
+    let service_id = format!("{}#linked-domain", did);
+    assert_eq!(res.events[1].attributes[2].key, "new_service");
+    assert_eq!(res.events[1].attributes[2].value, service_id);
+
+    let did_document = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(
+        vec![Service {
+            id: Did::new(&service_id),
+            a_type: "LinkedDomains".into(),
+            service_endpoint: "https://example.com".into(),
+        }],
+        did_document.service
+    );
+
+    // Already exists under its absolute id
+    let result = contract
+        .add_service(
+            Did::new(&did),
+            Service {
+                id: Did::new(&service_id),
+                a_type: "LinkedDomains".into(),
+                service_endpoint: "https://example.com".into(),
+            },
+        )
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Did document service already exists: {}", service_id),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn add_service_of_other_did() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "service_owner_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc.clone()).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // A service of another DID, including one whose DID has ours as a prefix
+    let other_service_id = format!("{}x#service1", did);
+    let result = contract
+        .add_service(
+            Did::new(&did),
+            Service {
+                id: Did::new(&other_service_id),
+                a_type: "Service".into(),
+                service_endpoint: "https://example.com".into(),
+            },
+        )
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!(
+            "Did url {} does not reference did document {}",
+            other_service_id, did
+        ),
+        result.err().unwrap().to_string()
+    );
+
+    let did_document = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(new_did_doc, did_document);
+}
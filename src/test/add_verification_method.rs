@@ -0,0 +1,416 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{Did, DidDocument, PublicKeyJwk, VerificationMethod},
+    test::{verification_method, DID_METHOD, DID_PREFIX},
+};
+
+#[test]
+fn add_valid_verification_method() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "valid_vm_did");
+    let mut original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+
+    // Create the original DID Document
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // Add a valid verification method
+    let new_method = verification_method("key1", &did);
+
+    let result = contract
+        .add_verification_method(Did::new(&did), new_method.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // Verify the updated DID Document
+    original_did_doc.verification_method.push(new_method);
+    let updated_did_doc = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(
+        original_did_doc, updated_did_doc,
+        "DID Document was not updated correctly"
+    );
+}
+
+#[test]
+fn add_jwk_verification_method() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "jwk_vm_did");
+    let mut original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let new_method = VerificationMethod {
+        id: Did::new(&format!("{}#{}", did, "key1")),
+        a_type: "JsonWebKey2020".to_string(),
+        controller: Did::new(&did),
+        public_key_multibase: None,
+        public_key_jwk: Some(PublicKeyJwk {
+            kty: "EC".to_string(),
+            crv: Some("secp256k1".to_string()),
+            x: Some("Z4Y3NNOxv0J6tCgqOBFnHnaZhJF6LdulT7z8A-2D5_8".to_string()),
+            y: Some("i5a2NtJoUKXkLm6q8nOEu9WOkso1Ag6FTUT6k_LMnGk".to_string()),
+            n: None,
+            e: None,
+        }),
+        blockchain_account_id: None,
+    };
+
+    let result = contract
+        .add_verification_method(Did::new(&did), new_method.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    original_did_doc.verification_method.push(new_method);
+    let updated_did_doc = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(
+        original_did_doc, updated_did_doc,
+        "DID Document was not updated correctly"
+    );
+}
+
+#[test]
+fn add_duplicate_verification_method() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "duplicate_vm_did");
+    let original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        verification_method: vec![verification_method("key1", &did)],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // Attempt to add a duplicate verification method
+    let duplicate_method = original_did_doc.verification_method[0].clone();
+    let result = contract
+        .add_verification_method(Did::new(&did), duplicate_method)
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+
+    assert_eq!(
+        format!(
+            "Did document verification method already exists: {}",
+            original_did_doc.verification_method[0].id
+        ),
+        result.err().unwrap().to_string()
+    );
+
+    // Verify that the DID Document was not updated
+    let current_did_doc = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(
+        original_did_doc, current_did_doc,
+        "DID Document was updated incorrectly"
+    );
+}
+
+#[test]
+fn add_verification_method_with_invalid_id_format() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "invalid_vm_id_did");
+    let original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let mut invalid_method = verification_method("key1", &did);
+    invalid_method.id = Did::new("invalid_vm_id");
+
+    let result = contract
+        .add_verification_method(Did::new(&did), invalid_method)
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+
+    assert_eq!(
+        "Verification method id format error: Did format error: invalid_vm_id (missing did scheme)",
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn add_verification_method_with_invalid_controller() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "invalid_vm_controller_did");
+    let original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let mut invalid_method = verification_method("key1", &did);
+    invalid_method.controller = Did::new("invalid_controller");
+
+    let result = contract
+        .add_verification_method(Did::new(&did), invalid_method)
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+
+    assert_eq!(
+        "Verification method controller format error: Did format error: invalid_controller (missing did scheme)",
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn add_verification_method_without_type() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "vm_without_type_did");
+    let original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let mut invalid_method = verification_method("key1", &did);
+    invalid_method.a_type = "".to_string();
+
+    let result = contract
+        .add_verification_method(Did::new(&did), invalid_method.clone())
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+
+    assert_eq!(
+        format!("Verification method type missing: {}", invalid_method.id),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn add_verification_method_with_invalid_key_material() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "vm_key_material_did");
+    let original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // No key material at all
+    let mut no_key_method = verification_method("key1", &did);
+    no_key_method.public_key_multibase = None;
+
+    let result = contract
+        .add_verification_method(Did::new(&did), no_key_method.clone())
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!(
+            "Verification method must have exactly one public key: {}",
+            no_key_method.id
+        ),
+        result.err().unwrap().to_string()
+    );
+
+    // More than one key material
+    let mut two_keys_method = verification_method("key1", &did);
+    two_keys_method.blockchain_account_id = Some(format!("cosmos:c4e:{}", owner));
+
+    let result = contract
+        .add_verification_method(Did::new(&did), two_keys_method.clone())
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!(
+            "Verification method must have exactly one public key: {}",
+            two_keys_method.id
+        ),
+        result.err().unwrap().to_string()
+    );
+
+    // Empty key material
+    let mut empty_key_method = verification_method("key1", &did);
+    empty_key_method.public_key_multibase = Some("".to_string());
+
+    let result = contract
+        .add_verification_method(Did::new(&did), empty_key_method.clone())
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!(
+            "Verification method must have exactly one public key: {}",
+            empty_key_method.id
+        ),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn add_verification_method_unauthorized() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let unauthorized_user = "unauthorized_user".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "unauthorized_vm_did");
+    let original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .add_verification_method(Did::new(&did), verification_method("key1", &did))
+        .call(&unauthorized_user);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+
+    assert_eq!(
+        format!("Unauthorized: {}", unauthorized_user),
+        result.err().unwrap().to_string()
+    );
+
+    let current_did_doc = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(
+        original_did_doc, current_did_doc,
+        "DID Document was updated incorrectly"
+    );
+}
+
+#[test]
+fn add_verification_method_with_event_verification() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "event_vm_did");
+    let original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let new_method = verification_method("key1", &did);
+    let result = contract
+        .add_verification_method(Did::new(&did), new_method.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // Check the emitted events
+    let res = result.expect("Failed to get result");
+
+    assert_eq!(res.events.len(), 2);
+
+    assert_eq!(res.events[0].ty, "execute");
+
+    assert_eq!(res.events[1].attributes.len(), 3);
+
+    assert_eq!(res.events[1].ty, "wasm-add_verification_method");
+    assert_eq!(res.events[1].attributes[0].key, "_contract_address");
+    assert_eq!(
+        res.events[1].attributes[0].value,
+        contract.contract_addr.to_string()
+    );
+    assert_eq!(res.events[1].attributes[1].key, "did");
+    assert_eq!(res.events[1].attributes[1].value, did.to_string());
+    assert_eq!(res.events[1].attributes[2].key, "new_verification_method");
+    assert_eq!(
+        res.events[1].attributes[2].value,
+        new_method.id.to_string()
+    );
+}
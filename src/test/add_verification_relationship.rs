@@ -0,0 +1,275 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{Did, DidDocument, RelationshipMethod, VerificationRelationship},
+    test::{verification_method, DID_METHOD, DID_PREFIX},
+};
+
+#[test]
+fn add_referenced_verification_relationship() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "referenced_relationship_did");
+    let method = verification_method("key1", &did);
+    let mut original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        verification_method: vec![method.clone()],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .add_verification_relationship(
+            Did::new(&did),
+            VerificationRelationship::Authentication,
+            RelationshipMethod::Reference(method.id.clone()),
+        )
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // Check the emitted events
+    let res = result.expect("Failed to get result");
+    assert_eq!(res.events.len(), 2);
+    assert_eq!(res.events[1].attributes.len(), 4);
+    assert_eq!(res.events[1].ty, "wasm-add_verification_relationship");
+    assert_eq!(res.events[1].attributes[1].key, "did");
+    assert_eq!(res.events[1].attributes[1].value, did.to_string());
+    assert_eq!(res.events[1].attributes[2].key, "relationship");
+    assert_eq!(res.events[1].attributes[2].value, "authentication");
+    assert_eq!(res.events[1].attributes[3].key, "new_method");
+    assert_eq!(res.events[1].attributes[3].value, method.id.to_string());
+
+    // Verify the updated DID Document
+    original_did_doc
+        .authentication
+        .push(RelationshipMethod::Reference(method.id.clone()));
+    let updated_did_doc = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(
+        original_did_doc, updated_did_doc,
+        "DID Document was not updated correctly"
+    );
+}
+
+#[test]
+fn add_embedded_verification_relationship() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "embedded_relationship_did");
+    let mut original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let embedded = RelationshipMethod::Embedded(Box::new(verification_method("key1", &did)));
+    let result = contract
+        .add_verification_relationship(
+            Did::new(&did),
+            VerificationRelationship::KeyAgreement,
+            embedded.clone(),
+        )
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    original_did_doc.key_agreement.push(embedded);
+    let updated_did_doc = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(
+        original_did_doc, updated_did_doc,
+        "DID Document was not updated correctly"
+    );
+}
+
+#[test]
+fn add_verification_relationship_with_unknown_reference() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "unknown_reference_did");
+    let original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let unknown_method_id = format!("{}{}", DID_PREFIX, "unknown_key");
+    let result = contract
+        .add_verification_relationship(
+            Did::new(&did),
+            VerificationRelationship::AssertionMethod,
+            RelationshipMethod::Reference(Did::new(&unknown_method_id)),
+        )
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+
+    assert_eq!(
+        format!(
+            "Verification relationship references unknown verification method: {}",
+            unknown_method_id
+        ),
+        result.err().unwrap().to_string()
+    );
+
+    // Verify that the DID Document was not updated
+    let current_did_doc = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(
+        original_did_doc, current_did_doc,
+        "DID Document was updated incorrectly"
+    );
+}
+
+#[test]
+fn add_duplicate_verification_relationship() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "duplicate_relationship_did");
+    let method = verification_method("key1", &did);
+    let original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        verification_method: vec![method.clone()],
+        capability_invocation: vec![RelationshipMethod::Reference(method.id.clone())],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .add_verification_relationship(
+            Did::new(&did),
+            VerificationRelationship::CapabilityInvocation,
+            RelationshipMethod::Reference(method.id.clone()),
+        )
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+
+    assert_eq!(
+        format!(
+            "Did document verification relationship method already exists: {}",
+            method.id
+        ),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn add_embedded_verification_relationship_with_existing_method_id() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "embedded_duplicate_id_did");
+    let method = verification_method("key1", &did);
+    let original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        verification_method: vec![method.clone()],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // Embedding a method with an id already used in verification_method is not allowed
+    let result = contract
+        .add_verification_relationship(
+            Did::new(&did),
+            VerificationRelationship::CapabilityDelegation,
+            RelationshipMethod::Embedded(Box::new(method.clone())),
+        )
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+
+    assert_eq!(
+        format!("Duplicated verification method: {}", method.id),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn add_verification_relationship_unauthorized() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let unauthorized_user = "unauthorized_user".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "unauthorized_relationship_did");
+    let method = verification_method("key1", &did);
+    let original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        verification_method: vec![method.clone()],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .add_verification_relationship(
+            Did::new(&did),
+            VerificationRelationship::Authentication,
+            RelationshipMethod::Reference(method.id.clone()),
+        )
+        .call(&unauthorized_user);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+
+    assert_eq!(
+        format!("Unauthorized: {}", unauthorized_user),
+        result.err().unwrap().to_string()
+    );
+}
@@ -0,0 +1,209 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{Did, DidDocument, DidOperation},
+    test::{service, DID_METHOD, DID_PREFIX},
+};
+
+#[test]
+fn batch_onboarding() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let site_did = format!("{}{}", DID_PREFIX, "site");
+    let device_dids: Vec<String> = (1..=3)
+        .map(|i| format!("{}device{}", DID_PREFIX, i))
+        .collect();
+
+    let mut operations = vec![DidOperation::CreateDidDocument {
+        did_doc: Box::new(DidDocument {
+            id: Did::new(&site_did),
+            controller: vec![],
+            service: vec![],
+            ..Default::default()
+        }),
+    }];
+    for device_did in &device_dids {
+        operations.push(DidOperation::CreateDidDocument {
+            did_doc: Box::new(DidDocument {
+                id: Did::new(device_did),
+                controller: vec![site_did.clone().into()],
+                service: vec![],
+                ..Default::default()
+            }),
+        });
+        operations.push(DidOperation::AddService {
+            did: Did::new(device_did),
+            service: service("service", device_did, "Service", "https://example.com"),
+        });
+    }
+
+    let res = contract
+        .batch(operations)
+        .call(&owner)
+        .expect("Failed to get result");
+
+    // One event per operation followed by the batch summary
+    assert_eq!(res.events.len(), 9);
+    assert_eq!(res.events[1].ty, "wasm-create_did_document");
+    assert_eq!(res.events[1].attributes[1].value, site_did);
+    assert_eq!(res.events[1].attributes[2].value, owner.to_string());
+    assert_eq!(res.events[2].ty, "wasm-create_did_document");
+    assert_eq!(res.events[3].ty, "wasm-add_service");
+    assert_eq!(res.events[8].ty, "wasm-batch");
+    assert_eq!(res.events[8].attributes[1].key, "operations");
+    assert_eq!(res.events[8].attributes[1].value, "7");
+
+    let dids = contract
+        .get_controlled_dids(site_did.clone().into(), None, None)
+        .unwrap();
+    assert_eq!(
+        device_dids.iter().map(|d| Did::new(d)).collect::<Vec<_>>(),
+        dids
+    );
+    for device_did in &device_dids {
+        let did_document = contract.get_did_document(Did::new(device_did)).unwrap();
+        assert_eq!(
+            vec![service("service", device_did, "Service", "https://example.com")],
+            did_document.service
+        );
+    }
+    assert_eq!(4, contract.count_dids().unwrap());
+}
+
+#[test]
+fn batch_is_atomic() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "batched_did");
+    let operations = vec![
+        DidOperation::CreateDidDocument {
+            did_doc: Box::new(DidDocument {
+                id: Did::new(&did),
+                controller: vec![owner.to_string().into()],
+                service: vec![],
+                ..Default::default()
+            }),
+        },
+        DidOperation::AddService {
+            did: Did::new(&did),
+            service: service("service", &did, "Service", "https://example.com"),
+        },
+        DidOperation::AddService {
+            did: Did::new(&did),
+            service: service("service", &did, "Service", "https://example.com"),
+        },
+    ];
+    let result = contract.batch(operations).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!(
+            "Batch operation 2 failed: Did document service already exists: {}#service",
+            did
+        ),
+        result.err().unwrap().to_string()
+    );
+
+    // Nothing from the batch was applied
+    let result = contract.get_did_document(Did::new(&did));
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(0, contract.count_dids().unwrap());
+}
+
+#[test]
+fn batch_unauthorized_operation() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let unauthorized_user = "unauthorized_user".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "owned_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc.clone()).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let other_did = format!("{}{}", DID_PREFIX, "other_did");
+    let operations = vec![
+        DidOperation::CreateDidDocument {
+            did_doc: Box::new(DidDocument {
+                id: Did::new(&other_did),
+                controller: vec![],
+                service: vec![],
+                ..Default::default()
+            }),
+        },
+        DidOperation::DeleteDidDocument {
+            did: Did::new(&did),
+            refund_address: None,
+        },
+    ];
+    let result = contract.batch(operations).call(&unauthorized_user);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Batch operation 1 failed: Unauthorized: {}", unauthorized_user),
+        result.err().unwrap().to_string()
+    );
+
+    let did_document = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(new_did_doc, did_document);
+    let result = contract.get_did_document(Did::new(&other_did));
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+}
+
+#[test]
+fn batch_empty() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let result = contract.batch(vec![]).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        "Batch contains no operations",
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn create_cannot_be_proposed() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "proposed_did");
+    let operation = DidOperation::CreateDidDocument {
+        did_doc: Box::new(DidDocument {
+            id: Did::new(&did),
+            controller: vec![owner.to_string().into()],
+            service: vec![],
+            ..Default::default()
+        }),
+    };
+    let result = contract.propose(operation, None).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+}
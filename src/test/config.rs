@@ -0,0 +1,358 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{
+        ConfigUpdate, Controller, Did, DidDocument, DocumentLimits, Pagination, Service,
+        TraversalLimits,
+    },
+    test::{DID_METHOD, DID_PREFIX},
+};
+
+#[test]
+fn instantiate_with_default_config() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let config = contract.get_config().unwrap();
+    assert_eq!(Some(owner), config.admin);
+    assert_eq!(None, config.pending_admin);
+    assert_eq!(DID_PREFIX, config.did_prefix);
+    assert_eq!(TraversalLimits::default(), config.traversal_limits);
+    assert_eq!(DocumentLimits::default(), config.document_limits);
+    assert_eq!(Pagination::default(), config.pagination);
+}
+
+#[test]
+fn instantiate_with_admin_and_config() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let admin = "admin".into_addr();
+
+    let pagination = Pagination {
+        default_limit: 10,
+        max_limit: 20,
+    };
+    let update = ConfigUpdate {
+        pagination: Some(pagination),
+        ..Default::default()
+    };
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), Some(admin.to_string()), Some(update))
+        .call(&owner)
+        .unwrap();
+
+    let config = contract.get_config().unwrap();
+    assert_eq!(Some(admin), config.admin);
+    assert_eq!(pagination, config.pagination);
+
+    let update = ConfigUpdate {
+        pagination: Some(Pagination {
+            default_limit: 30,
+            max_limit: 20,
+        }),
+        ..Default::default()
+    };
+    let result = code_id
+        .instantiate(DID_METHOD.to_string(), None, Some(update))
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        "Invalid pagination: default limit 30 must be positive and at most the maximum 20",
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn update_config() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let document_limits = DocumentLimits {
+        max_controllers: 2,
+        max_services: 1,
+        max_document_size: 1024,
+        ..Default::default()
+    };
+    let update = ConfigUpdate {
+        document_limits: Some(document_limits),
+        ..Default::default()
+    };
+    let res = contract
+        .update_config(update)
+        .call(&owner)
+        .expect("Failed to get result");
+    assert_eq!(res.events.len(), 2);
+    assert_eq!(res.events[1].ty, "wasm-update_config");
+    assert_eq!(res.events[1].attributes[3].key, "max_controllers");
+    assert_eq!(res.events[1].attributes[3].value, "2");
+    assert_eq!(res.events[1].attributes[4].key, "max_services");
+    assert_eq!(res.events[1].attributes[4].value, "1");
+
+    // Fields left out keep their value
+    let config = contract.get_config().unwrap();
+    assert_eq!(document_limits, config.document_limits);
+    assert_eq!(TraversalLimits::default(), config.traversal_limits);
+    assert_eq!(Pagination::default(), config.pagination);
+}
+
+#[test]
+fn update_config_unauthorized() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let unauthorized_user = "unauthorized_user".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let update = ConfigUpdate {
+        pagination: Some(Pagination {
+            default_limit: 1,
+            max_limit: 1,
+        }),
+        ..Default::default()
+    };
+    let result = contract.update_config(update).call(&unauthorized_user);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Unauthorized: {}", unauthorized_user),
+        result.err().unwrap().to_string()
+    );
+
+    let config = contract.get_config().unwrap();
+    assert_eq!(Pagination::default(), config.pagination);
+}
+
+#[test]
+fn pagination_from_config() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let update = ConfigUpdate {
+        pagination: Some(Pagination {
+            default_limit: 1,
+            max_limit: 2,
+        }),
+        ..Default::default()
+    };
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, Some(update))
+        .call(&owner)
+        .unwrap();
+
+    for i in 1..=3 {
+        let did_doc = DidDocument {
+            id: Did::new(&format!("{}did{}", DID_PREFIX, i)),
+            controller: vec![owner.to_string().into()],
+            service: vec![],
+            ..Default::default()
+        };
+        let result = contract.create_did_document(did_doc).call(&owner);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+    }
+
+    let dids = contract.list_dids(None, None, None).unwrap();
+    assert_eq!(1, dids.len());
+    let dids = contract.list_dids(Some(3), None, None).unwrap();
+    assert_eq!(2, dids.len());
+}
+
+#[test]
+fn document_limits() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let owner2 = "owner2".into_addr();
+
+    let update = ConfigUpdate {
+        document_limits: Some(DocumentLimits {
+            max_controllers: 1,
+            max_services: 1,
+            max_document_size: 512,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, Some(update))
+        .call(&owner)
+        .unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "limited_did");
+    let service = |name: &str, endpoint: &str| Service {
+        id: Did::new(&format!("{}#{}", did, name)),
+        a_type: "Service".into(),
+        service_endpoint: endpoint.into(),
+    };
+    let did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into(), owner2.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(did_doc).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Did document {} has more than 1 controllers", did),
+        result.err().unwrap().to_string()
+    );
+
+    let did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![service("service1", "https://example.com")],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .add_controller(Did::new(&did), owner2.to_string().into())
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Did document {} has more than 1 controllers", did),
+        result.err().unwrap().to_string()
+    );
+
+    let result = contract
+        .add_service(Did::new(&did), service("service2", "https://example.com"))
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Did document {} has more than 1 services", did),
+        result.err().unwrap().to_string()
+    );
+
+    let long_endpoint = format!("https://example.com/{}", "a".repeat(512));
+    let result = contract
+        .update_service(
+            Did::new(&did),
+            service("service1", "https://example.com").id,
+            None,
+            Some(long_endpoint.as_str().into()),
+        )
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Did document {} is larger than 512 bytes", did),
+        result.err().unwrap().to_string()
+    );
+
+    let did_document = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(vec![Controller::from(owner.to_string())], did_document.controller);
+    assert_eq!(vec![service("service1", "https://example.com")], did_document.service);
+}
+
+#[test]
+fn transfer_admin() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let new_admin = "new_admin".into_addr();
+    let other = "other".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let result = contract.accept_admin().call(&new_admin);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!("No pending admin", result.err().unwrap().to_string());
+
+    let result = contract.transfer_admin(new_admin.to_string()).call(&other);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Unauthorized: {}", other),
+        result.err().unwrap().to_string()
+    );
+
+    let res = contract
+        .transfer_admin(new_admin.to_string())
+        .call(&owner)
+        .expect("Failed to get result");
+    assert_eq!(res.events[1].ty, "wasm-transfer_admin");
+    assert_eq!(res.events[1].attributes[2].key, "pending_admin");
+    assert_eq!(res.events[1].attributes[2].value, new_admin.to_string());
+
+    // The current admin stays in charge until the transfer is accepted
+    let config = contract.get_config().unwrap();
+    assert_eq!(Some(owner.clone()), config.admin);
+    assert_eq!(Some(new_admin.clone()), config.pending_admin);
+
+    let result = contract.accept_admin().call(&other);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Unauthorized: {}", other),
+        result.err().unwrap().to_string()
+    );
+
+    let res = contract
+        .accept_admin()
+        .call(&new_admin)
+        .expect("Failed to get result");
+    assert_eq!(res.events[1].ty, "wasm-accept_admin");
+    assert_eq!(res.events[1].attributes[1].key, "new_admin");
+    assert_eq!(res.events[1].attributes[1].value, new_admin.to_string());
+    assert_eq!(res.events[1].attributes[2].key, "old_admin");
+    assert_eq!(res.events[1].attributes[2].value, owner.to_string());
+
+    let config = contract.get_config().unwrap();
+    assert_eq!(Some(new_admin.clone()), config.admin);
+    assert_eq!(None, config.pending_admin);
+
+    let result = contract.update_config(ConfigUpdate::default()).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    let result = contract.update_config(ConfigUpdate::default()).call(&new_admin);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+}
+
+#[test]
+fn renounce_admin() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let new_admin = "new_admin".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let result = contract.transfer_admin(new_admin.to_string()).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let res = contract
+        .renounce_admin()
+        .call(&owner)
+        .expect("Failed to get result");
+    assert_eq!(res.events[1].ty, "wasm-renounce_admin");
+
+    // A pending transfer can no longer be accepted
+    let config = contract.get_config().unwrap();
+    assert_eq!(None, config.admin);
+    assert_eq!(None, config.pending_admin);
+
+    let result = contract.accept_admin().call(&new_admin);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!("No pending admin", result.err().unwrap().to_string());
+
+    let result = contract.update_config(ConfigUpdate::default()).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Unauthorized: {}", owner),
+        result.err().unwrap().to_string()
+    );
+}
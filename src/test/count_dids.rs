@@ -0,0 +1,76 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{Did, DidDocument},
+    test::{DID_METHOD, DID_PREFIX},
+};
+
+#[test]
+fn count_dids() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    assert_eq!(0, contract.count_dids().unwrap());
+
+    let did1 = format!("{}{}", DID_PREFIX, "did1");
+    let did2 = format!("{}{}", DID_PREFIX, "did2");
+    for did in [&did1, &did2] {
+        let new_did_doc = DidDocument {
+            id: Did::new(did),
+            controller: vec![owner.to_string().into()],
+            service: vec![],
+            ..Default::default()
+        };
+        let result = contract.create_did_document(new_did_doc).call(&owner);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+    }
+    assert_eq!(2, contract.count_dids().unwrap());
+
+    // Updates do not change the count
+    let result = contract
+        .add_controller(Did::new(&did1), "controller".into_addr().to_string().into())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    assert_eq!(2, contract.count_dids().unwrap());
+
+    // Deactivated documents are kept and still counted
+    let result = contract.deactivate_did_document(Did::new(&did2)).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    assert_eq!(2, contract.count_dids().unwrap());
+
+    let result = contract.delete_did_document(Did::new(&did1), None).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    assert_eq!(1, contract.count_dids().unwrap());
+}
+
+#[test]
+fn count_dids_failed_create() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(1, contract.count_dids().unwrap());
+}
@@ -4,7 +4,8 @@ use sylvia::multitest::App;
 
 use crate::{
     contract::sv::mt::{CodeId, DidContractProxy},
-    state::{Did, DidDocument, Service, DID_PREFIX},
+    state::{Did, DidDocument, RelationshipMethod, Service, VerificationMethod},
+    test::{DID_METHOD, DID_PREFIX},
 };
 
 #[test]
@@ -14,7 +15,7 @@ fn create_and_get_document() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // let did_owner = "did_owner";
     let did = "new_did";
@@ -23,17 +24,18 @@ fn create_and_get_document() {
         controller: vec![owner.to_string().into()],
         // controller: Controllers(vec![owner.to_string().into()]),
         service: vec![Service {
-            a_type: "".to_string(),
+            a_type: "Service".into(),
             id: Did::new("dfdsfs"),
-            service_endpoint: "dfdsfs".to_string(),
+            service_endpoint: "https://example.com".into(),
         }],
+        ..Default::default()
     };
     let result = contract
         .create_did_document(new_did_doc.clone())
         .call(&owner);
     assert!(result.is_err(), "Expected Err, but got an Ok");
     assert_eq!(
-        "Did format error: new_did",
+        "Did format error: new_did (missing did scheme)",
         result.err().unwrap().to_string()
     );
 
@@ -45,11 +47,11 @@ fn create_and_get_document() {
         .call(&owner);
     assert!(result.is_err(), "Expected Err, but got an Ok");
     assert_eq!(
-        "Service id format error: Did format error: dfdsfs",
+        "Service id format error: Did format error: dfdsfs (missing did scheme)",
         result.err().unwrap().to_string()
     );
 
-    new_did_doc.service[0].id = Did::new(&format!("{}{}", DID_PREFIX, "ffffff"));
+    new_did_doc.service[0].id = Did::new(&format!("{}#{}", did, "ffffff"));
 
     let result = contract
         .create_did_document(new_did_doc.clone())
@@ -67,17 +69,18 @@ fn create_document_with_invalid_did_format() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = "invalid_did_format";
     let new_did_doc = DidDocument {
         id: Did::new(did),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
+            a_type: "ServiceType".into(),
             id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -85,7 +88,193 @@ fn create_document_with_invalid_did_format() {
         .call(&owner);
     assert!(result.is_err(), "Expected Err, but got an Ok");
     assert_eq!(
-        "Did format error: invalid_did_format",
+        "Did format error: invalid_did_format (missing did scheme)",
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn create_document_with_invalid_did_syntax() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let cases = [
+        ("did:c4e:", "empty method-specific id"),
+        ("did:c4e:a b#frag?x", "invalid method-specific id character ' '"),
+        ("did:c4e:abc%G1", "invalid percent-encoding"),
+        ("did:c4e:abc:", "method-specific id ends with ':'"),
+        ("did:c4e:abc#key-1", "did url not allowed"),
+        ("did:c4e:abc/path", "did url not allowed"),
+        ("did:other:abc", "unsupported did method 'other'"),
+    ];
+
+    for (did, reason) in cases {
+        let new_did_doc = DidDocument {
+            id: Did::new(did),
+            controller: vec![owner.to_string().into()],
+            service: vec![],
+            ..Default::default()
+        };
+
+        let result = contract
+            .create_did_document(new_did_doc.clone())
+            .call(&owner);
+        assert!(result.is_err(), "Expected Err, but got an Ok");
+        assert_eq!(
+            format!("Did format error: {} ({})", did, reason),
+            result.err().unwrap().to_string()
+        );
+    }
+}
+
+#[test]
+fn create_document_with_did_url_service_id() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "did_url_service_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![Service {
+            a_type: "LinkedDomains".into(),
+            id: Did::new(&format!("{}#linked-domain", did)),
+            service_endpoint: "https://example.com".into(),
+        }],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let created_did_doc = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(new_did_doc, created_did_doc);
+}
+
+#[test]
+fn create_document_with_relative_ids() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "relative_ids_did");
+    let method = VerificationMethod {
+        id: Did::new("#key-1"),
+        a_type: "Ed25519VerificationKey2020".to_string(),
+        controller: Did::new(&did),
+        public_key_multibase: Some("z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH".to_string()),
+        public_key_jwk: None,
+        blockchain_account_id: None,
+    };
+    let service = Service {
+        a_type: "LinkedDomains".into(),
+        id: Did::new("#linked-domain"),
+        service_endpoint: "https://example.com".into(),
+    };
+    let mut new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![service],
+        verification_method: vec![method],
+        authentication: vec![RelationshipMethod::Reference(Did::new("#key-1"))],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // Relative ids are stored resolved against the document
+    let key_id = Did::new(&format!("{}#key-1", did));
+    new_did_doc.service[0].id = Did::new(&format!("{}#linked-domain", did));
+    new_did_doc.verification_method[0].id = key_id.clone();
+    new_did_doc.authentication = vec![RelationshipMethod::Reference(key_id)];
+    let created_did_doc = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(new_did_doc, created_did_doc);
+}
+
+#[test]
+fn create_document_with_service_of_other_did() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "service_owner_did");
+    let other_did = format!("{}{}", DID_PREFIX, "other_did");
+    for service_id in [format!("{}#service1", other_did), did.clone()] {
+        let new_did_doc = DidDocument {
+            id: Did::new(&did),
+            controller: vec![owner.to_string().into()],
+            service: vec![Service {
+                a_type: "Service".into(),
+                id: Did::new(&service_id),
+                service_endpoint: "https://example.com".into(),
+            }],
+            ..Default::default()
+        };
+
+        let result = contract.create_did_document(new_did_doc).call(&owner);
+        assert!(result.is_err(), "Expected Err, but got an Ok");
+        assert_eq!(
+            format!(
+                "Did url {} does not reference did document {}",
+                service_id, did
+            ),
+            result.err().unwrap().to_string()
+        );
+    }
+}
+
+#[test]
+fn create_document_with_verification_method_of_other_did() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "method_owner_did");
+    let method_id = format!("{}{}", DID_PREFIX, "other_did#key1");
+    let method = VerificationMethod {
+        id: Did::new(&method_id),
+        a_type: "Ed25519VerificationKey2020".to_string(),
+        controller: Did::new(&did),
+        public_key_multibase: Some("z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH".to_string()),
+        public_key_jwk: None,
+        blockchain_account_id: None,
+    };
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        assertion_method: vec![RelationshipMethod::Embedded(Box::new(method))],
+        ..Default::default()
+    };
+
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!(
+            "Did url {} does not reference did document {}",
+            method_id, did
+        ),
         result.err().unwrap().to_string()
     );
 }
@@ -97,7 +286,7 @@ fn create_document_with_duplicate_controller() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "new_did");
     let new_did_doc = DidDocument {
@@ -107,10 +296,11 @@ fn create_document_with_duplicate_controller() {
             owner.to_string().into(), // Duplicate controller
         ],
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", did, "service1")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -118,7 +308,7 @@ fn create_document_with_duplicate_controller() {
         .call(&owner);
     assert!(result.is_err(), "Expected Err, but got an Ok");
     assert_eq!(
-        format!("Duplicated controller: {}", owner.to_string()),
+        format!("Duplicated controller: {}", owner),
         result.err().unwrap().to_string()
     );
 }
@@ -130,17 +320,18 @@ fn create_document_with_duplicated_did() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "duplicated_did");
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", did, "service1")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     // First creation should succeed
@@ -167,17 +358,18 @@ fn create_document_with_invalid_controller_format() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "new_did");
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec!["invalid_controller_format".to_string().into()], // Invalid controller format
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", did, "service1")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -197,17 +389,18 @@ fn create_document_with_nonexistent_controller() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "new_did");
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![format!("{}{}", DID_PREFIX, "nonexistent_controller").into()], // Nonexistent controller
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", did, "service1")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -227,17 +420,18 @@ fn create_document_with_invalid_service_id() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "new_did");
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
+            a_type: "ServiceType".into(),
             id: Did::new("invalid_service_id"), // Invalid service ID
-            service_endpoint: "http://example.com".to_string(),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -245,7 +439,7 @@ fn create_document_with_invalid_service_id() {
         .call(&owner);
     assert!(result.is_err(), "Expected Err, but got an Ok");
     assert_eq!(
-        "Service id format error: Did format error: invalid_service_id",
+        "Service id format error: Did format error: invalid_service_id (missing did scheme)",
         result.err().unwrap().to_string()
     );
 }
@@ -257,17 +451,18 @@ fn create_document_not_self_controlled() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "self_controlled_did");
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![did.to_string().into()], // Self-controlled
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", did, "service1")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -287,7 +482,7 @@ fn create_document_with_unsignable_did() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     const DID_STORE: Map<String, DidDocument> = Map::new("dids");
 
@@ -297,6 +492,7 @@ fn create_document_with_unsignable_did() {
         // controller: Controllers(vec![did_controlled_by_itself.to_string().into()]),
         controller: vec![did_controlled_by_itself.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     {
@@ -321,10 +517,11 @@ fn create_document_with_unsignable_did() {
         id: Did::new(&did),
         controller: vec![did_controlled_by_itself.into()], // Nonexistent controller
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", did, "service1")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -344,18 +541,19 @@ fn create_document_with_event_checks() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "event_test_did");
-    let service_did = format!("{}{}", DID_PREFIX, "service1");
+    let service_did = format!("{}#{}", did, "service1");
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
+            a_type: "ServiceType".into(),
             id: service_did.to_string().into(),
-            service_endpoint: "http://example.com".to_string(),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     // Create the DID Document
@@ -403,27 +601,28 @@ fn create_document_with_event_checks_many_controllers_and_services() {
     let owner = "owner".into_addr();
     let contorller2 = "contorller2".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "event_test_did");
-    let service_did: String = format!("{}{}", DID_PREFIX, "service1");
-    let service_did2: String = format!("{}{}", DID_PREFIX, "service2");
+    let service_did: String = format!("{}#{}", did, "service1");
+    let service_did2: String = format!("{}#{}", did, "service2");
 
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into(), contorller2.to_string().into()],
         service: vec![
             Service {
-                a_type: "ServiceType".to_string(),
+                a_type: "ServiceType".into(),
                 id: service_did.to_string().into(),
-                service_endpoint: "http://example.com".to_string(),
+                service_endpoint: "http://example.com".into(),
             },
             Service {
-                a_type: "ServiceType".to_string(),
+                a_type: "ServiceType".into(),
                 id: service_did2.to_string().into(),
-                service_endpoint: "http://example.com".to_string(),
+                service_endpoint: "http://example.com".into(),
             },
         ],
+        ..Default::default()
     };
 
     // Create the DID Document
@@ -459,7 +658,7 @@ fn create_document_with_event_checks_many_controllers_and_services() {
     assert_eq!(res.events[1].attributes[2].key, "controllers");
     assert_eq!(
         res.events[1].attributes[2].value,
-        format!("{},{}", owner.to_string(), contorller2.to_string())
+        format!("{},{}", owner, contorller2)
     );
     assert_eq!(res.events[1].attributes[3].key, "services");
     assert_eq!(
@@ -476,17 +675,18 @@ fn create_document_with_no_controller() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "no_controller_did");
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![], // No controllers provided
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", did, "service1")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     // Create the DID Document
@@ -518,17 +718,18 @@ fn create_document_with_normal_address_as_controller() {
     let owner = "owner".into_addr();
     let normal_address = "normal_address".into_addr(); // Normal address as controller
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "normal_address_controller_did");
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![normal_address.to_string().into()], // Normal address as controller
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", did, "service1")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     // Create the DID Document
@@ -559,7 +760,7 @@ fn create_document_with_other_did_as_controller() {
     let owner = "owner".into_addr();
     let normal_address = "normal_address".into_addr(); // Normal address as controller
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the first DID Document with a normal address as its controller
     let controller_did = format!("{}{}", DID_PREFIX, "controller_did");
@@ -567,10 +768,11 @@ fn create_document_with_other_did_as_controller() {
         id: Did::new(&controller_did),
         controller: vec![normal_address.to_string().into()], // Normal address as controller
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", controller_did, "service1")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -584,10 +786,11 @@ fn create_document_with_other_did_as_controller() {
         id: Did::new(&did),
         controller: vec![controller_did.to_string().into()], // Another DID as controller
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service2")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", did, "service2")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -617,18 +820,19 @@ fn create_did_document_with_index_verification() {
     let owner = "owner".into_addr();
     let controller2 = "controller2".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "indexed_did");
-    let service_did = format!("{}{}", DID_PREFIX, "service1");
+    let service_did = format!("{}#{}", did, "service1");
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into(), controller2.to_string().into()],
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
+            a_type: "ServiceType".into(),
             id: Did::new(&service_did),
-            service_endpoint: "http://example.com".to_string(),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     // Create the DID Document
@@ -672,13 +876,14 @@ fn create_did_document_with_no_services() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "no_services_did");
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()], // Single controller
         service: vec![], // No services
+        ..Default::default()
     };
 
     // Create the DID Document
@@ -712,26 +917,27 @@ fn create_document_with_duplicated_service() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "duplicated_service_did");
-    let service_id = format!("{}{}", DID_PREFIX, "service1");
+    let service_id = format!("{}#{}", did, "service1");
 
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![
             Service {
-                a_type: "ServiceType".to_string(),
+                a_type: "ServiceType".into(),
                 id: Did::new(&service_id),
-                service_endpoint: "http://example.com".to_string(),
+                service_endpoint: "http://example.com".into(),
             },
             Service {
-                a_type: "ServiceType".to_string(),
+                a_type: "ServiceType".into(),
                 id: Did::new(&service_id), // Duplicate service ID
-                service_endpoint: "http://example.com".to_string(),
+                service_endpoint: "http://example.com".into(),
             },
         ],
+        ..Default::default()
     };
 
     // Attempt to create the DID Document
@@ -747,6 +953,131 @@ fn create_document_with_duplicated_service() {
     );
 }
 
+#[test]
+fn create_document_with_duplicated_verification_method() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "duplicated_vm_did");
+    let method_id = format!("{}#{}", did, "key1");
+    let method = VerificationMethod {
+        id: Did::new(&method_id),
+        a_type: "Ed25519VerificationKey2020".to_string(),
+        controller: Did::new(&did),
+        public_key_multibase: Some("z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH".to_string()),
+        public_key_jwk: None,
+        blockchain_account_id: None,
+    };
+
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        verification_method: vec![method.clone(), method], // Duplicate verification method ID
+        ..Default::default()
+    };
+
+    // Attempt to create the DID Document
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+
+    // Verify the error message
+    assert_eq!(
+        format!("Duplicated verification method: {}", method_id),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn create_document_with_verification_relationships() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "relationships_did");
+    let method = VerificationMethod {
+        id: Did::new(&format!("{}#{}", did, "key1")),
+        a_type: "Ed25519VerificationKey2020".to_string(),
+        controller: Did::new(&did),
+        public_key_multibase: Some("z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH".to_string()),
+        public_key_jwk: None,
+        blockchain_account_id: None,
+    };
+    let mut embedded_method = method.clone();
+    embedded_method.id = Did::new(&format!("{}#{}", did, "key2"));
+
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        verification_method: vec![method.clone()],
+        authentication: vec![RelationshipMethod::Reference(method.id.clone())],
+        key_agreement: vec![RelationshipMethod::Embedded(Box::new(embedded_method.clone()))],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // Check relationship event attributes
+    let res = result.expect("Failed to get result");
+    assert_eq!(res.events[1].ty, "wasm-create_did_document");
+    assert_eq!(res.events[1].attributes[3].key, "verification_methods");
+    assert_eq!(res.events[1].attributes[3].value, method.id.to_string());
+    assert_eq!(res.events[1].attributes[4].key, "authentication");
+    assert_eq!(res.events[1].attributes[4].value, method.id.to_string());
+    assert_eq!(res.events[1].attributes[5].key, "key_agreement");
+    assert_eq!(res.events[1].attributes[5].value, embedded_method.id.to_string());
+
+    let created_did_doc = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(new_did_doc, created_did_doc);
+}
+
+#[test]
+fn create_document_with_unknown_verification_relationship_reference() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "unknown_reference_did");
+    let unknown_method_id = format!("{}{}", DID_PREFIX, "unknown_key");
+
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        authentication: vec![RelationshipMethod::Reference(Did::new(&unknown_method_id))],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+
+    assert_eq!(
+        format!(
+            "Verification relationship references unknown verification method: {}",
+            unknown_method_id
+        ),
+        result.err().unwrap().to_string()
+    );
+}
+
 #[test]
 fn replacing_document() {
     let app = App::default();
@@ -754,7 +1085,7 @@ fn replacing_document() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // let did_owner = "did_owner";
     let did = &format!("{}{}", DID_PREFIX, "new_did");
@@ -763,10 +1094,11 @@ fn replacing_document() {
         // controller: Controllers(vec![owner.to_string().into()]),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            a_type: "".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "dfdsfs")),
-            service_endpoint: "dfdsfs".to_string(),
+            a_type: "Service".into(),
+            id: Did::new(&format!("{}#{}", did, "dfdsfs")),
+            service_endpoint: "https://example.com".into(),
         }],
+        ..Default::default()
     };
     let mut result = contract
         .create_did_document(new_did_doc.clone())
@@ -778,10 +1110,11 @@ fn replacing_document() {
         // controller: Controllers(vec![owner.to_string().into()]),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            a_type: "".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "AAAA")),
-            service_endpoint: "BBBBB".to_string(),
+            a_type: "Service".into(),
+            id: Did::new(&format!("{}#{}", did, "AAAA")),
+            service_endpoint: "https://bbbbb.example.com".into(),
         }],
+        ..Default::default()
     };
 
     result = contract
@@ -0,0 +1,345 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{Controller, Did, DidDocument, Service},
+    test::{DID_METHOD, DID_PREFIX},
+};
+
+#[test]
+fn deactivate_did_document() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "deactivated_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract.deactivate_did_document(Did::new(&did)).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // The document is still resolvable, marked as deactivated
+    let did_document = contract.get_did_document(Did::new(&did)).unwrap();
+    assert!(did_document.deactivated);
+    assert_eq!(
+        DidDocument {
+            deactivated: true,
+            ..new_did_doc
+        },
+        did_document
+    );
+
+    // Its controllers are no longer indexed
+    let dids = contract
+        .get_controlled_dids(owner.to_string().into(), None, None)
+        .unwrap();
+    assert!(dids.is_empty());
+}
+
+#[test]
+fn deactivate_did_document_with_event_verification() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "event_deactivate_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let res = contract
+        .deactivate_did_document(Did::new(&did))
+        .call(&owner)
+        .expect("Failed to get result");
+
+    assert_eq!(res.events.len(), 2);
+    assert_eq!(res.events[1].ty, "wasm-deactivate_did_document");
+    assert_eq!(res.events[1].attributes.len(), 2);
+    assert_eq!(res.events[1].attributes[1].key, "did");
+    assert_eq!(res.events[1].attributes[1].value, did);
+}
+
+#[test]
+fn deactivate_did_document_not_found() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "did");
+    let result = contract.deactivate_did_document(Did::new(&did)).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!("Did document not found: type: did_contract::state::DidDocument; key: [00, 04, 64, 69, 64, 73, 64, 69, 64, 3A, 63, 34, 65, 3A, 64, 69, 64] not found", result.err().unwrap().to_string());
+}
+
+#[test]
+fn deactivate_did_document_unauthorized() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let unauthorized_user = "unauthorized_user".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "unauthorized_deactivate_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .deactivate_did_document(Did::new(&did))
+        .call(&unauthorized_user);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Unauthorized: {}", unauthorized_user),
+        result.err().unwrap().to_string()
+    );
+
+    let did_document = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(new_did_doc, did_document);
+}
+
+#[test]
+fn deactivate_did_document_that_is_controller() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let controller_did = format!("{}{}", DID_PREFIX, "controller_did");
+    let controller_did_doc = DidDocument {
+        id: Did::new(&controller_did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(controller_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let did = format!("{}{}", DID_PREFIX, "main_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![controller_did.clone().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .deactivate_did_document(Did::new(&controller_did))
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Did is controller of another document: {}", controller_did),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn deactivated_did_document_cannot_be_recreated() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "recreated_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract.deactivate_did_document(Did::new(&did)).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Did document deactivated: {}", did),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn create_deactivated_did_document_not_allowed() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "new_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        deactivated: true,
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Did document deactivated: {}", did),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn deactivated_did_document_cannot_be_modified() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "frozen_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract.deactivate_did_document(Did::new(&did)).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let expected = format!("Did document deactivated: {}", did);
+
+    let result = contract
+        .update_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert_eq!(expected, result.err().unwrap().to_string());
+
+    let result = contract
+        .add_controller(Did::new(&did), "new_controller".into_addr().to_string().into())
+        .call(&owner);
+    assert_eq!(expected, result.err().unwrap().to_string());
+
+    let result = contract
+        .add_service(
+            Did::new(&did),
+            Service {
+                id: Did::new(&format!("{}#service", did)),
+                a_type: "Service".into(),
+                service_endpoint: "https://example.com".into(),
+            },
+        )
+        .call(&owner);
+    assert_eq!(expected, result.err().unwrap().to_string());
+
+    let result = contract.deactivate_did_document(Did::new(&did)).call(&owner);
+    assert_eq!(expected, result.err().unwrap().to_string());
+
+    let result = contract.delete_did_document(Did::new(&did), None).call(&owner);
+    assert_eq!(expected, result.err().unwrap().to_string());
+}
+
+#[test]
+fn deactivated_did_document_is_no_longer_controller() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "former_controller");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let is_controller = contract
+        .is_controller_of(vec![Controller::new(&did)], owner.to_string().into())
+        .unwrap();
+    assert!(is_controller);
+
+    let result = contract.deactivate_did_document(Did::new(&did)).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let is_controller = contract
+        .is_controller_of(vec![Controller::new(&did)], owner.to_string().into())
+        .unwrap();
+    assert!(!is_controller);
+
+    // A deactivated DID cannot become a controller of another document
+    let controlled_did = format!("{}{}", DID_PREFIX, "controlled_did");
+    let controlled_did_doc = DidDocument {
+        id: Did::new(&controlled_did),
+        controller: vec![did.clone().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(controlled_did_doc)
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Did document deactivated: {}", did),
+        result.err().unwrap().to_string()
+    );
+
+    let exists = contract.does_controller_exist(Controller::new(&did)).unwrap();
+    assert!(!exists);
+}
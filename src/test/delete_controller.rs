@@ -4,7 +4,8 @@ use sylvia::multitest::App;
 
 use crate::{
     contract::sv::mt::{CodeId, DidContractProxy},
-    state::{Controller, Did, DidDocument, DID_PREFIX},
+    state::{Controller, Did, DidDocument},
+    test::{DID_METHOD, DID_PREFIX},
 };
 
 #[test]
@@ -15,13 +16,14 @@ fn delete_existing_controller() {
     let owner = "owner".into_addr();
     let controller_to_delete = "controller_to_delete".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "delete_controller_did");
     let mut original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into(), controller_to_delete.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -78,13 +80,14 @@ fn delete_non_existing_controller() {
     let owner = "owner".into_addr();
     let non_existing_controller = "non_existing_controller".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "delete_non_existing_controller_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -116,13 +119,14 @@ fn delete_last_controller() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "delete_last_controller_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -155,13 +159,14 @@ fn delete_controller_unauthorized() {
     let unauthorized_user = "unauthorized_user".into_addr();
     let controller_to_delete = "controller_to_delete".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "unauthorized_delete_controller_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into(), controller_to_delete.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -178,7 +183,7 @@ fn delete_controller_unauthorized() {
 
     // Verify the error message
     assert_eq!(
-        format!("Unauthorized: {}", unauthorized_user.to_string()),
+        format!("Unauthorized: {}", unauthorized_user),
         result.err().unwrap().to_string()
     );
 
@@ -199,13 +204,14 @@ fn delete_controller_with_index_verification() {
     let owner = "owner".into_addr();
     let controller_to_delete = "controller_to_delete".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "indexed_delete_controller_did");
     let mut original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into(), controller_to_delete.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -256,7 +262,7 @@ fn delete_controller_with_invalid_did_format() {
     let owner = "owner".into_addr();
     let controller_to_delete = "controller_to_delete".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Define a DID with an invalid format
     let invalid_did = "invalid_did_format";
@@ -269,7 +275,7 @@ fn delete_controller_with_invalid_did_format() {
 
     // Verify the error message
     assert_eq!(
-        format!("Did format error: {}", invalid_did),
+        format!("Did format error: {} (missing did scheme)", invalid_did),
         result.err().unwrap().to_string()
     );
 }
@@ -281,13 +287,14 @@ fn delete_controller_with_invalid_controller_format() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "valid_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -327,7 +334,7 @@ fn delete_address_controller_with_did_based_controller() {
     let owner = "owner".into_addr();
     let did_based_controller = format!("{}{}", DID_PREFIX, "did_based_controller");
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     const DID_STORE: Map<String, DidDocument> = Map::new("dids");
 
@@ -336,6 +343,7 @@ fn delete_address_controller_with_did_based_controller() {
         id: Did::new(&did_based_controller),
         controller: vec![did_based_controller.to_string().into()], // Self-controlled DID
         service: vec![],
+        ..Default::default()
     };
 
     {
@@ -361,6 +369,7 @@ fn delete_address_controller_with_did_based_controller() {
         id: Did::new(&did),
         controller: vec![owner.to_string().into(), did_based_controller.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -397,7 +406,7 @@ fn delete_controller_in_did_controlled_by_another_did() {
     let owner = "owner".into_addr();
     let owner2 = "owner2".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the first DID Document (controller DID)
     let controller_did = format!("{}{}", DID_PREFIX, "controller_did");
@@ -405,6 +414,7 @@ fn delete_controller_in_did_controlled_by_another_did() {
         id: Did::new(&controller_did),
         controller: vec![owner.to_string().into()], // Normal address as controller
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -418,6 +428,7 @@ fn delete_controller_in_did_controlled_by_another_did() {
         id: Did::new(&did),
         controller: vec![controller_did.clone().into(), owner2.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -3,7 +3,8 @@ use sylvia::multitest::App;
 
 use crate::{
     contract::sv::mt::{CodeId, DidContractProxy},
-    state::{Did, DidDocument, Service, DID_PREFIX},
+    state::{Did, DidDocument, Service},
+    test::{DID_METHOD, DID_PREFIX},
 };
 
 #[test]
@@ -13,10 +14,10 @@ fn delete_did_document_not_found() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = &format!("{}{}", DID_PREFIX, "did");
-    let no_did = contract.delete_did_document(Did::new(did)).call(&owner);
+    let no_did = contract.delete_did_document(Did::new(did), None).call(&owner);
     assert!(no_did.is_err(), "Expected Err, but got an Ok");
     assert_eq!("Did document not found: type: did_contract::state::DidDocument; key: [00, 04, 64, 69, 64, 73, 64, 69, 64, 3A, 63, 34, 65, 3A, 64, 69, 64] not found", no_did.err().unwrap().to_string());
 }
@@ -29,7 +30,7 @@ fn delete_did_document() {
     // let did_owner = "did_owner";
     let owner_addr = "did_owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner_addr).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner_addr).unwrap();
 
     // let did_owner = "did_owner";
     let did = &format!("{}{}", DID_PREFIX, "new_did");
@@ -38,10 +39,11 @@ fn delete_did_document() {
         // controller: Controllers(vec![owner_addr.to_string().into()]),
         controller: vec![owner_addr.to_string().into()],
         service: vec![Service {
-            a_type: "".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "dfdsfs")),
-            service_endpoint: "dfdsfs".to_string(),
+            a_type: "Service".into(),
+            id: Did::new(&format!("{}#{}", did, "dfdsfs")),
+            service_endpoint: "https://example.com".into(),
         }],
+        ..Default::default()
     };
     let result = contract
         .create_did_document(new_did_doc.clone())
@@ -52,7 +54,7 @@ fn delete_did_document() {
     assert_eq!(new_did_doc.clone(), did_document.clone());
 
     let result = contract
-        .delete_did_document(Did::new(did))
+        .delete_did_document(Did::new(did), None)
         .call(&owner_addr);
     assert!(result.is_ok(), "Expected Ok, but got an Err");
 
@@ -73,7 +75,7 @@ fn delete_did_document_wrong_owner() {
     let owner_addr = "did_owner".into_addr();
     let wrong_owner_addr = "wrong_did_owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner_addr).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner_addr).unwrap();
 
     // let did_owner = "did_owner";
     let did = &format!("{}{}", DID_PREFIX, "new_did");
@@ -82,10 +84,11 @@ fn delete_did_document_wrong_owner() {
         // controller: Controllers(vec![owner_addr.to_string().into()]),
         controller: vec![owner_addr.to_string().into()],
         service: vec![Service {
-            a_type: "".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "dfdsfs")),
-            service_endpoint: "dfdsfs".to_string(),
+            a_type: "Service".into(),
+            id: Did::new(&format!("{}#{}", did, "dfdsfs")),
+            service_endpoint: "https://example.com".into(),
         }],
+        ..Default::default()
     };
     let result = contract
         .create_did_document(new_did_doc.clone())
@@ -96,10 +99,10 @@ fn delete_did_document_wrong_owner() {
     assert_eq!(new_did_doc.clone(), did_document.clone());
 
     let result = contract
-        .delete_did_document(Did::new(did))
+        .delete_did_document(Did::new(did), None)
         .call(&wrong_owner_addr);
     assert!(result.is_err(), "Expected Err, but got an Ok");
-    assert_eq!(format!("Unauthorized: {}", wrong_owner_addr.to_string()), result.err().unwrap().to_string());
+    assert_eq!(format!("Unauthorized: {}", wrong_owner_addr), result.err().unwrap().to_string());
 }
 
 #[test]
@@ -109,13 +112,14 @@ fn delete_existing_did_document() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "delete_existing_did");
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the DID Document
@@ -125,7 +129,7 @@ fn delete_existing_did_document() {
     assert!(result.is_ok(), "Expected Ok, but got an Err");
 
     // Delete the DID Document
-    let result = contract.delete_did_document(Did::new(&did)).call(&owner);
+    let result = contract.delete_did_document(Did::new(&did), None).call(&owner);
     assert!(result.is_ok(), "Expected Ok, but got an Err");
 
     // Verify the DID Document no longer exists
@@ -144,12 +148,12 @@ fn delete_non_existing_did_document() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let non_existing_did = format!("{}{}", DID_PREFIX, "non_existing_did");
 
     // Attempt to delete a non-existing DID Document
-    let result = contract.delete_did_document(Did::new(&non_existing_did)).call(&owner);
+    let result = contract.delete_did_document(Did::new(&non_existing_did), None).call(&owner);
     assert!(result.is_err(), "Expected Err, but got an Ok");
 
     // Verify the error message
@@ -167,13 +171,14 @@ fn delete_did_document_unauthorized() {
     let owner = "owner".into_addr();
     let unauthorized_user = "unauthorized_user".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "unauthorized_delete_did");
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the DID Document
@@ -183,12 +188,12 @@ fn delete_did_document_unauthorized() {
     assert!(result.is_ok(), "Expected Ok, but got an Err");
 
     // Attempt to delete the DID Document by an unauthorized user
-    let result = contract.delete_did_document(Did::new(&did)).call(&unauthorized_user);
+    let result = contract.delete_did_document(Did::new(&did), None).call(&unauthorized_user);
     assert!(result.is_err(), "Expected Err, but got an Ok");
 
     // Verify the error message
     assert_eq!(
-        format!("Unauthorized: {}", unauthorized_user.to_string()),
+        format!("Unauthorized: {}", unauthorized_user),
         result.err().unwrap().to_string()
     );
 
@@ -204,18 +209,18 @@ fn delete_did_document_with_invalid_format() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Define a DID with an invalid format
     let invalid_did = "invalid_did_format";
 
     // Attempt to delete a DID Document with an invalid format
-    let result = contract.delete_did_document(Did::new(invalid_did)).call(&owner);
+    let result = contract.delete_did_document(Did::new(invalid_did), None).call(&owner);
     assert!(result.is_err(), "Expected Err, but got an Ok");
 
     // Verify the error message
     assert_eq!(
-        format!("Did format error: {}", invalid_did),
+        format!("Did format error: {} (missing did scheme)", invalid_did),
         result.err().unwrap().to_string()
     );
 }
@@ -227,7 +232,7 @@ fn delete_did_document_that_is_controller() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the first DID Document
     let controller_did = format!("{}{}", DID_PREFIX, "controller_did");
@@ -235,6 +240,7 @@ fn delete_did_document_that_is_controller() {
         id: Did::new(&controller_did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -248,6 +254,7 @@ fn delete_did_document_that_is_controller() {
         id: Did::new(&did),
         controller: vec![controller_did.clone().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -256,7 +263,7 @@ fn delete_did_document_that_is_controller() {
     assert!(result.is_ok(), "Expected Ok, but got an Err");
 
     // Attempt to delete the first DID Document
-    let result = contract.delete_did_document(Did::new(&controller_did)).call(&owner);
+    let result = contract.delete_did_document(Did::new(&controller_did), None).call(&owner);
     assert!(result.is_err(), "Expected Err, but got an Ok");
 
     // Verify the error message
@@ -277,13 +284,14 @@ fn delete_did_document_with_event_verification() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "event_delete_did");
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the DID Document
@@ -293,7 +301,7 @@ fn delete_did_document_with_event_verification() {
     assert!(result.is_ok(), "Expected Ok, but got an Err");
 
     // Delete the DID Document
-    let result = contract.delete_did_document(Did::new(&did)).call(&owner);
+    let result = contract.delete_did_document(Did::new(&did), None).call(&owner);
     assert!(result.is_ok(), "Expected Ok, but got an Err");
 
     // Check the emitted events
@@ -329,7 +337,7 @@ fn delete_did_document_controlled_by_another_did() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the controller DID Document
     let controller_did = format!("{}{}", DID_PREFIX, "controller_did");
@@ -337,6 +345,7 @@ fn delete_did_document_controlled_by_another_did() {
         id: Did::new(&controller_did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -350,6 +359,7 @@ fn delete_did_document_controlled_by_another_did() {
         id: Did::new(&did),
         controller: vec![controller_did.clone().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -358,7 +368,7 @@ fn delete_did_document_controlled_by_another_did() {
     assert!(result.is_ok(), "Expected Ok, but got an Err");
 
     // Attempt to delete the main DID Document
-    let result = contract.delete_did_document(Did::new(&did)).call(&owner);
+    let result = contract.delete_did_document(Did::new(&did), None).call(&owner);
     assert!(result.is_ok(), "Expected Ok, but got an Err");
 
     // Verify the main DID Document no longer exists
@@ -382,7 +392,7 @@ fn delete_did_document_with_index_verification() {
     let owner = "owner".into_addr();
     // let controller = "controller".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the DID Document
     let did = format!("{}{}", DID_PREFIX, "indexed_did");
@@ -390,6 +400,7 @@ fn delete_did_document_with_index_verification() {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the DID Document
@@ -409,7 +420,7 @@ fn delete_did_document_with_index_verification() {
     );
 
     // Delete the DID Document
-    let result = contract.delete_did_document(Did::new(&did)).call(&owner);
+    let result = contract.delete_did_document(Did::new(&did), None).call(&owner);
     // Check if the result is Ok
     assert!(result.is_ok(), "Expected Ok, but got an Err");
 
@@ -3,7 +3,8 @@ use sylvia::multitest::App;
 
 use crate::{
     contract::sv::mt::{CodeId, DidContractProxy},
-    state::{Did, DidDocument, Service, DID_PREFIX},
+    state::{Did, DidDocument, Service},
+    test::{DID_METHOD, DID_PREFIX},
 };
 
 #[test]
@@ -13,19 +14,20 @@ fn delete_existing_service() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "delete_service_did");
-    let service_id = format!("{}{}", DID_PREFIX, "service1");
+    let service_id = format!("{}#{}", did, "service1");
 
     let mut original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
             id: Did::new(&service_id),
-            a_type: "ServiceType".to_string(),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -53,7 +55,7 @@ fn delete_non_existing_service() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "delete_non_existing_service_did");
     let non_existing_service_id = format!("{}{}", DID_PREFIX, "non_existing_service");
@@ -62,6 +64,7 @@ fn delete_non_existing_service() {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -90,10 +93,10 @@ fn delete_service_from_non_existing_did_document() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let non_existing_did = format!("{}{}", DID_PREFIX, "non_existing_did");
-    let service_id = format!("{}{}", DID_PREFIX, "service1");
+    let service_id = format!("{}#{}", non_existing_did, "service1");
 
     // Attempt to delete a service from a non-existing DID Document
     let result = contract
@@ -116,19 +119,20 @@ fn delete_service_unauthorized() {
     let owner = "owner".into_addr();
     let unauthorized_user = "unauthorized_user".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "unauthorized_delete_service_did");
-    let service_id = format!("{}{}", DID_PREFIX, "service1");
+    let service_id = format!("{}#{}", did, "service1");
 
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
             id: Did::new(&service_id),
-            a_type: "ServiceType".to_string(),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -145,7 +149,7 @@ fn delete_service_unauthorized() {
 
     // Verify the error message
     assert_eq!(
-        format!("Unauthorized: {}", unauthorized_user.to_string()),
+        format!("Unauthorized: {}", unauthorized_user),
         result.err().unwrap().to_string()
     );
 
@@ -164,7 +168,7 @@ fn delete_service_with_invalid_did_format() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Define a DID with an invalid format
     let invalid_did = "invalid_did_format";
@@ -178,7 +182,7 @@ fn delete_service_with_invalid_did_format() {
 
     // Verify the error message
     assert_eq!(
-        format!("Did format error: {}", invalid_did),
+        format!("Did format error: {} (missing did scheme)", invalid_did),
         result.err().unwrap().to_string()
     );
 }
@@ -190,7 +194,7 @@ fn delete_service_with_invalid_service_id_format() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "valid_did");
     let invalid_service_id = "invalid_service_id";
@@ -199,6 +203,7 @@ fn delete_service_with_invalid_service_id_format() {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -215,7 +220,7 @@ fn delete_service_with_invalid_service_id_format() {
 
     // Verify the error message
     assert_eq!(
-        format!("Did format error: {}", invalid_service_id),
+        format!("Did format error: {} (missing did scheme)", invalid_service_id),
         result.err().unwrap().to_string()
     );
 }
@@ -227,19 +232,20 @@ fn delete_service_with_event_verification() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "event_service_did");
-    let service_id = format!("{}{}", DID_PREFIX, "service1");
+    let service_id = format!("{}#{}", did, "service1");
 
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
             id: Did::new(&service_id),
-            a_type: "ServiceType".to_string(),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -290,19 +296,20 @@ fn delete_service_from_did_with_multiple_controllers() {
     let owner = "owner".into_addr();
     let controller2 = "controller2".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "multi_controller_service_did");
-    let service_id = format!("{}{}", DID_PREFIX, "service1");
+    let service_id = format!("{}#{}", did, "service1");
 
     let mut original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into(), controller2.to_string().into()],
         service: vec![Service {
             id: Did::new(&service_id),
-            a_type: "ServiceType".to_string(),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -330,15 +337,16 @@ fn delete_service_from_did_with_no_services() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "no_services_did");
-    let service_id = format!("{}{}", DID_PREFIX, "service1");
+    let service_id = format!("{}#{}", did, "service1");
 
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -367,7 +375,7 @@ fn delete_service_from_did_controlled_by_another_did() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the controller DID Document
     let controller_did = format!("{}{}", DID_PREFIX, "controller_did");
@@ -375,6 +383,7 @@ fn delete_service_from_did_controlled_by_another_did() {
         id: Did::new(&controller_did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -384,16 +393,17 @@ fn delete_service_from_did_controlled_by_another_did() {
 
     // Create the main DID Document controlled by the controller DID
     let did = format!("{}{}", DID_PREFIX, "main_did");
-    let service_id = format!("{}{}", DID_PREFIX, "service1");
+    let service_id = format!("{}#{}", did, "service1");
 
     let mut original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![controller_did.clone().into()],
         service: vec![Service {
             id: Did::new(&service_id),
-            a_type: "ServiceType".to_string(),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -411,4 +421,38 @@ fn delete_service_from_did_controlled_by_another_did() {
     original_did_doc.service.retain(|s| s.id != Did::new(&service_id));
     let updated_did_doc = contract.get_did_document(Did::new(&did)).unwrap();
     assert_eq!(original_did_doc, updated_did_doc, "DID Document was not updated correctly");
-}
\ No newline at end of file
+}
+
+#[test]
+fn delete_service_by_relative_id() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "relative_delete_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![Service {
+            id: Did::new(&format!("{}#service1", did)),
+            a_type: "Service".into(),
+            service_endpoint: "https://example.com".into(),
+        }],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let res = contract
+        .delete_service(Did::new(&did), Did::new("#service1"))
+        .call(&owner)
+        .expect("Failed to get result");
+    assert_eq!(res.events[1].attributes[2].key, "old_service");
+    assert_eq!(res.events[1].attributes[2].value, format!("{}#service1", did));
+
+    let did_document = contract.get_did_document(Did::new(&did)).unwrap();
+    assert!(did_document.service.is_empty());
+}
@@ -0,0 +1,144 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{Did, DidDocument, VerificationMethod},
+    test::{DID_METHOD, DID_PREFIX},
+};
+
+fn verification_method(id: &str, controller: &str) -> VerificationMethod {
+    VerificationMethod {
+        id: Did::new(id),
+        a_type: "EcdsaSecp256k1RecoveryMethod2020".to_string(),
+        controller: Did::new(controller),
+        public_key_multibase: None,
+        public_key_jwk: None,
+        blockchain_account_id: Some("cosmos:c4e:c4e1qkphn8h2rnyqjjtfh8j8dtuqgh5cac57nq2286".to_string()),
+    }
+}
+
+#[test]
+fn delete_existing_verification_method() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "delete_vm_did");
+    let method_id = format!("{}#{}", did, "key1");
+
+    let mut original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        verification_method: vec![verification_method(&method_id, &did)],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .delete_verification_method(Did::new(&did), Did::new(&method_id))
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // Check the emitted event
+    let res = result.expect("Failed to get result");
+    assert_eq!(res.events[1].ty, "wasm-delete_verification_method");
+    assert_eq!(res.events[1].attributes[1].key, "did");
+    assert_eq!(res.events[1].attributes[1].value, did.to_string());
+    assert_eq!(res.events[1].attributes[2].key, "old_verification_method");
+    assert_eq!(res.events[1].attributes[2].value, method_id);
+
+    // Verify the updated DID Document
+    original_did_doc
+        .verification_method
+        .retain(|m| m.id != Did::new(&method_id));
+    let updated_did_doc = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(original_did_doc, updated_did_doc, "DID Document was not updated correctly");
+}
+
+#[test]
+fn delete_non_existing_verification_method() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "delete_non_existing_vm_did");
+    let non_existing_method_id = format!("{}{}", DID_PREFIX, "non_existing_key");
+
+    let original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .delete_verification_method(Did::new(&did), Did::new(&non_existing_method_id))
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+
+    assert_eq!(
+        format!(
+            "Did document verification method does not exist: {}",
+            non_existing_method_id
+        ),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn delete_verification_method_unauthorized() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let unauthorized_user = "unauthorized_user".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "unauthorized_delete_vm_did");
+    let method_id = format!("{}#{}", did, "key1");
+
+    let original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        verification_method: vec![verification_method(&method_id, &did)],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .delete_verification_method(Did::new(&did), Did::new(&method_id))
+        .call(&unauthorized_user);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+
+    assert_eq!(
+        format!("Unauthorized: {}", unauthorized_user),
+        result.err().unwrap().to_string()
+    );
+
+    // Verify that the DID Document was not updated
+    let current_did_doc = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(original_did_doc, current_did_doc, "DID Document was updated incorrectly");
+}
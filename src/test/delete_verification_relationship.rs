@@ -0,0 +1,149 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{Did, DidDocument, RelationshipMethod, VerificationRelationship},
+    test::{verification_method, DID_METHOD, DID_PREFIX},
+};
+
+#[test]
+fn delete_existing_verification_relationship() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "delete_relationship_did");
+    let method = verification_method("key1", &did);
+    let mut original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        verification_method: vec![method.clone()],
+        authentication: vec![RelationshipMethod::Reference(method.id.clone())],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .delete_verification_relationship(
+            Did::new(&did),
+            VerificationRelationship::Authentication,
+            method.id.clone(),
+        )
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // Check the emitted event
+    let res = result.expect("Failed to get result");
+    assert_eq!(res.events[1].ty, "wasm-delete_verification_relationship");
+    assert_eq!(res.events[1].attributes[2].key, "relationship");
+    assert_eq!(res.events[1].attributes[2].value, "authentication");
+    assert_eq!(res.events[1].attributes[3].key, "old_method");
+    assert_eq!(res.events[1].attributes[3].value, method.id.to_string());
+
+    // Verify the updated DID Document
+    original_did_doc.authentication.clear();
+    let updated_did_doc = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(original_did_doc, updated_did_doc, "DID Document was not updated correctly");
+
+    // The referenced verification method can be deleted now
+    let result = contract
+        .delete_verification_method(Did::new(&did), method.id.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+}
+
+#[test]
+fn delete_non_existing_verification_relationship() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "delete_non_existing_relationship_did");
+    let method = verification_method("key1", &did);
+    let original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        verification_method: vec![method.clone()],
+        authentication: vec![RelationshipMethod::Reference(method.id.clone())],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // The method is in authentication, not in assertion_method
+    let result = contract
+        .delete_verification_relationship(
+            Did::new(&did),
+            VerificationRelationship::AssertionMethod,
+            method.id.clone(),
+        )
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+
+    assert_eq!(
+        format!(
+            "Did document verification relationship method does not exist: {}",
+            method.id
+        ),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn delete_referenced_verification_method() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "delete_referenced_method_did");
+    let method = verification_method("key1", &did);
+    let original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        verification_method: vec![method.clone()],
+        assertion_method: vec![RelationshipMethod::Reference(method.id.clone())],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .delete_verification_method(Did::new(&did), method.id.clone())
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+
+    assert_eq!(
+        format!(
+            "Verification method is referenced by a verification relationship: {}",
+            method.id
+        ),
+        result.err().unwrap().to_string()
+    );
+
+    // Verify that the DID Document was not updated
+    let current_did_doc = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(original_did_doc, current_did_doc, "DID Document was updated incorrectly");
+}
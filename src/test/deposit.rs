@@ -0,0 +1,328 @@
+use cosmwasm_std::{coin, coins, to_json_vec, Addr, Uint128};
+use sylvia::cw_multi_test::{App as MtApp, IntoAddr};
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{ConfigUpdate, DepositConfig, DepositShare, Did, DidDocument, Service},
+    test::{did_doc, DID_METHOD, DID_PREFIX},
+};
+
+const DENOM: &str = "ucosm";
+
+fn with_deposit(amount: u128, per_byte: u128) -> Option<ConfigUpdate> {
+    Some(ConfigUpdate {
+        deposit: Some(DepositConfig {
+            denom: DENOM.to_string(),
+            amount: Uint128::new(amount),
+            per_byte: Uint128::new(per_byte),
+        }),
+        ..Default::default()
+    })
+}
+
+fn share(depositor: &Addr, amount: u128) -> DepositShare {
+    DepositShare {
+        depositor: depositor.clone(),
+        amount: Uint128::new(amount),
+    }
+}
+
+fn required(did_doc: &DidDocument) -> u128 {
+    100 + to_json_vec(did_doc).unwrap().len() as u128
+}
+
+fn balance(app: &App<MtApp>, addr: &Addr) -> u128 {
+    app.app().wrap().query_balance(addr, DENOM).unwrap().amount.u128()
+}
+
+fn funded_app(accounts: &[&Addr]) -> App<MtApp> {
+    let app: App<MtApp> = App::default();
+    app.app_mut().init_modules(|router, _, storage| {
+        for addr in accounts {
+            router
+                .bank
+                .init_balance(storage, addr, coins(10_000, DENOM))
+                .unwrap();
+        }
+    });
+    app
+}
+
+#[test]
+fn create_did_document_requires_deposit() {
+    let owner = "owner".into_addr();
+    let app = funded_app(&[&owner]);
+    let code_id = CodeId::store_code(&app);
+
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, with_deposit(100, 1))
+        .call(&owner)
+        .unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "deposit_did");
+    let new_did_doc = did_doc(&did, owner.as_str());
+    let amount = required(&new_did_doc);
+
+    let result = contract.create_did_document(new_did_doc.clone()).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Insufficient deposit for {}: {}{} more required", did, amount, DENOM),
+        result.err().unwrap().to_string()
+    );
+
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .with_funds(&[coin(amount - 1, DENOM)])
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+
+    // Anything beyond the deposit is returned
+    let res = contract
+        .create_did_document(new_did_doc)
+        .with_funds(&[coin(1_000, DENOM)])
+        .call(&owner)
+        .expect("Failed to get result");
+    let event = res.events.iter().find(|e| e.ty == "wasm-deposit").unwrap();
+    assert_eq!(event.attributes[1].key, "did");
+    assert_eq!(event.attributes[1].value, did);
+    assert_eq!(event.attributes[2].key, "amount");
+    assert_eq!(event.attributes[2].value, format!("{}{}", amount, DENOM));
+
+    let deposit = contract.get_deposit(Did::new(&did)).unwrap().unwrap();
+    assert_eq!(coin(amount, DENOM), deposit.amount);
+    assert_eq!(vec![share(&owner, amount)], deposit.shares);
+    assert_eq!(10_000 - amount, balance(&app, &owner));
+    assert_eq!(amount, balance(&app, &contract.contract_addr));
+}
+
+#[test]
+fn update_did_document_adjusts_deposit() {
+    let owner = "owner".into_addr();
+    let app = funded_app(&[&owner]);
+    let code_id = CodeId::store_code(&app);
+
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, with_deposit(100, 1))
+        .call(&owner)
+        .unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "growing_did");
+    let small_did_doc = did_doc(&did, owner.as_str());
+    let small_amount = required(&small_did_doc);
+    let result = contract
+        .create_did_document(small_did_doc.clone())
+        .with_funds(&[coin(small_amount, DENOM)])
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let mut large_did_doc = small_did_doc.clone();
+    large_did_doc.service.push(Service {
+        id: Did::new(&format!("{}#{}", did, "service1")),
+        a_type: "LinkedDomains".into(),
+        service_endpoint: "https://example.com".into(),
+    });
+    let large_amount = required(&large_did_doc);
+
+    let result = contract.update_did_document(large_did_doc.clone()).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!(
+            "Insufficient deposit for {}: {}{} more required",
+            did,
+            large_amount - small_amount,
+            DENOM
+        ),
+        result.err().unwrap().to_string()
+    );
+
+    let result = contract
+        .update_did_document(large_did_doc)
+        .with_funds(&[coin(large_amount - small_amount, DENOM)])
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    let deposit = contract.get_deposit(Did::new(&did)).unwrap().unwrap();
+    assert_eq!(coin(large_amount, DENOM), deposit.amount);
+    assert_eq!(10_000 - large_amount, balance(&app, &owner));
+
+    // Shrinking the document refunds the surplus
+    let result = contract.update_did_document(small_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    let deposit = contract.get_deposit(Did::new(&did)).unwrap().unwrap();
+    assert_eq!(coin(small_amount, DENOM), deposit.amount);
+    assert_eq!(10_000 - small_amount, balance(&app, &owner));
+    assert_eq!(small_amount, balance(&app, &contract.contract_addr));
+}
+
+#[test]
+fn delete_did_document_refunds_deposit() {
+    let owner = "owner".into_addr();
+    let beneficiary = "beneficiary".into_addr();
+    let app = funded_app(&[&owner]);
+    let code_id = CodeId::store_code(&app);
+
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, with_deposit(100, 1))
+        .call(&owner)
+        .unwrap();
+
+    let did1 = format!("{}{}", DID_PREFIX, "refunded_did1");
+    let did2 = format!("{}{}", DID_PREFIX, "refunded_did2");
+    let amount1 = required(&did_doc(&did1, owner.as_str()));
+    let amount2 = required(&did_doc(&did2, owner.as_str()));
+    for (did, amount) in [(&did1, amount1), (&did2, amount2)] {
+        let result = contract
+            .create_did_document(did_doc(did, owner.as_str()))
+            .with_funds(&[coin(amount, DENOM)])
+            .call(&owner);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+    }
+    assert_eq!(10_000 - amount1 - amount2, balance(&app, &owner));
+
+    let result = contract
+        .delete_did_document(Did::new(&did1), Some(beneficiary.to_string()))
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    assert_eq!(amount1, balance(&app, &beneficiary));
+    assert_eq!(None, contract.get_deposit(Did::new(&did1)).unwrap());
+
+    // Without a refund address the depositor gets it back
+    let result = contract.delete_did_document(Did::new(&did2), None).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    assert_eq!(10_000 - amount1, balance(&app, &owner));
+    assert_eq!(None, contract.get_deposit(Did::new(&did2)).unwrap());
+    assert_eq!(0, balance(&app, &contract.contract_addr));
+}
+
+#[test]
+fn deposit_paid_by_several_depositors() {
+    let owner = "owner".into_addr();
+    let other = "other".into_addr();
+    let beneficiary = "beneficiary".into_addr();
+    let app = funded_app(&[&owner, &other]);
+    let code_id = CodeId::store_code(&app);
+
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, with_deposit(100, 1))
+        .call(&owner)
+        .unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "shared_did");
+    let small_did_doc = DidDocument {
+        controller: vec![owner.to_string().into(), other.to_string().into()],
+        ..did_doc(&did, owner.as_str())
+    };
+    let small_amount = required(&small_did_doc);
+    let result = contract
+        .create_did_document(small_did_doc.clone())
+        .with_funds(&[coin(small_amount, DENOM)])
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let mut large_did_doc = small_did_doc.clone();
+    large_did_doc.service.push(Service {
+        id: Did::new(&format!("{}#{}", did, "service1")),
+        a_type: "LinkedDomains".into(),
+        service_endpoint: "https://example.com".into(),
+    });
+    let top_up = required(&large_did_doc) - small_amount;
+
+    // The second controller pays the top-up and owns that share
+    let result = contract
+        .update_did_document(large_did_doc.clone())
+        .with_funds(&[coin(top_up, DENOM)])
+        .call(&other);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    let deposit = contract.get_deposit(Did::new(&did)).unwrap().unwrap();
+    assert_eq!(
+        vec![share(&owner, small_amount), share(&other, top_up)],
+        deposit.shares
+    );
+
+    // Surplus goes back to the latest depositor, whoever shrinks the document
+    let result = contract.update_did_document(small_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    assert_eq!(10_000, balance(&app, &other));
+    assert_eq!(10_000 - small_amount, balance(&app, &owner));
+    let deposit = contract.get_deposit(Did::new(&did)).unwrap().unwrap();
+    assert_eq!(vec![share(&owner, small_amount)], deposit.shares);
+
+    let result = contract
+        .update_did_document(large_did_doc)
+        .with_funds(&[coin(top_up, DENOM)])
+        .call(&other);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // On delete every depositor gets their share; only the sender's follows
+    // the refund address
+    let result = contract
+        .delete_did_document(Did::new(&did), Some(beneficiary.to_string()))
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    assert_eq!(small_amount, balance(&app, &beneficiary));
+    assert_eq!(10_000, balance(&app, &other));
+    assert_eq!(0, balance(&app, &contract.contract_addr));
+}
+
+#[test]
+fn deactivate_did_document_refunds_deposit() {
+    let owner = "owner".into_addr();
+    let app = funded_app(&[&owner]);
+    let code_id = CodeId::store_code(&app);
+
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, with_deposit(100, 1))
+        .call(&owner)
+        .unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "deactivated_did");
+    let amount = required(&did_doc(&did, owner.as_str()));
+    let result = contract
+        .create_did_document(did_doc(&did, owner.as_str()))
+        .with_funds(&[coin(amount, DENOM)])
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    assert_eq!(10_000 - amount, balance(&app, &owner));
+
+    // A tombstone can never be changed or deleted, so it holds no deposit
+    let result = contract.deactivate_did_document(Did::new(&did)).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    assert_eq!(10_000, balance(&app, &owner));
+    assert_eq!(None, contract.get_deposit(Did::new(&did)).unwrap());
+    assert_eq!(0, balance(&app, &contract.contract_addr));
+}
+
+#[test]
+fn invalid_deposit() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let update = ConfigUpdate {
+        deposit: Some(DepositConfig {
+            denom: "".to_string(),
+            amount: Uint128::new(100),
+            per_byte: Uint128::zero(),
+        }),
+        ..Default::default()
+    };
+    let result = code_id
+        .instantiate(DID_METHOD.to_string(), None, Some(update))
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        "Invalid deposit: denom must not be empty",
+        result.err().unwrap().to_string()
+    );
+
+    // Without a deposit configured no funds are kept
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, None)
+        .call(&owner)
+        .unwrap();
+    let did = format!("{}{}", DID_PREFIX, "free_did");
+    let result = contract.create_did_document(did_doc(&did, owner.as_str())).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    assert_eq!(None, contract.get_deposit(Did::new(&did)).unwrap());
+}
@@ -0,0 +1,265 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{
+        BlockStamp, DereferencedContent, Did, DidDereferencingError, DidDereferencingResult,
+        DidDocument, DidDocumentMetadata, RelationshipMethod, Service, ServiceEndpoint,
+        DID_JSON_CONTENT_TYPE, JSON_CONTENT_TYPE, URI_LIST_CONTENT_TYPE,
+    },
+    test::{verification_method, DID_METHOD, DID_PREFIX},
+};
+
+fn did_document(did: &str, owner: &str) -> DidDocument {
+    let method = verification_method("key-1", did);
+    DidDocument {
+        id: Did::new(did),
+        controller: vec![owner.into()],
+        service: vec![
+            Service {
+                id: Did::new(&format!("{}#files", did)),
+                a_type: "FileService".into(),
+                service_endpoint: "https://files.example.com/store/".into(),
+            },
+            Service {
+                id: Did::new(&format!("{}#hub", did)),
+                a_type: "Hub".into(),
+                service_endpoint: ServiceEndpoint::Set(vec![
+                    "https://hub1.example.com".into(),
+                    "https://hub2.example.com".into(),
+                ]),
+            },
+        ],
+        verification_method: vec![method.clone()],
+        authentication: vec![RelationshipMethod::Reference(method.id)],
+        key_agreement: vec![RelationshipMethod::Embedded(Box::new(verification_method(
+            "key-2", did,
+        )))],
+        ..Default::default()
+    }
+}
+
+fn dereferenced_url(result: DidDereferencingResult) -> String {
+    assert_eq!(
+        Some(URI_LIST_CONTENT_TYPE.to_string()),
+        result.dereferencing_metadata.content_type
+    );
+    match result.content_stream {
+        Some(DereferencedContent::Url(url)) => url,
+        other => panic!("unexpected content: {:?}", other),
+    }
+}
+
+#[test]
+fn dereference_did_document() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "dereferenced_did");
+    let new_did_doc = did_document(&did, owner.as_str());
+    let result = contract.create_did_document(new_did_doc.clone()).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let created = BlockStamp::from(&app.block_info());
+    let result = contract.dereference(Did::new(&did)).unwrap();
+    assert_eq!(
+        Some(DereferencedContent::DidDocument(new_did_doc)),
+        result.content_stream
+    );
+    assert_eq!(
+        DidDocumentMetadata {
+            created: Some(created),
+            version_id: Some(1),
+            ..Default::default()
+        },
+        result.content_metadata
+    );
+    assert_eq!(
+        Some(DID_JSON_CONTENT_TYPE.to_string()),
+        result.dereferencing_metadata.content_type
+    );
+    assert_eq!(None, result.dereferencing_metadata.error);
+}
+
+#[test]
+fn dereference_fragments() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "fragments_did");
+    let new_did_doc = did_document(&did, owner.as_str());
+    let result = contract.create_did_document(new_did_doc.clone()).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .dereference(Did::new(&format!("{}#files", did)))
+        .unwrap();
+    assert_eq!(
+        Some(DereferencedContent::Service(new_did_doc.service[0].clone())),
+        result.content_stream
+    );
+    assert_eq!(
+        Some(JSON_CONTENT_TYPE.to_string()),
+        result.dereferencing_metadata.content_type
+    );
+
+    let result = contract
+        .dereference(Did::new(&format!("{}#key-1", did)))
+        .unwrap();
+    assert_eq!(
+        Some(DereferencedContent::VerificationMethod(
+            new_did_doc.verification_method[0].clone()
+        )),
+        result.content_stream
+    );
+
+    // Methods embedded in a relationship are found as well
+    let result = contract
+        .dereference(Did::new(&format!("{}#key-2", did)))
+        .unwrap();
+    assert_eq!(
+        Some(DereferencedContent::VerificationMethod(verification_method(
+            "key-2", &did
+        ))),
+        result.content_stream
+    );
+
+    let result = contract
+        .dereference(Did::new(&format!("{}#unknown", did)))
+        .unwrap();
+    assert_eq!(None, result.content_stream);
+    assert_eq!(
+        Some(DidDereferencingError::NotFound),
+        result.dereferencing_metadata.error
+    );
+}
+
+#[test]
+fn dereference_service_endpoint() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "service_url_did");
+    let result = contract
+        .create_did_document(did_document(&did, owner.as_str()))
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .dereference(Did::new(&format!("{}?service=files", did)))
+        .unwrap();
+    assert_eq!("https://files.example.com/store/", dereferenced_url(result));
+
+    let result = contract
+        .dereference(Did::new(&format!(
+            "{}?service=files&relativeRef=a/b.json",
+            did
+        )))
+        .unwrap();
+    assert_eq!(
+        "https://files.example.com/store/a/b.json",
+        dereferenced_url(result)
+    );
+
+    // Percent-encoded absolute path, with the fragment carried over
+    let result = contract
+        .dereference(Did::new(&format!(
+            "{}?service=files&relativeRef=%2Fa.json#part",
+            did
+        )))
+        .unwrap();
+    assert_eq!(
+        "https://files.example.com/a.json#part",
+        dereferenced_url(result)
+    );
+
+    // The first URI of an endpoint set
+    let result = contract
+        .dereference(Did::new(&format!("{}?service=hub&relativeRef=/inbox", did)))
+        .unwrap();
+    assert_eq!("https://hub1.example.com/inbox", dereferenced_url(result));
+
+    let result = contract
+        .dereference(Did::new(&format!("{}?service=unknown", did)))
+        .unwrap();
+    assert_eq!(
+        Some(DidDereferencingError::NotFound),
+        result.dereferencing_metadata.error
+    );
+}
+
+#[test]
+fn dereference_errors() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "errors_did");
+    let result = contract
+        .create_did_document(did_document(&did, owner.as_str()))
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let cases = [
+        ("invalid_did_url".to_string(), DidDereferencingError::InvalidDidUrl),
+        (format!("{}?a=<b>", did), DidDereferencingError::InvalidDidUrl),
+        (format!("{}?relativeRef=/a", did), DidDereferencingError::InvalidDidUrl),
+        (
+            format!("{}?service=files&service=hub", did),
+            DidDereferencingError::InvalidDidUrl,
+        ),
+        (format!("{}?versionId=1", did), DidDereferencingError::InvalidDidUrl),
+        ("did:other:abc#key-1".to_string(), DidDereferencingError::MethodNotSupported),
+        (
+            format!("{}{}", DID_PREFIX, "missing_did#key-1"),
+            DidDereferencingError::NotFound,
+        ),
+        (format!("{}/path", did), DidDereferencingError::NotFound),
+    ];
+    for (did_url, error) in cases {
+        let result = contract.dereference(Did::new(&did_url)).unwrap();
+        assert_eq!(
+            DidDereferencingResult::error(error),
+            result,
+            "Unexpected result for {}",
+            did_url
+        );
+    }
+}
+
+#[test]
+fn dereferencing_error_codes() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let cases = [
+        ("invalid_did_url".to_string(), "invalidDidUrl"),
+        ("did:other:abc#key-1".to_string(), "methodNotSupported"),
+        (format!("{}{}", DID_PREFIX, "missing_did#key-1"), "notFound"),
+    ];
+    for (did_url, code) in cases {
+        let result = contract.dereference(Did::new(&did_url)).unwrap();
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(code, json["dereferencing_metadata"]["error"], "Unexpected code for {}", did_url);
+    }
+}
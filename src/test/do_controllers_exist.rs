@@ -3,7 +3,8 @@ use sylvia::multitest::App;
 
 use crate::{
     contract::sv::mt::{CodeId, DidContractProxy},
-    state::{Controller, Did, DidDocument, DID_PREFIX},
+    state::{Controller, Did, DidDocument},
+    test::{DID_METHOD, DID_PREFIX},
 };
 
 #[test]
@@ -16,13 +17,14 @@ fn do_controllers_exist_test() {
     let controller2 = format!("{}{}", DID_PREFIX, "controller2");
     let non_existing_controller = format!("{}{}", DID_PREFIX, "non_existing_controller");
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the first DID Document (controller1)
     let controller1_doc = DidDocument {
         id: Did::new(&controller1),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -35,6 +37,7 @@ fn do_controllers_exist_test() {
         id: Did::new(&controller2),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -82,13 +85,14 @@ fn all_controllers_exist() {
     let controller1 = format!("{}{}", DID_PREFIX, "controller1");
     let controller2 = format!("{}{}", DID_PREFIX, "controller2");
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the first DID Document (controller1)
     let controller1_doc = DidDocument {
         id: Did::new(&controller1),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
     let result = contract
         .create_did_document(controller1_doc.clone())
@@ -100,6 +104,7 @@ fn all_controllers_exist() {
         id: Did::new(&controller2),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
     let result = contract
         .create_did_document(controller2_doc.clone())
@@ -124,13 +129,14 @@ fn some_controllers_do_not_exist() {
     let controller1 = format!("{}{}", DID_PREFIX, "controller1");
     let non_existing_controller = format!("{}{}", DID_PREFIX, "non_existing_controller");
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the first DID Document (controller1)
     let controller1_doc = DidDocument {
         id: Did::new(&controller1),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
     let result = contract
         .create_did_document(controller1_doc.clone())
@@ -155,7 +161,7 @@ fn no_controllers_exist() {
     let non_existing_controller1 = format!("{}{}", DID_PREFIX, "non_existing_controller1");
     let non_existing_controller2 = format!("{}{}", DID_PREFIX, "non_existing_controller2");
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Check if no controllers exist
     let controllers = vec![
@@ -173,7 +179,7 @@ fn empty_controller_list() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Check with an empty controller list
     let controllers: Vec<Controller> = vec![];
@@ -195,7 +201,7 @@ fn invalid_controller_format() {
     let owner = "owner".into_addr();
     let invalid_controller = "invalid_controller_format";
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Attempt to check with an invalid controller format
     let controllers = vec![Controller::new(invalid_controller)];
@@ -220,13 +226,14 @@ fn mixed_valid_and_invalid_controllers() {
     let valid_controller = format!("{}{}", DID_PREFIX, "valid_controller");
     let invalid_controller = "invalid_controller_format";
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the valid controller DID Document
     let valid_controller_doc = DidDocument {
         id: Did::new(&valid_controller),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
     let result = contract
         .create_did_document(valid_controller_doc.clone())
@@ -256,13 +263,14 @@ fn did_based_controllers() {
     let owner = "owner".into_addr();
     let did_controller = format!("{}{}", DID_PREFIX, "did_controller");
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the DID-based controller
     let did_controller_doc = DidDocument {
         id: Did::new(&did_controller),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
     let result = contract
         .create_did_document(did_controller_doc.clone())
@@ -282,10 +290,10 @@ fn address_based_controllers() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Check if the address-based controller exists
-    let controllers = vec![Controller::new(&owner.to_string())];
+    let controllers = vec![Controller::new(owner.as_ref())];
     let result = contract.do_controllers_exist(controllers.clone()).unwrap();
     assert!(result, "Expected the address-based controller to exist, but got false");
 }
\ No newline at end of file
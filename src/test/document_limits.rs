@@ -0,0 +1,268 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{
+        ConfigUpdate, Did, DidDocument, DocumentLimits, RelationshipMethod,
+        VerificationRelationship,
+    },
+    test::{service, verification_method, DID_METHOD, DID_PREFIX},
+};
+
+fn with_limits(limits: DocumentLimits) -> Option<ConfigUpdate> {
+    Some(ConfigUpdate {
+        document_limits: Some(limits),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn verification_method_limit() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let limits = DocumentLimits {
+        max_verification_methods: 1,
+        ..Default::default()
+    };
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, with_limits(limits))
+        .call(&owner)
+        .unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "vm_limit_did");
+    let did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        verification_method: vec![verification_method("key-1", &did)],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .add_verification_method(Did::new(&did), verification_method("key-2", &did))
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Did document {} has more than 1 verification methods", did),
+        result.err().unwrap().to_string()
+    );
+
+    // Methods embedded in a relationship count too
+    let result = contract
+        .add_verification_relationship(
+            Did::new(&did),
+            VerificationRelationship::Authentication,
+            RelationshipMethod::Embedded(Box::new(verification_method("key-2", &did))),
+        )
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Did document {} has more than 1 verification methods", did),
+        result.err().unwrap().to_string()
+    );
+
+    let result = contract
+        .add_verification_relationship(
+            Did::new(&did),
+            VerificationRelationship::Authentication,
+            RelationshipMethod::Reference(verification_method("key-1", &did).id),
+        )
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+}
+
+#[test]
+fn id_length_limit() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let limits = DocumentLimits {
+        max_id_length: 72,
+        ..Default::default()
+    };
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, with_limits(limits))
+        .call(&owner)
+        .unwrap();
+
+    let long_did = format!("{}{}", DID_PREFIX, "a".repeat(72));
+    let did_doc = DidDocument {
+        id: Did::new(&long_did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(did_doc).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Id longer than 72 bytes: {}", long_did),
+        result.err().unwrap().to_string()
+    );
+
+    let did = format!("{}{}", DID_PREFIX, "short");
+    let did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let long_service = service(&"s".repeat(60), &did, "Service", "https://example.com");
+    let result = contract
+        .add_service(Did::new(&did), long_service.clone())
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Id longer than 72 bytes: {}", long_service.id),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn type_and_endpoint_length_limits() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let limits = DocumentLimits {
+        max_type_length: 16,
+        max_endpoint_length: 32,
+        ..Default::default()
+    };
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, with_limits(limits))
+        .call(&owner)
+        .unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "length_limit_did");
+    let did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![service("service1", &did, "LinkedDomains", "https://example.com")],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .add_service(
+            Did::new(&did),
+            service("service2", &did, "DIDCommMessagingService", "https://example.com"),
+        )
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        "Type longer than 16 bytes: DIDCommMessagingService",
+        result.err().unwrap().to_string()
+    );
+
+    // Verification method types are bound by the same limit
+    let result = contract
+        .add_verification_method(Did::new(&did), verification_method("key-1", &did))
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        "Type longer than 16 bytes: Ed25519VerificationKey2020",
+        result.err().unwrap().to_string()
+    );
+
+    let service_id = service("service1", &did, "LinkedDomains", "https://example.com").id;
+    let result = contract
+        .update_service(
+            Did::new(&did),
+            service_id.clone(),
+            None,
+            Some("https://example.com/a/much/longer/path".into()),
+        )
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Service endpoint of {} longer than 32 bytes", service_id),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn endpoint_length_above_default() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let limits = DocumentLimits {
+        max_endpoint_length: 4096,
+        ..Default::default()
+    };
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, with_limits(limits))
+        .call(&owner)
+        .unwrap();
+
+    // Only the configured limit bounds an endpoint
+    let did = format!("{}{}", DID_PREFIX, "long_endpoint_did");
+    let endpoint = format!("https://example.com/{}", "a".repeat(3000));
+    let did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![service("service1", &did, "LinkedDomains", &endpoint)],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let endpoint = format!("https://example.com/{}", "a".repeat(4096));
+    let service2 = service("service2", &did, "LinkedDomains", &endpoint);
+    let result = contract.add_service(Did::new(&did), service2.clone()).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Service endpoint of {} longer than 4096 bytes", service2.id),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn invalid_document_limits() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let limits = DocumentLimits {
+        max_id_length: 0,
+        ..Default::default()
+    };
+    let result = code_id
+        .instantiate(DID_METHOD.to_string(), None, with_limits(limits))
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        "Invalid document limits: max_id_length must be positive",
+        result.err().unwrap().to_string()
+    );
+
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, None)
+        .call(&owner)
+        .unwrap();
+    let limits = DocumentLimits {
+        max_endpoint_length: 0,
+        ..Default::default()
+    };
+    let result = contract.update_config(with_limits(limits).unwrap()).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        "Invalid document limits: max_endpoint_length must be positive",
+        result.err().unwrap().to_string()
+    );
+}
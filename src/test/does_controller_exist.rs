@@ -3,7 +3,8 @@ use sylvia::multitest::App;
 
 use crate::{
     contract::sv::mt::{CodeId, DidContractProxy},
-    state::{Controller, Did, DidDocument, DID_PREFIX},
+    state::{Controller, Did, DidDocument},
+    test::{DID_METHOD, DID_PREFIX},
 };
 
 #[test]
@@ -14,13 +15,14 @@ fn controller_exists_as_did() {
     let owner = "owner".into_addr();
     let controller_did = format!("{}{}", DID_PREFIX, "controller_did");
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the DID Document for the controller
     let controller_did_doc = DidDocument {
         id: Did::new(&controller_did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -42,11 +44,11 @@ fn controller_exists_as_address() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Check if the address-based controller exists
     let result = contract
-        .does_controller_exist(Controller::new(&owner.to_string()))
+        .does_controller_exist(Controller::new(owner.as_ref()))
         .unwrap();
     assert!(result, "Expected the address-based controller to exist, but got false");
 }
@@ -59,7 +61,7 @@ fn controller_does_not_exist() {
     let owner = "owner".into_addr();
     let non_existing_controller = format!("{}{}", DID_PREFIX, "non_existing_controller");
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Check if the non-existing controller exists
     let result = contract
@@ -76,7 +78,7 @@ fn invalid_controller_format() {
     let owner = "owner".into_addr();
     let invalid_controller = "invalid_controller_format";
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Attempt to check with an invalid controller format
     let result = contract.does_controller_exist(Controller::new(invalid_controller));
@@ -97,7 +99,7 @@ fn empty_controller() {
     let owner = "owner".into_addr();
     let empty_controller = "";
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Attempt to check with an empty controller
     let result = contract.does_controller_exist(Controller::new(empty_controller));
@@ -117,7 +119,7 @@ fn controller_exists_in_complex_did_hierarchy() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the first DID Document (controller1)
     let controller1 = format!("{}{}", DID_PREFIX, "controller1");
@@ -125,6 +127,7 @@ fn controller_exists_in_complex_did_hierarchy() {
         id: Did::new(&controller1),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -138,6 +141,7 @@ fn controller_exists_in_complex_did_hierarchy() {
         id: Did::new(&controller2),
         controller: vec![controller1.clone().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -0,0 +1,302 @@
+use cosmwasm_std::Binary;
+use ed25519_zebra::{SigningKey as Ed25519SigningKey, VerificationKey};
+use k256::ecdsa::{signature::Signer, Signature as Secp256k1Signature, SigningKey as Secp256k1SigningKey};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{
+        AuthorizationPolicy, ControllerWeight, Did, DidDocument, DidOperation, RelationshipMethod,
+        VerificationMethod,
+    },
+    test::{service, DID_METHOD, DID_PREFIX},
+};
+
+const ED25519_SECRET: [u8; 32] = [7u8; 32];
+const SECP256K1_SECRET: [u8; 32] = [9u8; 32];
+
+fn multikey(id: &str, controller: &str, multicodec: [u8; 2], public_key: &[u8]) -> VerificationMethod {
+    let mut bytes = multicodec.to_vec();
+    bytes.extend_from_slice(public_key);
+    VerificationMethod {
+        id: Did::new(id),
+        a_type: "Multikey".to_string(),
+        controller: Did::new(controller),
+        public_key_multibase: Some(format!("z{}", bs58::encode(bytes).into_string())),
+        public_key_jwk: None,
+        blockchain_account_id: None,
+    }
+}
+
+fn ed25519_method(id: &str, controller: &str) -> VerificationMethod {
+    let signing_key = Ed25519SigningKey::from(ED25519_SECRET);
+    let public_key: [u8; 32] = VerificationKey::from(&signing_key).into();
+    multikey(id, controller, [0xed, 0x01], &public_key)
+}
+
+fn secp256k1_method(id: &str, controller: &str) -> VerificationMethod {
+    let signing_key = Secp256k1SigningKey::from_slice(&SECP256K1_SECRET).unwrap();
+    let public_key = signing_key.verifying_key().to_encoded_point(true);
+    multikey(id, controller, [0xe7, 0x01], public_key.as_bytes())
+}
+
+fn ed25519_sign(message: &[u8]) -> Binary {
+    let signing_key = Ed25519SigningKey::from(ED25519_SECRET);
+    let signature: [u8; 64] = signing_key.sign(message).into();
+    Binary::from(signature.to_vec())
+}
+
+fn secp256k1_sign(message: &[u8]) -> Binary {
+    let signing_key = Secp256k1SigningKey::from_slice(&SECP256K1_SECRET).unwrap();
+    let signature: Secp256k1Signature = signing_key.sign(message);
+    Binary::from(signature.to_bytes().to_vec())
+}
+
+#[test]
+fn execute_signed_with_ed25519_key() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let relayer = "relayer".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "signed_did");
+    let method = ed25519_method(&format!("{}#key-1", did), &did);
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        verification_method: vec![method.clone()],
+        capability_invocation: vec![RelationshipMethod::Reference(method.id.clone())],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    assert_eq!(0, contract.get_nonce(Did::new(&did)).unwrap());
+
+    let operation = DidOperation::AddService {
+        did: Did::new(&did),
+        service: service("service", &did, "Service", "https://example.com"),
+    };
+    let sign_bytes = contract.get_sign_bytes(operation.clone(), 0).unwrap();
+    let signature = ed25519_sign(&sign_bytes);
+
+    let res = contract
+        .execute_signed(operation.clone(), method.id.clone(), 0, signature.clone())
+        .call(&relayer)
+        .expect("Failed to get result");
+    assert_eq!(res.events.len(), 3);
+    assert_eq!(res.events[1].ty, "wasm-add_service");
+    assert_eq!(res.events[2].ty, "wasm-execute_signed");
+    assert_eq!(res.events[2].attributes[1].key, "did");
+    assert_eq!(res.events[2].attributes[1].value, did);
+    assert_eq!(res.events[2].attributes[2].key, "verification_method");
+    assert_eq!(res.events[2].attributes[2].value, method.id.to_string());
+    assert_eq!(res.events[2].attributes[3].key, "nonce");
+    assert_eq!(res.events[2].attributes[3].value, "0");
+    assert_eq!(res.events[2].attributes[4].key, "relayer");
+    assert_eq!(res.events[2].attributes[4].value, relayer.to_string());
+
+    let did_document = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(vec![service("service", &did, "Service", "https://example.com")], did_document.service);
+    assert_eq!(1, contract.get_nonce(Did::new(&did)).unwrap());
+
+    // The same signature cannot be replayed
+    let result = contract
+        .execute_signed(operation, method.id.clone(), 0, signature)
+        .call(&relayer);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Invalid nonce for {}: expected 1, got 0", did),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn execute_signed_with_secp256k1_key() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let relayer = "relayer".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "secp256k1_did");
+    let method = secp256k1_method(&format!("{}#key-1", did), &did);
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        capability_invocation: vec![RelationshipMethod::Embedded(Box::new(method.clone()))],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let operation = DidOperation::AddService {
+        did: Did::new(&did),
+        service: service("service", &did, "Service", "https://example.com"),
+    };
+    let sign_bytes = contract.get_sign_bytes(operation.clone(), 0).unwrap();
+    let result = contract
+        .execute_signed(operation, method.id.clone(), 0, secp256k1_sign(&sign_bytes))
+        .call(&relayer);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let operation = DidOperation::DeleteService {
+        did: Did::new(&did),
+        service_did: service("service", &did, "Service", "https://example.com").id,
+    };
+    let sign_bytes = contract.get_sign_bytes(operation.clone(), 1).unwrap();
+    let result = contract
+        .execute_signed(operation, method.id.clone(), 1, secp256k1_sign(&sign_bytes))
+        .call(&relayer);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let did_document = contract.get_did_document(Did::new(&did)).unwrap();
+    assert!(did_document.service.is_empty());
+    assert_eq!(2, contract.get_nonce(Did::new(&did)).unwrap());
+}
+
+#[test]
+fn execute_signed_invalid_signature() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let relayer = "relayer".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "forged_did");
+    let method = ed25519_method(&format!("{}#key-1", did), &did);
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        verification_method: vec![method.clone()],
+        capability_invocation: vec![RelationshipMethod::Reference(method.id.clone())],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let operation = DidOperation::DeactivateDidDocument { did: Did::new(&did) };
+
+    // Signed for a different nonce
+    let sign_bytes = contract.get_sign_bytes(operation.clone(), 1).unwrap();
+    let result = contract
+        .execute_signed(operation.clone(), method.id.clone(), 0, ed25519_sign(&sign_bytes))
+        .call(&relayer);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Invalid signature for verification method: {}", method.id),
+        result.err().unwrap().to_string()
+    );
+
+    // Signed with a different key
+    let sign_bytes = contract.get_sign_bytes(operation.clone(), 0).unwrap();
+    let result = contract
+        .execute_signed(operation.clone(), method.id.clone(), 0, secp256k1_sign(&sign_bytes))
+        .call(&relayer);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+
+    let did_document = contract.get_did_document(Did::new(&did)).unwrap();
+    assert!(!did_document.deactivated);
+    assert_eq!(0, contract.get_nonce(Did::new(&did)).unwrap());
+}
+
+#[test]
+fn execute_signed_requires_capability_invocation() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let relayer = "relayer".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "auth_only_did");
+    let method = ed25519_method(&format!("{}#key-1", did), &did);
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        verification_method: vec![method.clone()],
+        authentication: vec![RelationshipMethod::Reference(method.id.clone())],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let operation = DidOperation::AddService {
+        did: Did::new(&did),
+        service: service("service", &did, "Service", "https://example.com"),
+    };
+    let sign_bytes = contract.get_sign_bytes(operation.clone(), 0).unwrap();
+    let result = contract
+        .execute_signed(operation, method.id.clone(), 0, ed25519_sign(&sign_bytes))
+        .call(&relayer);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!(
+            "Verification method not authorized for capability invocation: {}",
+            method.id
+        ),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn execute_signed_does_not_bypass_policy() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let relayer = "relayer".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "policy_signed_did");
+    let method = ed25519_method(&format!("{}#key-1", did), &did);
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        verification_method: vec![method.clone()],
+        capability_invocation: vec![RelationshipMethod::Reference(method.id.clone())],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let policy = AuthorizationPolicy {
+        threshold: 1,
+        weights: vec![ControllerWeight {
+            controller: owner.to_string().into(),
+            weight: 1,
+        }],
+    };
+    let result = contract
+        .set_authorization_policy(Did::new(&did), Some(policy))
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let operation = DidOperation::SetAuthorizationPolicy {
+        did: Did::new(&did),
+        policy: None,
+    };
+    let sign_bytes = contract.get_sign_bytes(operation.clone(), 0).unwrap();
+    let result = contract
+        .execute_signed(operation, method.id.clone(), 0, ed25519_sign(&sign_bytes))
+        .call(&relayer);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!(
+            "Verification method not authorized for capability invocation: {}",
+            method.id
+        ),
+        result.err().unwrap().to_string()
+    );
+    assert_eq!(0, contract.get_nonce(Did::new(&did)).unwrap());
+}
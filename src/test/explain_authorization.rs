@@ -0,0 +1,237 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{AuthorizationExplanation, AuthorizationPolicy, Controller, ControllerWeight, Did, DidDocument},
+    test::{DID_METHOD, DID_PREFIX},
+};
+
+#[test]
+fn explain_direct_controller() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "direct_did");
+    let did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let explanation = contract
+        .explain_authorization(Did::new(&did), owner.to_string().into())
+        .unwrap();
+    assert_eq!(
+        AuthorizationExplanation {
+            authorized: true,
+            path: vec![did.clone().into(), owner.to_string().into()],
+            frontier: vec![],
+            reason: None,
+        },
+        explanation
+    );
+}
+
+#[test]
+fn explain_transitive_controller() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let other_owner = "other_owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    // did_c -> did_b -> did_a -> owner, with an unrelated branch on did_c
+    let did_a = format!("{}{}", DID_PREFIX, "did_a");
+    let did_b = format!("{}{}", DID_PREFIX, "did_b");
+    let did_c = format!("{}{}", DID_PREFIX, "did_c");
+    let other_did = format!("{}{}", DID_PREFIX, "other_did");
+    for (did, controllers) in [
+        (&did_a, vec![owner.to_string()]),
+        (&other_did, vec![other_owner.to_string()]),
+        (&did_b, vec![did_a.clone()]),
+        (&did_c, vec![other_did.clone(), did_b.clone()]),
+    ] {
+        let did_doc = DidDocument {
+            id: Did::new(did),
+            controller: controllers.into_iter().map(Controller::from).collect(),
+            service: vec![],
+            ..Default::default()
+        };
+        let result = contract.create_did_document(did_doc).call(&owner);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+    }
+
+    let explanation = contract
+        .explain_authorization(Did::new(&did_c), owner.to_string().into())
+        .unwrap();
+    assert!(explanation.authorized);
+    assert_eq!(
+        vec![
+            Controller::from(did_c.clone()),
+            did_b.clone().into(),
+            did_a.clone().into(),
+            owner.to_string().into(),
+        ],
+        explanation.path
+    );
+    assert!(explanation.frontier.is_empty());
+
+    // A DID controller is itself a candidate
+    let explanation = contract
+        .explain_authorization(Did::new(&did_c), did_a.clone().into())
+        .unwrap();
+    assert!(explanation.authorized);
+    assert_eq!(
+        vec![
+            Controller::from(did_c.clone()),
+            did_b.clone().into(),
+            did_a.clone().into(),
+        ],
+        explanation.path
+    );
+}
+
+#[test]
+fn explain_unauthorized_controller() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let unauthorized_user = "unauthorized_user".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did_a = format!("{}{}", DID_PREFIX, "did_a");
+    let did_b = format!("{}{}", DID_PREFIX, "did_b");
+    for (did, controller) in [(&did_a, owner.to_string()), (&did_b, did_a.clone())] {
+        let did_doc = DidDocument {
+            id: Did::new(did),
+            controller: vec![controller.into()],
+            service: vec![],
+            ..Default::default()
+        };
+        let result = contract.create_did_document(did_doc).call(&owner);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+    }
+
+    let explanation = contract
+        .explain_authorization(Did::new(&did_b), unauthorized_user.to_string().into())
+        .unwrap();
+    assert_eq!(
+        AuthorizationExplanation {
+            authorized: false,
+            path: vec![],
+            frontier: vec![Did::new(&did_a)],
+            reason: Some(format!("Unauthorized: {}", unauthorized_user)),
+        },
+        explanation
+    );
+}
+
+#[test]
+fn explain_controller_below_policy_threshold() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner1 = "owner1".into_addr();
+    let owner2 = "owner2".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner1).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "policy_did");
+    let did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner1.to_string().into(), owner2.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(did_doc).call(&owner1);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let policy = AuthorizationPolicy {
+        threshold: 2,
+        weights: vec![
+            ControllerWeight {
+                controller: owner1.to_string().into(),
+                weight: 1,
+            },
+            ControllerWeight {
+                controller: owner2.to_string().into(),
+                weight: 1,
+            },
+        ],
+    };
+    let result = contract
+        .set_authorization_policy(Did::new(&did), Some(policy))
+        .call(&owner1);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // Controls the document, but cannot act alone
+    let explanation = contract
+        .explain_authorization(Did::new(&did), owner1.to_string().into())
+        .unwrap();
+    assert_eq!(
+        AuthorizationExplanation {
+            authorized: false,
+            path: vec![did.clone().into(), owner1.to_string().into()],
+            frontier: vec![],
+            reason: Some(format!("Authorization threshold not met for {}: 1 of 2", did)),
+        },
+        explanation
+    );
+}
+
+#[test]
+fn explain_deactivated_did_document() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "deactivated_did");
+    let did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    let result = contract.deactivate_did_document(Did::new(&did)).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let explanation = contract
+        .explain_authorization(Did::new(&did), owner.to_string().into())
+        .unwrap();
+    assert!(!explanation.authorized);
+    assert_eq!(
+        Some(format!("Did document deactivated: {}", did)),
+        explanation.reason
+    );
+}
+
+#[test]
+fn explain_non_existing_did_document() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "non_existing_did");
+    let result = contract.explain_authorization(Did::new(&did), owner.to_string().into());
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+}
@@ -3,7 +3,8 @@ use sylvia::multitest::App;
 
 use crate::{
     contract::sv::mt::{CodeId, DidContractProxy},
-    state::{Did, DidDocument, Service, DID_PREFIX},
+    state::{Did, DidDocument, Service},
+    test::{DID_METHOD, DID_PREFIX},
 };
 
 #[test]
@@ -13,7 +14,7 @@ fn retrieve_all_controlled_did_documents_for_single_controller() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create two DID Documents controlled by the same controller
     let did1 = format!("{}{}", DID_PREFIX, "did1");
@@ -23,12 +24,14 @@ fn retrieve_all_controlled_did_documents_for_single_controller() {
         id: Did::new(&did1),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let did_doc2 = DidDocument {
         id: Did::new(&did2),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Save the DID Documents
@@ -54,7 +57,7 @@ fn retrieve_controlled_did_documents_for_controller_with_no_dids() {
     let owner = "owner".into_addr();
     let controller = "controller_with_no_dids".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Retrieve controlled DID Documents for a controller with no DIDs
     let controlled_did_docs = contract
@@ -73,7 +76,7 @@ fn retrieve_controlled_did_documents_with_pagination() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create three DID Documents controlled by the same controller
     let did1 = format!("{}{}", DID_PREFIX, "did1");
@@ -84,18 +87,21 @@ fn retrieve_controlled_did_documents_with_pagination() {
         id: Did::new(&did1),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let did_doc2 = DidDocument {
         id: Did::new(&did2),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let did_doc3 = DidDocument {
         id: Did::new(&did3),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Save the DID Documents
@@ -132,7 +138,7 @@ fn retrieve_controlled_did_documents_for_non_existing_controller() {
     let owner = "owner".into_addr();
     let non_existing_controller = format!("{}{}", DID_PREFIX, "non_existing_controller");
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Retrieve controlled DID Documents for a non-existing controller
     let controlled_did_docs = contract
@@ -152,7 +158,7 @@ fn retrieve_controlled_did_documents_with_invalid_controller_format() {
     let owner = "owner".into_addr();
     let invalid_controller = "invalid_controller_format";
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Attempt to retrieve controlled DID Documents with an invalid controller format
     let result = contract.get_controlled_did_documents(invalid_controller.into(), None, None);
@@ -172,25 +178,26 @@ fn retrieve_controlled_did_documents_with_services() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create a DID Document with services
     let did = format!("{}{}", DID_PREFIX, "did_with_services");
     let service1 = Service {
-        id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-        a_type: "ServiceType1".to_string(),
-        service_endpoint: "https://service1.com".to_string(),
+        id: Did::new(&format!("{}#{}", did, "service1")),
+        a_type: "ServiceType1".into(),
+        service_endpoint: "https://service1.com".into(),
     };
     let service2 = Service {
-        id: Did::new(&format!("{}{}", DID_PREFIX, "service2")),
-        a_type: "ServiceType2".to_string(),
-        service_endpoint: "https://service2.com".to_string(),
+        id: Did::new(&format!("{}#{}", did, "service2")),
+        a_type: "ServiceType2".into(),
+        service_endpoint: "https://service2.com".into(),
     };
 
     let did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![service1.clone(), service2.clone()],
+        ..Default::default()
     };
 
     // Save the DID Document
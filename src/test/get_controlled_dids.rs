@@ -3,7 +3,8 @@ use sylvia::multitest::App;
 
 use crate::{
     contract::sv::mt::{CodeId, DidContractProxy},
-    state::{Did, DidDocument, Service, DID_PREFIX},
+    state::{Did, DidDocument, Service},
+    test::{DID_METHOD, DID_PREFIX},
 };
 
 #[test]
@@ -14,7 +15,7 @@ fn get_controlled_dids() {
     let owner = "owner".into_addr();
     let owner2 = "owner2".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // let did_owner = "did_owner";
     let did1 = format!("{}{}", DID_PREFIX, "new_did11111111111111111111111111");
@@ -23,10 +24,11 @@ fn get_controlled_dids() {
         // controller: Controllers(vec![owner.to_string().into()]),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            a_type: "".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "ffffff")),
-            service_endpoint: "dfdsfs".to_string(),
+            a_type: "Service".into(),
+            id: Did::new(&format!("{}#{}", did1, "ffffff")),
+            service_endpoint: "https://example.com".into(),
         }],
+        ..Default::default()
     };
     let result = contract
         .create_did_document(new_did_doc.clone())
@@ -39,10 +41,11 @@ fn get_controlled_dids() {
         // controller: Controllers(vec![owner.to_string().into()]),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            a_type: "".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "ffffff")),
-            service_endpoint: "dfdsfs".to_string(),
+            a_type: "Service".into(),
+            id: Did::new(&format!("{}#{}", did2, "ffffff")),
+            service_endpoint: "https://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -56,10 +59,11 @@ fn get_controlled_dids() {
         // controller: Controllers(vec![owner2.to_string().into()]),
         controller: vec![owner2.to_string().into()],
         service: vec![Service {
-            a_type: "".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "ffffff")),
-            service_endpoint: "dfdsfs".to_string(),
+            a_type: "Service".into(),
+            id: Did::new(&format!("{}#{}", did3, "ffffff")),
+            service_endpoint: "https://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -100,7 +104,7 @@ fn retrieve_controlled_dids_for_single_controller() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create two DID Documents controlled by the same controller
     let did1 = format!("{}{}", DID_PREFIX, "did1");
@@ -110,12 +114,14 @@ fn retrieve_controlled_dids_for_single_controller() {
         id: Did::new(&did1),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let did_doc2 = DidDocument {
         id: Did::new(&did2),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Save the DID Documents
@@ -144,7 +150,7 @@ fn retrieve_controlled_dids_for_controller_with_no_dids() {
     let owner = "owner".into_addr();
     let controller = "controller_with_no_dids".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Retrieve controlled DIDs for a controller with no DIDs
     let controlled_dids = contract
@@ -163,7 +169,7 @@ fn retrieve_controlled_dids_with_pagination() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create three DID Documents controlled by the same controller
     let did1 = format!("{}{}", DID_PREFIX, "did1");
@@ -174,18 +180,21 @@ fn retrieve_controlled_dids_with_pagination() {
         id: Did::new(&did1),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let did_doc2 = DidDocument {
         id: Did::new(&did2),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let did_doc3 = DidDocument {
         id: Did::new(&did3),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Save the DID Documents
@@ -222,7 +231,7 @@ fn retrieve_controlled_dids_with_invalid_controller_format() {
     let owner = "owner".into_addr();
     let invalid_controller = "invalid_controller_format";
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Attempt to retrieve controlled DIDs with an invalid controller format
     let result = contract.get_controlled_dids(invalid_controller.into(), None, None);
@@ -243,7 +252,7 @@ fn retrieve_controlled_dids_for_non_existing_controller() {
     let owner = "owner".into_addr();
     let non_existing_controller = format!("{}{}", DID_PREFIX, "non_existing_controller");
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Retrieve controlled DIDs for a non-existing controller
     let controlled_dids = contract
@@ -253,4 +262,47 @@ fn retrieve_controlled_dids_for_non_existing_controller() {
         controlled_dids.is_empty(),
         "Expected no controlled DIDs, but got some"
     );
-}
\ No newline at end of file
+}
+#[test]
+fn get_controlled_dids_with_overlapping_controllers() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    // Controller DIDs where one is a prefix of the other
+    let controller = format!("{}{}", DID_PREFIX, "ctrl");
+    let controller_ext = format!("{}{}", DID_PREFIX, "ctrlx");
+    for c in [&controller, &controller_ext] {
+        let did_doc = DidDocument {
+            id: Did::new(c),
+            controller: vec![owner.to_string().into()],
+            service: vec![],
+            ..Default::default()
+        };
+        let result = contract.create_did_document(did_doc).call(&owner);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+    }
+
+    let did = format!("{}{}", DID_PREFIX, "controlled");
+    let did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![controller.clone().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let dids = contract
+        .get_controlled_dids(controller.clone().into(), None, None)
+        .unwrap();
+    assert_eq!(vec![Did::new(&did)], dids);
+
+    let dids = contract
+        .get_controlled_dids(controller_ext.clone().into(), None, None)
+        .unwrap();
+    assert!(dids.is_empty());
+}
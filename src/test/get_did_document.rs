@@ -3,7 +3,8 @@ use sylvia::multitest::App;
 
 use crate::{
     contract::sv::mt::{CodeId, DidContractProxy},
-    state::{Did, DidDocument, Service, DID_PREFIX},
+    state::{Did, DidDocument, Service},
+    test::{DID_METHOD, DID_PREFIX},
 };
 
 #[test]
@@ -13,13 +14,13 @@ fn get_document_not_found() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = "did";
     let no_did = contract.get_did_document(Did::new(did));
     assert!(no_did.is_err(), "Expected Err, but got an Ok");
     assert_eq!(
-        "Generic error: Querier contract error: Did format error: did",
+        "Generic error: Querier contract error: Did format error: did (missing did scheme)",
         no_did.err().unwrap().to_string()
     );
 
@@ -39,13 +40,14 @@ fn get_existing_did_document() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "existing_did");
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the DID Document
@@ -69,7 +71,7 @@ fn get_non_existing_did_document() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let non_existing_did = format!("{}{}", DID_PREFIX, "non_existing_did");
 
@@ -91,7 +93,7 @@ fn get_did_document_with_invalid_format() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Define a DID with an invalid format
     let invalid_did = "invalid_did_format";
@@ -102,7 +104,7 @@ fn get_did_document_with_invalid_format() {
 
     // Verify the error message
     assert_eq!(
-        format!("Generic error: Querier contract error: Did format error: {}", invalid_did),
+        format!("Generic error: Querier contract error: Did format error: {} (missing did scheme)", invalid_did),
         result.err().unwrap().to_string()
     );
 }
@@ -115,13 +117,14 @@ fn get_did_document_with_multiple_controllers() {
     let owner = "owner".into_addr();
     let controller2 = "controller2".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "multi_controller_did");
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into(), controller2.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the DID Document
@@ -145,17 +148,18 @@ fn get_did_document_with_services() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "service_did");
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            a_type: "ServiceType".to_string(),
-            service_endpoint: "http://example.com".to_string(),
+            id: Did::new(&format!("{}#{}", did, "service1")),
+            a_type: "ServiceType".into(),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     // Create the DID Document
@@ -0,0 +1,219 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{BlockStamp, Did, DidDocument, DidDocumentMetadata, Service, VersionTime},
+    test::{DID_METHOD, DID_PREFIX},
+};
+
+#[test]
+fn get_did_history() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "history_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    let created = BlockStamp::from(&app.block_info());
+
+    app.update_block(|block| {
+        block.height += 1;
+        block.time = block.time.plus_seconds(5);
+    });
+
+    let service = Service {
+        id: Did::new(&format!("{}#service", did)),
+        a_type: "Service".into(),
+        service_endpoint: "https://example.com".into(),
+    };
+    let result = contract
+        .add_service(Did::new(&did), service.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    let updated = BlockStamp::from(&app.block_info());
+
+    let history = contract.get_did_history(Did::new(&did), None, None).unwrap();
+    assert_eq!(2, history.len());
+
+    assert_eq!(new_did_doc, history[0].did_document);
+    assert_eq!(
+        DidDocumentMetadata {
+            created: Some(created.clone()),
+            version_id: Some(1),
+            ..Default::default()
+        },
+        history[0].did_document_metadata
+    );
+
+    assert_eq!(
+        DidDocument {
+            service: vec![service],
+            ..new_did_doc
+        },
+        history[1].did_document
+    );
+    assert_eq!(
+        DidDocumentMetadata {
+            created: Some(created),
+            updated: Some(updated),
+            version_id: Some(2),
+            ..Default::default()
+        },
+        history[1].did_document_metadata
+    );
+}
+
+#[test]
+fn get_did_history_paginated() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "paginated_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    for i in 0..4 {
+        let controller = format!("controller{}", i).into_addr();
+        let result = contract
+            .add_controller(Did::new(&did), controller.to_string().into())
+            .call(&owner);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+    }
+
+    let page = contract
+        .get_did_history(Did::new(&did), Some(2), None)
+        .unwrap();
+    let versions: Vec<_> = page
+        .iter()
+        .map(|r| r.did_document_metadata.version_id.unwrap())
+        .collect();
+    assert_eq!(vec![1, 2], versions);
+
+    let page = contract
+        .get_did_history(Did::new(&did), Some(2), Some(2))
+        .unwrap();
+    let versions: Vec<_> = page
+        .iter()
+        .map(|r| r.did_document_metadata.version_id.unwrap())
+        .collect();
+    assert_eq!(vec![3, 4], versions);
+
+    let page = contract
+        .get_did_history(Did::new(&did), Some(2), Some(4))
+        .unwrap();
+    assert_eq!(1, page.len());
+    assert_eq!(5, page[0].did_document.controller.len());
+}
+
+#[test]
+fn get_did_history_kept_on_delete() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "deleted_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    let created = BlockStamp::from(&app.block_info());
+
+    app.update_block(|block| {
+        block.height += 1;
+        block.time = block.time.plus_seconds(5);
+    });
+
+    let result = contract.delete_did_document(Did::new(&did), None).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    let deleted = BlockStamp::from(&app.block_info());
+
+    // The deletion is recorded as a final tombstone revision
+    let history = contract.get_did_history(Did::new(&did), None, None).unwrap();
+    assert_eq!(2, history.len());
+    assert_eq!(new_did_doc, history[0].did_document);
+    let tombstone = DidDocument {
+        id: Did::new(&did),
+        deactivated: true,
+        ..Default::default()
+    };
+    let tombstone_metadata = DidDocumentMetadata {
+        created: Some(created.clone()),
+        updated: Some(deleted.clone()),
+        version_id: Some(2),
+        deactivated: true,
+        deleted: true,
+        ..Default::default()
+    };
+    assert_eq!(tombstone, history[1].did_document);
+    assert_eq!(tombstone_metadata, history[1].did_document_metadata);
+
+    let result = contract.resolve_did_version(Did::new(&did), 1).unwrap();
+    assert_eq!(Some(new_did_doc.clone()), result.did_document);
+    assert_eq!(Some(deleted), result.did_document_metadata.next_update);
+
+    let result = contract
+        .resolve_did_at(Did::new(&did), VersionTime::Height(created.height))
+        .unwrap();
+    assert_eq!(Some(new_did_doc.clone()), result.did_document);
+    let result = contract.resolve_did_version(Did::new(&did), 2).unwrap();
+    assert_eq!(Some(tombstone), result.did_document);
+    assert_eq!(tombstone_metadata, result.did_document_metadata);
+
+    // Creating the DID again continues the history
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    let history = contract.get_did_history(Did::new(&did), None, None).unwrap();
+    assert_eq!(3, history.len());
+    assert_eq!(Some(3), history[2].did_document_metadata.version_id);
+}
+
+#[test]
+fn get_did_history_invalid_did() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let result = contract.get_did_history(Did::new("invalid_did_format"), None, None);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        "Generic error: Querier contract error: Did format error: invalid_did_format (missing did scheme)",
+        result.err().unwrap().to_string()
+    );
+}
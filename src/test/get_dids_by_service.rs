@@ -0,0 +1,212 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{Did, DidDocument, Service, ServiceEndpoint, ServiceType},
+    test::{DID_METHOD, DID_PREFIX},
+};
+
+fn service(did: &str, fragment: &str, a_type: ServiceType, service_endpoint: ServiceEndpoint) -> Service {
+    Service {
+        id: Did::new(&format!("{}#{}", did, fragment)),
+        a_type,
+        service_endpoint,
+    }
+}
+
+#[test]
+fn get_dids_by_service_type() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let station1 = format!("{}{}", DID_PREFIX, "station1");
+    let station2 = format!("{}{}", DID_PREFIX, "station2");
+    let meter = format!("{}{}", DID_PREFIX, "meter");
+    for (did, a_type) in [
+        (&station1, ServiceType::Single("ChargingStation".to_string())),
+        (
+            &station2,
+            ServiceType::Set(vec!["ChargingStation".to_string(), "Meter".to_string()]),
+        ),
+        (&meter, "Meter".into()),
+    ] {
+        let did_doc = DidDocument {
+            id: Did::new(did),
+            controller: vec![owner.to_string().into()],
+            service: vec![
+                service(did, "s1", a_type.clone(), "https://example.com".into()),
+                service(did, "s2", a_type, "https://example.org".into()),
+            ],
+            ..Default::default()
+        };
+        let result = contract.create_did_document(did_doc).call(&owner);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+    }
+
+    let dids = contract
+        .get_dids_by_service_type("ChargingStation".to_string(), None, None)
+        .unwrap();
+    assert_eq!(vec![Did::new(&station1), Did::new(&station2)], dids);
+
+    let dids = contract
+        .get_dids_by_service_type("Meter".to_string(), Some(1), None)
+        .unwrap();
+    assert_eq!(vec![Did::new(&meter)], dids);
+    let dids = contract
+        .get_dids_by_service_type("Meter".to_string(), Some(1), Some(meter.clone()))
+        .unwrap();
+    assert_eq!(vec![Did::new(&station2)], dids);
+
+    let dids = contract
+        .get_dids_by_service_type("Unknown".to_string(), None, None)
+        .unwrap();
+    assert!(dids.is_empty());
+}
+
+#[test]
+fn service_type_index_follows_changes() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "indexed_did");
+    let did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(did_doc.clone()).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let by_type = |a_type: &str| {
+        contract
+            .get_dids_by_service_type(a_type.to_string(), None, None)
+            .unwrap()
+    };
+
+    let result = contract
+        .add_service(
+            Did::new(&did),
+            service(&did, "s1", "ChargingStation".into(), "https://example.com".into()),
+        )
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    assert_eq!(vec![Did::new(&did)], by_type("ChargingStation"));
+
+    let result = contract
+        .update_service(
+            Did::new(&did),
+            Did::new(&format!("{}#s1", did)),
+            Some("Meter".into()),
+            None,
+        )
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    assert!(by_type("ChargingStation").is_empty());
+    assert_eq!(vec![Did::new(&did)], by_type("Meter"));
+
+    let result = contract
+        .delete_service(Did::new(&did), Did::new(&format!("{}#s1", did)))
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    assert!(by_type("Meter").is_empty());
+
+    let mut new_did_doc = did_doc.clone();
+    new_did_doc.service = vec![service(&did, "s2", "Hub".into(), "https://example.com".into())];
+    let result = contract.update_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    assert_eq!(vec![Did::new(&did)], by_type("Hub"));
+
+    let result = contract.delete_did_document(Did::new(&did), None).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    assert!(by_type("Hub").is_empty());
+}
+
+#[test]
+fn deactivated_did_not_found_by_service_type() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "deactivated_did");
+    let did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![service(&did, "s1", "Hub".into(), "https://hub.example.com".into())],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract.deactivate_did_document(Did::new(&did)).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let dids = contract
+        .get_dids_by_service_type("Hub".to_string(), None, None)
+        .unwrap();
+    assert!(dids.is_empty());
+    let dids = contract
+        .get_dids_by_service_endpoint("hub.example.com".to_string(), None, None)
+        .unwrap();
+    assert!(dids.is_empty());
+}
+
+#[test]
+fn get_dids_by_service_endpoint() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did1 = format!("{}{}", DID_PREFIX, "endpoint_did1");
+    let did2 = format!("{}{}", DID_PREFIX, "endpoint_did2");
+    for (did, service_endpoint) in [
+        (&did1, "https://Hub.Example.com:8443/inbox".into()),
+        (
+            &did2,
+            ServiceEndpoint::Set(vec![
+                "wss://hub.example.com/ws".into(),
+                "https://other.example.org".into(),
+            ]),
+        ),
+    ] {
+        let did_doc = DidDocument {
+            id: Did::new(did),
+            controller: vec![owner.to_string().into()],
+            service: vec![service(did, "hub", "Hub".into(), service_endpoint)],
+            ..Default::default()
+        };
+        let result = contract.create_did_document(did_doc).call(&owner);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+    }
+
+    let dids = contract
+        .get_dids_by_service_endpoint("hub.example.com".to_string(), None, None)
+        .unwrap();
+    assert_eq!(vec![Did::new(&did1), Did::new(&did2)], dids);
+
+    // Hosts are matched case-insensitively, also when given as a URI
+    let dids = contract
+        .get_dids_by_service_endpoint("https://OTHER.example.org/x".to_string(), None, None)
+        .unwrap();
+    assert_eq!(vec![Did::new(&did2)], dids);
+
+    let dids = contract
+        .get_dids_by_service_endpoint("hub.example.com".to_string(), Some(1), Some(did1.clone()))
+        .unwrap();
+    assert_eq!(vec![Did::new(&did2)], dids);
+}
@@ -0,0 +1,302 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{ConfigUpdate, ControlledDid, Did, DidDocument, TraversalLimits},
+    test::{DID_METHOD, DID_PREFIX},
+};
+
+fn controlled(did: &str, depth: u32) -> ControlledDid {
+    ControlledDid {
+        did: Did::new(did),
+        depth,
+    }
+}
+
+#[test]
+fn get_transitively_controlled_dids() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    // owner -> site -> {device1, device2}, device2 -> sensor
+    let site = format!("{}{}", DID_PREFIX, "site");
+    let device1 = format!("{}{}", DID_PREFIX, "device1");
+    let device2 = format!("{}{}", DID_PREFIX, "device2");
+    let sensor = format!("{}{}", DID_PREFIX, "sensor");
+    for (did, controller) in [
+        (&site, owner.to_string()),
+        (&device2, site.clone()),
+        (&device1, site.clone()),
+        (&sensor, device2.clone()),
+    ] {
+        let did_doc = DidDocument {
+            id: Did::new(did),
+            controller: vec![controller.into()],
+            service: vec![],
+            ..Default::default()
+        };
+        let result = contract.create_did_document(did_doc).call(&owner);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+    }
+
+    let dids = contract
+        .get_transitively_controlled_dids(owner.to_string().into(), None, None, None)
+        .unwrap()
+        .dids;
+    assert_eq!(
+        vec![
+            controlled(&site, 1),
+            controlled(&device1, 2),
+            controlled(&device2, 2),
+            controlled(&sensor, 3),
+        ],
+        dids
+    );
+
+    let dids = contract
+        .get_transitively_controlled_dids(site.clone().into(), None, None, None)
+        .unwrap()
+        .dids;
+    assert_eq!(
+        vec![
+            controlled(&device1, 1),
+            controlled(&device2, 1),
+            controlled(&sensor, 2),
+        ],
+        dids
+    );
+
+    let dids = contract
+        .get_transitively_controlled_dids(owner.to_string().into(), Some(2), None, None)
+        .unwrap()
+        .dids;
+    assert_eq!(
+        vec![
+            controlled(&site, 1),
+            controlled(&device1, 2),
+            controlled(&device2, 2),
+        ],
+        dids
+    );
+
+    let page = contract
+        .get_transitively_controlled_dids(sensor.clone().into(), None, None, None)
+        .unwrap();
+    assert!(page.dids.is_empty());
+    assert_eq!(None, page.next);
+}
+
+#[test]
+fn get_transitively_controlled_dids_with_pagination() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    // A chain owner -> did1 -> did2 -> did3 -> did4
+    let dids: Vec<String> = (1..=4)
+        .map(|i| format!("{}did{}", DID_PREFIX, i))
+        .collect();
+    let mut controller = owner.to_string();
+    for did in &dids {
+        let did_doc = DidDocument {
+            id: Did::new(did),
+            controller: vec![controller.into()],
+            service: vec![],
+            ..Default::default()
+        };
+        let result = contract.create_did_document(did_doc).call(&owner);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+        controller = did.clone();
+    }
+
+    let page = contract
+        .get_transitively_controlled_dids(owner.to_string().into(), None, Some(2), None)
+        .unwrap();
+    assert_eq!(vec![controlled(&dids[0], 1), controlled(&dids[1], 2)], page.dids);
+    assert!(page.next.is_some(), "Expected a next cursor");
+
+    let page = contract
+        .get_transitively_controlled_dids(owner.to_string().into(), None, Some(2), page.next)
+        .unwrap();
+    assert_eq!(vec![controlled(&dids[2], 3), controlled(&dids[3], 4)], page.dids);
+    assert!(page.next.is_some(), "Expected a next cursor");
+
+    let page = contract
+        .get_transitively_controlled_dids(owner.to_string().into(), None, Some(2), page.next)
+        .unwrap();
+    assert!(page.dids.is_empty());
+    assert_eq!(None, page.next);
+
+    let result = contract.get_transitively_controlled_dids(
+        owner.to_string().into(),
+        None,
+        Some(2),
+        Some(dids[1].clone()),
+    );
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!(
+            "Generic error: Querier contract error: Invalid cursor: {}",
+            dids[1]
+        ),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn get_transitively_controlled_dids_wider_than_visited_nodes() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let update = ConfigUpdate {
+        traversal_limits: Some(TraversalLimits {
+            max_visited_nodes: 3,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, Some(update))
+        .call(&owner)
+        .unwrap();
+
+    // Five documents directly under the owner, and one more under the first
+    let dids: Vec<String> = (1..=5)
+        .map(|i| format!("{}wide{}", DID_PREFIX, i))
+        .collect();
+    let deep = format!("{}{}", DID_PREFIX, "deep");
+    let mut docs: Vec<(&String, String)> = dids.iter().map(|did| (did, owner.to_string())).collect();
+    docs.push((&deep, dids[0].clone()));
+    for (did, controller) in docs {
+        let did_doc = DidDocument {
+            id: Did::new(did),
+            controller: vec![controller.into()],
+            service: vec![],
+            ..Default::default()
+        };
+        let result = contract.create_did_document(did_doc).call(&owner);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+    }
+
+    // Each page looks into three documents at most and goes on from where
+    // the previous one stopped
+    let page = contract
+        .get_transitively_controlled_dids(owner.to_string().into(), None, None, None)
+        .unwrap();
+    let mut expected: Vec<ControlledDid> = dids.iter().map(|did| controlled(did, 1)).collect();
+    expected.push(controlled(&deep, 2));
+    assert_eq!(expected, page.dids);
+    assert!(page.next.is_some(), "Expected a next cursor");
+
+    let page = contract
+        .get_transitively_controlled_dids(owner.to_string().into(), None, None, page.next)
+        .unwrap();
+    assert!(page.dids.is_empty());
+    assert!(page.next.is_some(), "Expected a next cursor");
+
+    let page = contract
+        .get_transitively_controlled_dids(owner.to_string().into(), None, None, page.next)
+        .unwrap();
+    assert!(page.dids.is_empty());
+    assert_eq!(None, page.next);
+
+    // Pages smaller than the graph return every DID once
+    let mut found = Vec::new();
+    let mut next = None;
+    loop {
+        let page = contract
+            .get_transitively_controlled_dids(owner.to_string().into(), None, Some(2), next)
+            .unwrap();
+        found.extend(page.dids);
+        next = page.next;
+        if next.is_none() {
+            break;
+        }
+    }
+    assert_eq!(expected, found);
+
+    // Not following the DIDs found ends the walk with the first page
+    let page = contract
+        .get_transitively_controlled_dids(owner.to_string().into(), Some(1), None, None)
+        .unwrap();
+    assert_eq!(expected[..5], page.dids);
+    assert_eq!(None, page.next);
+}
+
+#[test]
+fn get_transitively_controlled_dids_with_cycle() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did_a = format!("{}{}", DID_PREFIX, "cycle_a");
+    let did_b = format!("{}{}", DID_PREFIX, "cycle_b");
+    let did_doc_a = DidDocument {
+        id: Did::new(&did_a),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(did_doc_a.clone()).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    let did_doc_b = DidDocument {
+        id: Did::new(&did_b),
+        controller: vec![did_a.clone().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(did_doc_b).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    let result = contract
+        .add_controller(Did::new(&did_a), did_b.clone().into())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // Every DID is reported once, at the depth it was first reached
+    let dids = contract
+        .get_transitively_controlled_dids(owner.to_string().into(), None, None, None)
+        .unwrap()
+        .dids;
+    assert_eq!(vec![controlled(&did_a, 1), controlled(&did_b, 2)], dids);
+
+    let dids = contract
+        .get_transitively_controlled_dids(did_a.clone().into(), None, None, None)
+        .unwrap()
+        .dids;
+    assert_eq!(vec![controlled(&did_b, 1)], dids);
+}
+
+#[test]
+fn get_transitively_controlled_dids_with_invalid_controller_format() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let invalid_controller = "invalid_controller_format";
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let result =
+        contract.get_transitively_controlled_dids(invalid_controller.into(), None, None, None);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!(
+            "Generic error: Querier contract error: Controller format error: {}",
+            invalid_controller
+        ),
+        result.err().unwrap().to_string()
+    );
+}
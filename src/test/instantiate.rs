@@ -0,0 +1,106 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{Did, DidDocument},
+    test::DID_PREFIX,
+};
+
+#[test]
+fn instantiate_with_custom_did_method() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id
+        .instantiate("c4etest".to_string(), None, None)
+        .call(&owner)
+        .unwrap();
+
+    // Document with the configured prefix is accepted
+    let did = "did:c4etest:custom_method_did";
+    let did_doc = DidDocument {
+        id: Did::new(did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(did_doc.clone()).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let created_did_doc = contract.get_did_document(Did::new(did)).unwrap();
+    assert_eq!(did_doc, created_did_doc);
+
+    // Document with the default c4e prefix is rejected
+    let other_did = format!("{}{}", DID_PREFIX, "custom_method_did");
+    let other_did_doc = DidDocument {
+        id: Did::new(&other_did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(other_did_doc).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Did format error: {} (unsupported did method 'c4e')", other_did),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn instantiate_with_custom_did_method_and_did_controller() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id
+        .instantiate("c4etest".to_string(), None, None)
+        .call(&owner)
+        .unwrap();
+
+    let controller_did = "did:c4etest:controller_did";
+    let controller_did_doc = DidDocument {
+        id: Did::new(controller_did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(controller_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // A DID of the configured method is recognized as a DID controller
+    let did = "did:c4etest:controlled_did";
+    let did_doc = DidDocument {
+        id: Did::new(did),
+        controller: vec![controller_did.into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .is_did_controller(Did::new(did), owner.to_string().into())
+        .unwrap();
+    assert!(result, "Expected owner to control the document through the DID controller");
+}
+
+#[test]
+fn instantiate_with_invalid_did_method() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    for did_method in ["", "C4E", "c4e:test", "c4e test"] {
+        let result = code_id.instantiate(did_method.to_string(), None, None).call(&owner);
+        assert!(result.is_err(), "Expected Err, but got an Ok");
+        assert_eq!(
+            format!("Did method format error: {}", did_method),
+            result.err().unwrap().to_string()
+        );
+    }
+}
@@ -3,7 +3,8 @@ use sylvia::multitest::App;
 
 use crate::{
     contract::sv::mt::{CodeId, DidContractProxy},
-    state::{Controller, Did, DidDocument, DID_PREFIX},
+    state::{Controller, Did, DidDocument},
+    test::{DID_METHOD, DID_PREFIX},
 };
 
 #[test]
@@ -14,13 +15,13 @@ fn is_valid_controller_in_list() {
     let owner = "owner".into_addr();
     let controller = "controller".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
-    let controllers = vec![Controller::new(&controller.to_string())];
+    let controllers = vec![Controller::new(controller.as_ref())];
 
     // Check if the controller is in the list of controllers
     let is_controller = contract
-        .is_controller_of(controllers.clone(), Controller::new(&controller.to_string()))
+        .is_controller_of(controllers.clone(), Controller::new(controller.as_ref()))
         .unwrap();
     assert!(
         is_controller,
@@ -36,13 +37,13 @@ fn is_non_controller_in_list() {
     let owner = "owner".into_addr();
     let non_controller = "non_controller".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
-    let controllers = vec![Controller::new(&owner.to_string())];
+    let controllers = vec![Controller::new(owner.as_ref())];
 
     // Check if the non-controller is in the list of controllers
     let is_controller = contract
-        .is_controller_of(controllers.clone(), Controller::new(&non_controller.to_string()))
+        .is_controller_of(controllers.clone(), Controller::new(non_controller.as_ref()))
         .unwrap();
     assert!(
         !is_controller,
@@ -57,7 +58,7 @@ fn is_did_in_list_of_controllers() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create a DID-based controller
     let did_controller = format!("{}{}", DID_PREFIX, "did_controller");
@@ -80,11 +81,11 @@ fn is_non_existing_did_not_in_list_of_controllers() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Define a non-existing DID
     let non_existing_did = format!("{}{}", DID_PREFIX, "non_existing_did");
-    let controllers = vec![Controller::new(&owner.to_string())];
+    let controllers = vec![Controller::new(owner.as_ref())];
 
     // Check if the non-existing DID is in the list of controllers
     let is_controller = contract
@@ -103,11 +104,11 @@ fn is_controller_with_invalid_format() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Define a controller with an invalid format
     let invalid_controller = "invalid_controller_format";
-    let controllers = vec![Controller::new(&owner.to_string())];
+    let controllers = vec![Controller::new(owner.as_ref())];
 
     // Attempt to check if the invalid controller is in the list of controllers
     let result = contract.is_controller_of(controllers.clone(), Controller::new(invalid_controller));
@@ -128,14 +129,14 @@ fn is_controller_in_vec_with_invalid_format() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Define a controller with an invalid format
     let invalid_controller = "invalid_controller_format";
-    let controllers = vec![Controller::new(&invalid_controller)];
+    let controllers = vec![Controller::new(invalid_controller)];
 
     // Attempt to check if the invalid controller is in the list of controllers
-    let result = contract.is_controller_of(controllers.clone(), Controller::new(&owner.to_string()));
+    let result = contract.is_controller_of(controllers.clone(), Controller::new(owner.as_ref()));
     assert!(result.is_err(), "Expected Err, but got an Ok");
 
     // Verify the error message
@@ -152,14 +153,14 @@ fn is_controller_in_empty_list() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Define an empty list of controllers
     let controllers: Vec<Controller> = vec![];
 
     // Check if any controller is in the empty list
     let is_controller = contract
-        .is_controller_of(controllers.clone(), Controller::new(&owner.to_string()))
+        .is_controller_of(controllers.clone(), Controller::new(owner.as_ref()))
         .unwrap();
     assert!(
         !is_controller,
@@ -174,7 +175,7 @@ fn is_controller_of_with_looped_controllers() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the first DID Document (loop start)
     let did1 = format!("{}{}", DID_PREFIX, "did1");
@@ -182,6 +183,7 @@ fn is_controller_of_with_looped_controllers() {
         id: Did::new(&did1),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract.create_did_document(did_doc1.clone()).call(&owner);
@@ -193,6 +195,7 @@ fn is_controller_of_with_looped_controllers() {
         id: Did::new(&did2),
         controller: vec![did1.clone().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract.create_did_document(did_doc2.clone()).call(&owner);
@@ -204,6 +207,7 @@ fn is_controller_of_with_looped_controllers() {
         id: Did::new(&did3),
         controller: vec![did2.clone().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract.create_did_document(did_doc3.clone()).call(&owner);
@@ -246,7 +250,7 @@ fn is_controller_of_with_looped_controllers() {
     );
 
     let is_controller = contract
-        .is_controller_of(did_doc1.controller.clone(), Controller::new(&owner.to_string()))
+        .is_controller_of(did_doc1.controller.clone(), Controller::new(owner.as_ref()))
         .unwrap();
     assert!(
         is_controller,
@@ -278,7 +282,7 @@ fn is_controller_of_with_looped_controllers() {
     );
 
     let is_controller = contract
-        .is_controller_of(did_doc2.controller.clone(), Controller::new(&owner.to_string()))
+        .is_controller_of(did_doc2.controller.clone(), Controller::new(owner.as_ref()))
         .unwrap();
     assert!(
         is_controller,
@@ -310,7 +314,7 @@ fn is_controller_of_with_looped_controllers() {
     );
 
     let is_controller = contract
-        .is_controller_of(did_doc3.controller.clone(), Controller::new(&owner.to_string()))
+        .is_controller_of(did_doc3.controller.clone(), Controller::new(owner.as_ref()))
         .unwrap();
     assert!(
         is_controller,
@@ -4,7 +4,8 @@ use sylvia::multitest::App;
 
 use crate::{
     contract::sv::mt::{CodeId, DidContractProxy},
-    state::{Controller, Did, DidDocument, Service, DID_PREFIX},
+    state::{Controller, Did, DidDocument, Service},
+    test::{DID_METHOD, DID_PREFIX},
 };
 
 #[test]
@@ -18,9 +19,9 @@ fn is_did_controller() {
     let owner = "owner".into_addr();
     let unknow_addr = "unknown".into_addr();
     let unknow_did = &format!("{}{}", DID_PREFIX, "unknown");
-    let service_did = &format!("{}{}", DID_PREFIX, "dfdsfs");
+    let service_did = "#dfdsfs";
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // let did_owner = "did_owner";
     let did_simple = format!("{}{}", DID_PREFIX, "did_simple");
@@ -29,10 +30,11 @@ fn is_did_controller() {
         // controller: Controllers(vec![owner.to_string().into()]),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            a_type: "".to_string(),
+            a_type: "Service".into(),
             id: Did::new(service_did),
-            service_endpoint: "dfdsfs".to_string(),
+            service_endpoint: "https://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -59,10 +61,11 @@ fn is_did_controller() {
         // controller: Controllers(vec![did_controlled_by_itself.to_string().into()]),
         controller: vec![did_controlled_by_itself.to_string().into()],
         service: vec![Service {
-            a_type: "".to_string(),
+            a_type: "Service".into(),
             id: Did::new(service_did),
-            service_endpoint: "dfdsfs".to_string(),
+            service_endpoint: "https://example.com".into(),
         }],
+        ..Default::default()
     };
 
     {
@@ -113,10 +116,11 @@ fn is_did_controller() {
         // controller: Controllers(vec![did_looped_2.to_string().into()]),
         controller: vec![did_looped_2.to_string().into()],
         service: vec![Service {
-            a_type: "".to_string(),
+            a_type: "Service".into(),
             id: Did::new(service_did),
-            service_endpoint: "dfdsfs".to_string(),
+            service_endpoint: "https://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let did_doc_looped_2 = DidDocument {
@@ -124,10 +128,11 @@ fn is_did_controller() {
         // controller: Controllers(vec![did_looped_1.to_string().into()]),
         controller: vec![did_looped_1.to_string().into()],
         service: vec![Service {
-            a_type: "".to_string(),
+            a_type: "Service".into(),
             id: Did::new(service_did),
-            service_endpoint: "dfdsfs".to_string(),
+            service_endpoint: "https://example.com".into(),
         }],
+        ..Default::default()
     };
 
     {
@@ -184,10 +189,11 @@ fn is_did_controller() {
         // controller: Controllers(vec![did_simple.to_string().into()]),
         controller: vec![did_simple.to_string().into()],
         service: vec![Service {
-            a_type: "".to_string(),
+            a_type: "Service".into(),
             id: Did::new(service_did),
-            service_endpoint: "dfdsfs".to_string(),
+            service_endpoint: "https://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -221,13 +227,14 @@ fn is_valid_controller_of_did() {
     let owner = "owner".into_addr();
     let controller = "controller".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "valid_did");
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![controller.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the DID Document
@@ -238,7 +245,7 @@ fn is_valid_controller_of_did() {
 
     // Check if the controller is a controller of the DID
     let is_controller = contract
-        .is_did_controller(Did::new(&did), Controller::new(&controller.to_string()))
+        .is_did_controller(Did::new(&did), Controller::new(controller.as_ref()))
         .unwrap();
     assert!(
         is_controller,
@@ -254,13 +261,14 @@ fn is_non_controller_of_did() {
     let owner = "owner".into_addr();
     let non_controller = "non_controller".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "valid_did");
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the DID Document
@@ -271,7 +279,7 @@ fn is_non_controller_of_did() {
 
     // Check if the non-controller is a controller of the DID
     let is_controller = contract
-        .is_did_controller(Did::new(&did), Controller::new(&non_controller.to_string()))
+        .is_did_controller(Did::new(&did), Controller::new(non_controller.as_ref()))
         .unwrap();
     assert!(
         !is_controller,
@@ -286,7 +294,7 @@ fn is_did_controller_of_another_did() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the controller DID Document
     let controller_did = format!("{}{}", DID_PREFIX, "controller_did");
@@ -294,6 +302,7 @@ fn is_did_controller_of_another_did() {
         id: Did::new(&controller_did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -307,6 +316,7 @@ fn is_did_controller_of_another_did() {
         id: Did::new(&did),
         controller: vec![controller_did.clone().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -332,13 +342,14 @@ fn is_non_existing_did_not_a_controller() {
     let owner = "owner".into_addr();
     let non_existing_did = format!("{}{}", DID_PREFIX, "non_existing_did");
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "valid_did");
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the DID Document
@@ -365,7 +376,7 @@ fn is_did_controller_with_invalid_did_format() {
     let owner = "owner".into_addr();
     let controller = "controller".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Define a DID with an invalid format
     let invalid_did = "invalid_did_format";
@@ -373,13 +384,13 @@ fn is_did_controller_with_invalid_did_format() {
     // Attempt to check if the controller is a controller of the invalid DID
     let result = contract.is_did_controller(
         Did::new(invalid_did),
-        Controller::new(&controller.to_string()),
+        Controller::new(controller.as_ref()),
     );
     assert!(result.is_err(), "Expected Err, but got an Ok");
 
     // Verify the error message
     assert_eq!(
-        "Generic error: Querier contract error: Did format error: invalid_did_format",
+        "Generic error: Querier contract error: Did format error: invalid_did_format (missing did scheme)",
         result.err().unwrap().to_string()
     );
 }
@@ -391,13 +402,14 @@ fn is_did_controller_with_invalid_controller_format() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "valid_did");
     let new_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the DID Document
@@ -427,7 +439,7 @@ fn is_did_controller_with_looped_controllers() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the first DID Document (loop start)
     let did1 = format!("{}{}", DID_PREFIX, "did1");
@@ -435,6 +447,7 @@ fn is_did_controller_with_looped_controllers() {
         id: Did::new(&did1),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract.create_did_document(did_doc1.clone()).call(&owner);
@@ -446,6 +459,7 @@ fn is_did_controller_with_looped_controllers() {
         id: Did::new(&did2),
         controller: vec![did1.clone().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract.create_did_document(did_doc2.clone()).call(&owner);
@@ -457,6 +471,7 @@ fn is_did_controller_with_looped_controllers() {
         id: Did::new(&did3),
         controller: vec![did2.clone().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract.create_did_document(did_doc3.clone()).call(&owner);
@@ -495,7 +510,7 @@ fn is_did_controller_with_looped_controllers() {
     );
 
     let is_controller = contract
-        .is_did_controller(Did::new(&did1), Controller::new(&owner.to_string()))
+        .is_did_controller(Did::new(&did1), Controller::new(owner.as_ref()))
         .unwrap();
     assert!(
         is_controller,
@@ -527,7 +542,7 @@ fn is_did_controller_with_looped_controllers() {
     );
 
     let is_controller = contract
-        .is_did_controller(Did::new(&did2), Controller::new(&owner.to_string()))
+        .is_did_controller(Did::new(&did2), Controller::new(owner.as_ref()))
         .unwrap();
     assert!(
         is_controller,
@@ -559,7 +574,7 @@ fn is_did_controller_with_looped_controllers() {
     );
 
     let is_controller = contract
-        .is_did_controller(Did::new(&did3), Controller::new(&owner.to_string()))
+        .is_did_controller(Did::new(&did3), Controller::new(owner.as_ref()))
         .unwrap();
     assert!(
         is_controller,
@@ -0,0 +1,79 @@
+use cosmwasm_std::Order;
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{Did, DidDocument},
+    test::{DID_METHOD, DID_PREFIX},
+};
+
+#[test]
+fn list_did_documents() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let mut did_docs = vec![];
+    for i in 0..3 {
+        let new_did_doc = DidDocument {
+            id: Did::new(&format!("{}did{}", DID_PREFIX, i)),
+            controller: vec![owner.to_string().into()],
+            service: vec![],
+            ..Default::default()
+        };
+        let result = contract
+            .create_did_document(new_did_doc.clone())
+            .call(&owner);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+        did_docs.push(new_did_doc);
+    }
+
+    let result = contract.list_did_documents(None, None, None).unwrap();
+    assert_eq!(did_docs, result);
+
+    let result = contract
+        .list_did_documents(Some(1), Some(did_docs[0].id.to_string()), None)
+        .unwrap();
+    assert_eq!(did_docs[1..2].to_vec(), result);
+
+    let result = contract
+        .list_did_documents(None, None, Some(Order::Descending))
+        .unwrap();
+    assert_eq!(
+        did_docs.iter().rev().cloned().collect::<Vec<_>>(),
+        result
+    );
+}
+
+#[test]
+fn list_did_documents_includes_deactivated() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "deactivated_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract.deactivate_did_document(Did::new(&did)).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract.list_did_documents(None, None, None).unwrap();
+    assert_eq!(1, result.len());
+    assert!(result[0].deactivated);
+}
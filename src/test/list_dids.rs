@@ -0,0 +1,99 @@
+use cosmwasm_std::Order;
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{Did, DidDocument},
+    test::{DID_METHOD, DID_PREFIX},
+};
+
+#[test]
+fn list_dids() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let owner2 = "owner2".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let dids = contract.list_dids(None, None, None).unwrap();
+    assert!(dids.is_empty());
+
+    let did1 = format!("{}{}", DID_PREFIX, "did1");
+    let did2 = format!("{}{}", DID_PREFIX, "did2");
+    let did3 = format!("{}{}", DID_PREFIX, "did3");
+    for (did, sender) in [(&did2, &owner), (&did1, &owner), (&did3, &owner2)] {
+        let new_did_doc = DidDocument {
+            id: Did::new(did),
+            controller: vec![sender.to_string().into()],
+            service: vec![],
+            ..Default::default()
+        };
+        let result = contract.create_did_document(new_did_doc).call(sender);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+    }
+
+    // All documents regardless of their controllers, in ascending order
+    let dids = contract.list_dids(None, None, None).unwrap();
+    assert_eq!(
+        vec![Did::new(&did1), Did::new(&did2), Did::new(&did3)],
+        dids
+    );
+
+    let dids = contract
+        .list_dids(None, None, Some(Order::Descending))
+        .unwrap();
+    assert_eq!(
+        vec![Did::new(&did3), Did::new(&did2), Did::new(&did1)],
+        dids
+    );
+}
+
+#[test]
+fn list_dids_paginated() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let mut dids = vec![];
+    for i in 0..5 {
+        let did = format!("{}did{}", DID_PREFIX, i);
+        let new_did_doc = DidDocument {
+            id: Did::new(&did),
+            controller: vec![owner.to_string().into()],
+            service: vec![],
+            ..Default::default()
+        };
+        let result = contract.create_did_document(new_did_doc).call(&owner);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+        dids.push(Did::new(&did));
+    }
+
+    let page = contract.list_dids(Some(2), None, None).unwrap();
+    assert_eq!(dids[0..2].to_vec(), page);
+
+    let page = contract
+        .list_dids(Some(2), Some(dids[1].to_string()), None)
+        .unwrap();
+    assert_eq!(dids[2..4].to_vec(), page);
+
+    let page = contract
+        .list_dids(Some(2), Some(dids[3].to_string()), None)
+        .unwrap();
+    assert_eq!(dids[4..5].to_vec(), page);
+
+    // Descending pages continue below `start_after`
+    let page = contract
+        .list_dids(Some(2), Some(dids[3].to_string()), Some(Order::Descending))
+        .unwrap();
+    assert_eq!(vec![dids[2].clone(), dids[1].clone()], page);
+
+    // A zero limit falls back to the default page size
+    let page = contract.list_dids(Some(0), None, None).unwrap();
+    assert_eq!(dids, page);
+}
@@ -0,0 +1,382 @@
+use cosmwasm_std::{Empty, Order};
+use cw_storage_plus::{Index, Map};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::{
+        sv::mt::{CodeId, DidContractProxy},
+        DidContract, CONTRACT_NAME, CONTRACT_VERSION,
+    },
+    state::{ContractVersion, Did, DidDocument, Service},
+    test::{DID_METHOD, DID_PREFIX},
+};
+
+#[test]
+fn instantiate_stores_contract_version() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let contract_info = {
+        let app = app.app();
+        let storage = app.contract_storage(&contract.contract_addr);
+        DidContract::new().contract_info.load(&*storage).unwrap()
+    };
+    assert_eq!(
+        ContractVersion {
+            contract: CONTRACT_NAME.to_string(),
+            version: CONTRACT_VERSION.to_string(),
+        },
+        contract_info
+    );
+}
+
+#[test]
+fn migrate_same_version() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, None)
+        .with_admin(owner.as_str())
+        .call(&owner)
+        .unwrap();
+
+    let result = contract.migrate(None).call(&owner, code_id.code_id());
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // Check the emitted events
+    let res = result.expect("Failed to get result");
+    assert_eq!(res.events.len(), 2);
+    assert_eq!(res.events[1].ty, "wasm-migrate");
+    assert_eq!(res.events[1].attributes[1].key, "from_version");
+    assert_eq!(res.events[1].attributes[1].value, CONTRACT_VERSION);
+    assert_eq!(res.events[1].attributes[2].key, "to_version");
+    assert_eq!(res.events[1].attributes[2].value, CONTRACT_VERSION);
+    assert_eq!(res.events[1].attributes[3].key, "migrations");
+    assert_eq!(res.events[1].attributes[3].value, "");
+}
+
+#[test]
+fn migrate_with_admin() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let new_admin = "new_admin".into_addr();
+
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, None)
+        .with_admin(owner.as_str())
+        .call(&owner)
+        .unwrap();
+    let result = contract.renounce_admin().call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let res = contract
+        .migrate(Some(new_admin.to_string()))
+        .call(&owner, code_id.code_id())
+        .expect("Failed to get result");
+    assert_eq!(res.events[1].ty, "wasm-migrate");
+    assert_eq!(res.events[1].attributes[4].key, "admin");
+    assert_eq!(res.events[1].attributes[4].value, new_admin.to_string());
+
+    let config = contract.get_config().unwrap();
+    assert_eq!(Some(new_admin), config.admin);
+}
+
+#[test]
+fn migrate_legacy_instance() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, None)
+        .with_admin(owner.as_str())
+        .call(&owner)
+        .unwrap();
+
+    // Simulate an instance created before contract info and config were stored
+    {
+        let did_contract = DidContract::new();
+        let mut app = app.app_mut();
+        let mut storage = app.contract_storage_mut(&contract.contract_addr);
+        did_contract.contract_info.remove(&mut *storage);
+        did_contract.config.remove(&mut *storage);
+    }
+
+    let result = contract.migrate(None).call(&owner, code_id.code_id());
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let res = result.expect("Failed to get result");
+    assert_eq!(res.events[1].ty, "wasm-migrate");
+    assert_eq!(res.events[1].attributes[1].value, "0.1.0");
+    assert_eq!(res.events[1].attributes[2].value, CONTRACT_VERSION);
+    assert_eq!(
+        res.events[1].attributes[3].value,
+        "backfill_config,reindex_controllers,backfill_did_count,index_services,normalize_service_ids"
+    );
+
+    // The legacy DID prefix is usable again
+    let did = format!("{}{}", DID_PREFIX, "migrated_did");
+    let did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let contract_info = {
+        let app = app.app();
+        let storage = app.contract_storage(&contract.contract_addr);
+        DidContract::new().contract_info.load(&*storage).unwrap()
+    };
+    assert_eq!(CONTRACT_VERSION, contract_info.version);
+}
+
+#[test]
+fn migrate_legacy_controller_index() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, None)
+        .with_admin(owner.as_str())
+        .call(&owner)
+        .unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "indexed_did");
+    let did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(did_doc.clone()).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // Replace the controller index with the legacy MultiSet layout
+    let legacy_controllers: Map<String, Empty> = Map::new("controllers");
+    let legacy_dids: Map<String, Empty> = Map::new_dyn(format!("controllers{}", owner));
+    {
+        let did_contract = DidContract::new();
+        let mut app = app.app_mut();
+        let mut storage = app.contract_storage_mut(&contract.contract_addr);
+        did_contract.contract_info.remove(&mut *storage);
+        did_contract.did_count.remove(&mut *storage);
+        did_contract
+            .did_docs
+            .idx
+            .controller
+            .remove(&mut *storage, did.as_bytes(), &did_doc)
+            .unwrap();
+        legacy_controllers
+            .save(&mut *storage, owner.to_string(), &Empty {})
+            .unwrap();
+        legacy_dids
+            .save(&mut *storage, did.clone(), &Empty {})
+            .unwrap();
+    }
+    let dids = contract
+        .get_controlled_dids(owner.to_string().into(), None, None)
+        .unwrap();
+    assert!(dids.is_empty());
+
+    let result = contract.migrate(None).call(&owner, code_id.code_id());
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let dids = contract
+        .get_controlled_dids(owner.to_string().into(), None, None)
+        .unwrap();
+    assert_eq!(vec![Did::new(&did)], dids);
+    assert_eq!(1, contract.count_dids().unwrap());
+
+    // The legacy entries are gone
+    {
+        let app = app.app();
+        let storage = app.contract_storage(&contract.contract_addr);
+        assert!(legacy_controllers
+            .keys(&*storage, None, None, Order::Ascending)
+            .next()
+            .is_none());
+        assert!(legacy_dids
+            .keys(&*storage, None, None, Order::Ascending)
+            .next()
+            .is_none());
+    }
+}
+
+#[test]
+fn migrate_legacy_service_indexes() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, None)
+        .with_admin(owner.as_str())
+        .call(&owner)
+        .unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "service_indexed_did");
+    let did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![Service {
+            id: Did::new(&format!("{}#charging", did)),
+            a_type: "ChargingStation".into(),
+            service_endpoint: "https://station.example.com/ocpp".into(),
+        }],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(did_doc.clone()).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // Simulate an instance created before the service indexes existed and
+    // service ids had to be URLs into their document
+    let legacy_did_doc = DidDocument {
+        service: vec![
+            Service {
+                id: Did::new(&did),
+                ..did_doc.service[0].clone()
+            },
+            Service {
+                id: Did::new(&format!("{}{}", DID_PREFIX, "other#ocpp")),
+                a_type: "OCPP".into(),
+                service_endpoint: "https://station.example.com/ocpp".into(),
+            },
+        ],
+        ..did_doc.clone()
+    };
+    {
+        let did_contract = DidContract::new();
+        let mut app = app.app_mut();
+        let mut storage = app.contract_storage_mut(&contract.contract_addr);
+        did_contract.contract_info.remove(&mut *storage);
+        did_contract
+            .did_docs
+            .save(&mut *storage, did.clone(), &legacy_did_doc)
+            .unwrap();
+        let idx = &did_contract.did_docs.idx;
+        idx.service_type
+            .remove(&mut *storage, did.as_bytes(), &legacy_did_doc)
+            .unwrap();
+        idx.service_endpoint_host
+            .remove(&mut *storage, did.as_bytes(), &legacy_did_doc)
+            .unwrap();
+    }
+    let dids = contract
+        .get_dids_by_service_type("ChargingStation".to_string(), None, None)
+        .unwrap();
+    assert!(dids.is_empty());
+
+    let result = contract.migrate(None).call(&owner, code_id.code_id());
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let dids = contract
+        .get_dids_by_service_type("ChargingStation".to_string(), None, None)
+        .unwrap();
+    assert_eq!(vec![Did::new(&did)], dids);
+    let dids = contract
+        .get_dids_by_service_endpoint("station.example.com".to_string(), None, None)
+        .unwrap();
+    assert_eq!(vec![Did::new(&did)], dids);
+
+    // Legacy service ids now point into the document, which can be updated again
+    let did_document = contract.get_did_document(Did::new(&did)).unwrap();
+    let service_ids: Vec<String> = did_document.service.iter().map(|s| s.id.to_string()).collect();
+    assert_eq!(
+        vec![format!("{}#service-1", did), format!("{}#ocpp", did)],
+        service_ids
+    );
+    let result = contract.update_did_document(did_document).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+}
+
+#[test]
+fn migrate_from_newer_version() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, None)
+        .with_admin(owner.as_str())
+        .call(&owner)
+        .unwrap();
+
+    {
+        let mut app = app.app_mut();
+        let mut storage = app.contract_storage_mut(&contract.contract_addr);
+        DidContract::new()
+            .contract_info
+            .save(
+                &mut *storage,
+                &ContractVersion {
+                    contract: CONTRACT_NAME.to_string(),
+                    version: "99.0.0".to_string(),
+                },
+            )
+            .unwrap();
+    }
+
+    let result = contract.migrate(None).call(&owner, code_id.code_id());
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!(
+            "Cannot migrate from version 99.0.0 to older version {}",
+            CONTRACT_VERSION
+        ),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn migrate_from_different_contract() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, None)
+        .with_admin(owner.as_str())
+        .call(&owner)
+        .unwrap();
+
+    {
+        let mut app = app.app_mut();
+        let mut storage = app.contract_storage_mut(&contract.contract_addr);
+        DidContract::new()
+            .contract_info
+            .save(
+                &mut *storage,
+                &ContractVersion {
+                    contract: "crates.io:other-contract".to_string(),
+                    version: CONTRACT_VERSION.to_string(),
+                },
+            )
+            .unwrap();
+    }
+
+    let result = contract.migrate(None).call(&owner, code_id.code_id());
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        "Cannot migrate from different contract: crates.io:other-contract",
+        result.err().unwrap().to_string()
+    );
+}
@@ -1,3 +1,21 @@
+#[cfg(test)]
+mod instantiate;
+
+#[cfg(test)]
+mod migrate;
+
+#[cfg(test)]
+mod config;
+
+#[cfg(test)]
+mod pause;
+
+#[cfg(test)]
+mod document_limits;
+
+#[cfg(test)]
+mod deposit;
+
 #[cfg(test)]
 mod create_did_document;
 
@@ -15,19 +33,57 @@ mod add_service;
 
 #[cfg(test)]
 mod delete_service;
+#[cfg(test)]
+mod update_service;
+
+#[cfg(test)]
+mod add_verification_method;
+
+#[cfg(test)]
+mod delete_verification_method;
+
+#[cfg(test)]
+mod add_verification_relationship;
+
+#[cfg(test)]
+mod delete_verification_relationship;
 
 #[cfg(test)]
 mod delete_did_document;
 
+#[cfg(test)]
+mod deactivate_did_document;
+
 #[cfg(test)]
 mod get_did_document;
 
+#[cfg(test)]
+mod resolve_did;
+
+#[cfg(test)]
+mod resolve_did_version;
+
+#[cfg(test)]
+mod resolve_did_at;
+
+#[cfg(test)]
+mod dereference;
+
+#[cfg(test)]
+mod get_did_history;
+
 #[cfg(test)]
 mod is_did_controller;
 
 #[cfg(test)]
 mod is_controller_of;
 
+#[cfg(test)]
+mod explain_authorization;
+
+#[cfg(test)]
+mod traversal_limits;
+
 #[cfg(test)]
 mod do_controllers_exist;
 
@@ -39,3 +95,59 @@ mod get_controlled_dids;
 
 #[cfg(test)]
 mod get_controlled_did_documents;
+
+#[cfg(test)]
+mod get_transitively_controlled_dids;
+
+#[cfg(test)]
+mod get_dids_by_service;
+
+#[cfg(test)]
+mod list_dids;
+
+#[cfg(test)]
+mod list_did_documents;
+
+#[cfg(test)]
+mod count_dids;
+#[cfg(test)]
+mod set_authorization_policy;
+#[cfg(test)]
+mod proposals;
+#[cfg(test)]
+mod execute_signed;
+#[cfg(test)]
+mod batch;
+
+use crate::state::{Did, DidDocument, Service, VerificationMethod};
+
+pub const DID_METHOD: &str = "c4e";
+pub const DID_PREFIX: &str = "did:c4e:";
+
+pub fn verification_method(id: &str, controller: &str) -> VerificationMethod {
+    VerificationMethod {
+        id: Did::new(&format!("{}#{}", controller, id)),
+        a_type: "Ed25519VerificationKey2020".to_string(),
+        controller: Did::new(controller),
+        public_key_multibase: Some("z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH".to_string()),
+        public_key_jwk: None,
+        blockchain_account_id: None,
+    }
+}
+
+pub fn did_doc(did: &str, controller: &str) -> DidDocument {
+    DidDocument {
+        id: Did::new(did),
+        controller: vec![controller.into()],
+        service: vec![],
+        ..Default::default()
+    }
+}
+
+pub fn service(id: &str, did: &str, a_type: &str, endpoint: &str) -> Service {
+    Service {
+        id: Did::new(&format!("{}#{}", did, id)),
+        a_type: a_type.into(),
+        service_endpoint: endpoint.into(),
+    }
+}
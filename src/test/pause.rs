@@ -0,0 +1,185 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{ConfigUpdate, Did, DidOperation, OperationKind, PauseScope, Service},
+    test::{did_doc, DID_METHOD, DID_PREFIX},
+};
+
+#[test]
+fn pause_all() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "paused_did");
+    let result = contract
+        .create_did_document(did_doc(&did, owner.as_str()))
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let res = contract
+        .pause(None)
+        .call(&owner)
+        .expect("Failed to get result");
+    assert_eq!(res.events.len(), 2);
+    assert_eq!(res.events[1].ty, "wasm-pause");
+    assert_eq!(res.events[1].attributes[1].key, "operations");
+    assert_eq!(res.events[1].attributes[1].value, "all");
+    assert_eq!(Some(PauseScope::All), contract.get_pause_scope().unwrap());
+
+    let other_did = format!("{}{}", DID_PREFIX, "other_did");
+    let result = contract
+        .create_did_document(did_doc(&other_did, owner.as_str()))
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!("Contract is paused", result.err().unwrap().to_string());
+
+    let result = contract.delete_did_document(Did::new(&did), None).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!("Contract is paused", result.err().unwrap().to_string());
+
+    let operations = vec![DidOperation::DeleteDidDocument {
+        did: Did::new(&did),
+        refund_address: None,
+    }];
+    let result = contract.batch(operations).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!("Contract is paused", result.err().unwrap().to_string());
+
+    // Queries and admin messages keep working
+    let did_document = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(did_doc(&did, owner.as_str()), did_document);
+    let result = contract.update_config(ConfigUpdate::default()).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let res = contract
+        .unpause()
+        .call(&owner)
+        .expect("Failed to get result");
+    assert_eq!(res.events[1].ty, "wasm-unpause");
+    assert_eq!(res.events[1].attributes[1].key, "operations");
+    assert_eq!(res.events[1].attributes[1].value, "all");
+    assert_eq!(None, contract.get_pause_scope().unwrap());
+
+    let result = contract
+        .create_did_document(did_doc(&other_did, owner.as_str()))
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+}
+
+#[test]
+fn pause_operations() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "partly_paused_did");
+    let result = contract
+        .create_did_document(did_doc(&did, owner.as_str()))
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let operations = vec![OperationKind::CreateDidDocument, OperationKind::AddService];
+    let res = contract
+        .pause(Some(operations.clone()))
+        .call(&owner)
+        .expect("Failed to get result");
+    assert_eq!(res.events[1].attributes[1].key, "operations");
+    assert_eq!(res.events[1].attributes[1].value, "create_did_document,add_service");
+    assert_eq!(
+        Some(PauseScope::Operations(operations)),
+        contract.get_pause_scope().unwrap()
+    );
+
+    let other_did = format!("{}{}", DID_PREFIX, "other_did");
+    let result = contract
+        .create_did_document(did_doc(&other_did, owner.as_str()))
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        "Operation paused: create_did_document",
+        result.err().unwrap().to_string()
+    );
+
+    // Paused operations cannot be batched or proposed either
+    let service = Service {
+        id: Did::new(&format!("{}#{}", did, "service1")),
+        a_type: "Service".into(),
+        service_endpoint: "https://example.com".into(),
+    };
+    let operation = DidOperation::AddService {
+        did: Did::new(&did),
+        service,
+    };
+    let result = contract.batch(vec![operation.clone()]).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        "Batch operation 0 failed: Operation paused: add_service",
+        result.err().unwrap().to_string()
+    );
+    let result = contract.propose(operation, None).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        "Operation paused: add_service",
+        result.err().unwrap().to_string()
+    );
+
+    // Other operations go through
+    let result = contract.delete_did_document(Did::new(&did), None).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+}
+
+#[test]
+fn pause_unauthorized() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let unauthorized_user = "unauthorized_user".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let result = contract.pause(None).call(&unauthorized_user);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Unauthorized: {}", unauthorized_user),
+        result.err().unwrap().to_string()
+    );
+
+    let result = contract.pause(None).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract.unpause().call(&unauthorized_user);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Unauthorized: {}", unauthorized_user),
+        result.err().unwrap().to_string()
+    );
+    assert_eq!(Some(PauseScope::All), contract.get_pause_scope().unwrap());
+}
+
+#[test]
+fn pause_invalid() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let result = contract.pause(Some(vec![])).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!("No operations to pause", result.err().unwrap().to_string());
+
+    let result = contract.unpause().call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!("Contract is not paused", result.err().unwrap().to_string());
+}
@@ -0,0 +1,336 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{AuthorizationPolicy, Controller, ControllerWeight, Did, DidDocument, DidOperation},
+    test::{DID_METHOD, DID_PREFIX},
+};
+
+#[test]
+fn propose_approve_and_execute() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let second = "second".into_addr();
+    let relayer = "relayer".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "multisig_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into(), second.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let policy = AuthorizationPolicy {
+        threshold: 2,
+        weights: vec![
+            ControllerWeight {
+                controller: owner.to_string().into(),
+                weight: 1,
+            },
+            ControllerWeight {
+                controller: second.to_string().into(),
+                weight: 1,
+            },
+        ],
+    };
+    let result = contract
+        .set_authorization_policy(Did::new(&did), Some(policy))
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let new_controller: Controller = "new_controller".into_addr().to_string().into();
+    let operation = DidOperation::AddController {
+        did: Did::new(&did),
+        controller: new_controller.clone(),
+    };
+    let res = contract
+        .propose(operation.clone(), None)
+        .call(&owner)
+        .expect("Failed to get result");
+    assert_eq!(res.events[1].ty, "wasm-propose");
+    assert_eq!(res.events[1].attributes[1].key, "proposal_id");
+    assert_eq!(res.events[1].attributes[1].value, "1");
+    assert_eq!(res.events[1].attributes[2].value, did);
+    assert_eq!(res.events[1].attributes[3].value, owner.to_string());
+
+    let proposal = contract.get_proposal(1).unwrap();
+    assert_eq!(operation, proposal.operation);
+    assert_eq!(vec![Controller::from(owner.to_string())], proposal.approvals);
+
+    // One approval is not enough
+    let result = contract.execute_proposal(1).call(&relayer);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Authorization threshold not met for {}: 1 of 2", did),
+        result.err().unwrap().to_string()
+    );
+
+    let result = contract.approve_proposal(1).call(&owner);
+    assert_eq!(
+        format!("Proposal already approved by: {}", owner),
+        result.err().unwrap().to_string()
+    );
+
+    let res = contract
+        .approve_proposal(1)
+        .call(&second)
+        .expect("Failed to get result");
+    assert_eq!(res.events[1].ty, "wasm-approve_proposal");
+    assert_eq!(res.events[1].attributes[3].key, "approver");
+    assert_eq!(res.events[1].attributes[3].value, second.to_string());
+
+    // Anyone can execute an approved proposal
+    let res = contract
+        .execute_proposal(1)
+        .call(&relayer)
+        .expect("Failed to get result");
+    assert_eq!(res.events.len(), 3);
+    assert_eq!(res.events[1].ty, "wasm-add_controller");
+    assert_eq!(res.events[2].ty, "wasm-execute_proposal");
+    assert_eq!(res.events[2].attributes[1].value, "1");
+
+    let did_document = contract.get_did_document(Did::new(&did)).unwrap();
+    assert!(did_document.has_controller(&new_controller));
+
+    let result = contract.get_proposal(1);
+    assert_eq!(
+        "Generic error: Querier contract error: Proposal not found: 1",
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn propose_unauthorized() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let unauthorized_user = "unauthorized_user".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "proposal_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let operation = DidOperation::DeleteDidDocument {
+        did: Did::new(&did),
+        refund_address: None,
+    };
+    let result = contract.propose(operation.clone(), None).call(&unauthorized_user);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Unauthorized: {}", unauthorized_user),
+        result.err().unwrap().to_string()
+    );
+
+    let result = contract.propose(operation, None).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract.approve_proposal(1).call(&unauthorized_user);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Unauthorized: {}", unauthorized_user),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn proposal_expiration() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "expiring_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let operation = DidOperation::DeactivateDidDocument {
+        did: Did::new(&did),
+    };
+    let now = app.block_info().time;
+    let result = contract.propose(operation.clone(), Some(now)).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        "Proposal expiration must be in the future",
+        result.err().unwrap().to_string()
+    );
+
+    let result = contract
+        .propose(operation, Some(now.plus_seconds(10)))
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract.close_proposal(1).call(&owner);
+    assert_eq!("Proposal not expired: 1", result.err().unwrap().to_string());
+
+    app.update_block(|block| {
+        block.height += 1;
+        block.time = block.time.plus_seconds(10);
+    });
+
+    let result = contract.execute_proposal(1).call(&owner);
+    assert_eq!("Proposal expired: 1", result.err().unwrap().to_string());
+
+    // An expired proposal is no longer listed as pending, but can still be closed
+    let proposals = contract.list_proposals(Did::new(&did), None, None).unwrap();
+    assert!(proposals.is_empty());
+
+    let res = contract
+        .close_proposal(1)
+        .call(&owner)
+        .expect("Failed to get result");
+    assert_eq!(res.events[1].ty, "wasm-close_proposal");
+
+    let proposal = contract.get_proposal(1);
+    assert!(proposal.is_err(), "Expected Err, but got an Ok");
+
+    let did_document = contract.get_did_document(Did::new(&did)).unwrap();
+    assert!(!did_document.deactivated);
+}
+
+#[test]
+fn list_proposals() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "listed_did");
+    let other_did = format!("{}{}", DID_PREFIX, "other_did");
+    for id in [&did, &other_did] {
+        let new_did_doc = DidDocument {
+            id: Did::new(id),
+            controller: vec![owner.to_string().into()],
+            service: vec![],
+            ..Default::default()
+        };
+        let result = contract.create_did_document(new_did_doc).call(&owner);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+    }
+
+    for id in [&did, &other_did, &did, &did] {
+        let operation = DidOperation::DeactivateDidDocument { did: Did::new(id) };
+        let result = contract.propose(operation, None).call(&owner);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+    }
+
+    let proposals = contract.list_proposals(Did::new(&did), None, None).unwrap();
+    assert_eq!(
+        vec![1, 3, 4],
+        proposals.iter().map(|p| p.id).collect::<Vec<_>>()
+    );
+
+    let proposals = contract
+        .list_proposals(Did::new(&did), Some(1), Some(1))
+        .unwrap();
+    assert_eq!(vec![3], proposals.iter().map(|p| p.id).collect::<Vec<_>>());
+
+    // Deleting the document drops its pending proposals
+    let result = contract.delete_did_document(Did::new(&did), None).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    let proposals = contract.list_proposals(Did::new(&did), None, None).unwrap();
+    assert!(proposals.is_empty());
+    let proposals = contract
+        .list_proposals(Did::new(&other_did), None, None)
+        .unwrap();
+    assert_eq!(1, proposals.len());
+}
+
+#[test]
+fn policy_keeps_weighted_controllers() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let second = "second".into_addr();
+    let third = "third".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "weighted_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![
+            owner.to_string().into(),
+            second.to_string().into(),
+            third.to_string().into(),
+        ],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let policy = AuthorizationPolicy {
+        threshold: 2,
+        weights: vec![
+            ControllerWeight {
+                controller: owner.to_string().into(),
+                weight: 1,
+            },
+            ControllerWeight {
+                controller: second.to_string().into(),
+                weight: 1,
+            },
+        ],
+    };
+    let result = contract
+        .set_authorization_policy(Did::new(&did), Some(policy))
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // Third carries no weight, so it cannot approve
+    let operation = DidOperation::DeleteController {
+        did: Did::new(&did),
+        controller: second.to_string().into(),
+    };
+    let result = contract.propose(operation.clone(), None).call(&third);
+    assert_eq!(
+        format!("Unauthorized: {}", third),
+        result.err().unwrap().to_string()
+    );
+
+    let result = contract.propose(operation, None).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    let result = contract.approve_proposal(1).call(&second);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract.execute_proposal(1).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!(
+            "Authorization policy cannot be satisfied by the controllers of {}",
+            did
+        ),
+        result.err().unwrap().to_string()
+    );
+
+    // The failed execution leaves the proposal in place
+    assert!(contract.get_proposal(1).is_ok());
+}
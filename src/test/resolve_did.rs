@@ -0,0 +1,255 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{
+        BlockStamp, Did, DidDocument, DidDocumentMetadata, DidResolutionError, DidResolutionResult,
+        Service, DID_JSON_CONTENT_TYPE,
+    },
+    test::{DID_METHOD, DID_PREFIX},
+};
+
+#[test]
+fn resolve_created_did_document() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "resolved_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let created = BlockStamp::from(&app.block_info());
+    let result = contract.resolve_did(Did::new(&did)).unwrap();
+    assert_eq!(Some(new_did_doc), result.did_document);
+    assert_eq!(
+        DidDocumentMetadata {
+            created: Some(created),
+            version_id: Some(1),
+            ..Default::default()
+        },
+        result.did_document_metadata
+    );
+    assert_eq!(
+        Some(DID_JSON_CONTENT_TYPE.to_string()),
+        result.did_resolution_metadata.content_type
+    );
+    assert_eq!(None, result.did_resolution_metadata.error);
+}
+
+#[test]
+fn resolve_updated_did_document() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "updated_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    let created = BlockStamp::from(&app.block_info());
+
+    app.update_block(|block| {
+        block.height += 1;
+        block.time = block.time.plus_seconds(5);
+    });
+
+    let result = contract
+        .add_service(
+            Did::new(&did),
+            Service {
+                id: Did::new(&format!("{}#service", did)),
+                a_type: "Service".into(),
+                service_endpoint: "https://example.com".into(),
+            },
+        )
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    app.update_block(|block| {
+        block.height += 1;
+        block.time = block.time.plus_seconds(5);
+    });
+
+    let result = contract
+        .delete_service(Did::new(&did), Did::new(&format!("{}#service", did)))
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    let updated = BlockStamp::from(&app.block_info());
+
+    let result = contract.resolve_did(Did::new(&did)).unwrap();
+    assert_eq!(Some(new_did_doc), result.did_document);
+    assert_eq!(
+        DidDocumentMetadata {
+            created: Some(created),
+            updated: Some(updated),
+            version_id: Some(3),
+            ..Default::default()
+        },
+        result.did_document_metadata
+    );
+}
+
+#[test]
+fn resolve_deactivated_did_document() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "deactivated_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract.deactivate_did_document(Did::new(&did)).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract.resolve_did(Did::new(&did)).unwrap();
+    assert!(result.did_document.unwrap().deactivated);
+    assert!(result.did_document_metadata.deactivated);
+    assert_eq!(Some(2), result.did_document_metadata.version_id);
+}
+
+#[test]
+fn resolve_deleted_did_document() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "deleted_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract.delete_did_document(Did::new(&did), None).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract.resolve_did(Did::new(&did)).unwrap();
+    assert_eq!(DidResolutionResult::error(DidResolutionError::NotFound), result);
+
+    // A re-created document continues after the deletion tombstone
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract.resolve_did(Did::new(&did)).unwrap();
+    assert_eq!(Some(3), result.did_document_metadata.version_id);
+    assert_eq!(None, result.did_document_metadata.updated);
+}
+
+#[test]
+fn resolve_not_found() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "unknown_did");
+    let result = contract.resolve_did(Did::new(&did)).unwrap();
+    assert_eq!(None, result.did_document);
+    assert_eq!(DidDocumentMetadata::default(), result.did_document_metadata);
+    assert_eq!(
+        Some(DidResolutionError::NotFound),
+        result.did_resolution_metadata.error
+    );
+    assert_eq!(None, result.did_resolution_metadata.content_type);
+}
+
+#[test]
+fn resolution_error_codes() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "unknown_did");
+    for (did, code) in [
+        (did.as_str(), "notFound"),
+        ("invalid_did_format", "invalidDid"),
+        ("did:other:abc", "methodNotSupported"),
+    ] {
+        let result = contract.resolve_did(Did::new(did)).unwrap();
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(code, json["did_resolution_metadata"]["error"]);
+    }
+}
+
+#[test]
+fn resolve_invalid_did() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let result = contract.resolve_did(Did::new("invalid_did_format")).unwrap();
+    assert_eq!(DidResolutionResult::error(DidResolutionError::InvalidDid), result);
+
+    let did = format!("{}{}", DID_PREFIX, "did#fragment");
+    let result = contract.resolve_did(Did::new(&did)).unwrap();
+    assert_eq!(DidResolutionResult::error(DidResolutionError::InvalidDid), result);
+}
+
+#[test]
+fn resolve_unsupported_method() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let result = contract.resolve_did(Did::new("did:other:abc")).unwrap();
+    assert_eq!(
+        DidResolutionResult::error(DidResolutionError::MethodNotSupported),
+        result
+    );
+}
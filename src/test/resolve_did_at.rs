@@ -0,0 +1,88 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{Did, DidDocument, DidResolutionError, DidResolutionResult, VersionTime},
+    test::{DID_METHOD, DID_PREFIX},
+};
+
+#[test]
+fn resolve_did_at_height_and_time() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let controller = "controller".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "timed_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let created = app.block_info();
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    app.update_block(|block| {
+        block.height += 10;
+        block.time = block.time.plus_seconds(50);
+    });
+
+    let updated = app.block_info();
+    let result = contract
+        .add_controller(Did::new(&did), controller.to_string().into())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // Before creation
+    let result = contract
+        .resolve_did_at(Did::new(&did), VersionTime::Height(created.height - 1))
+        .unwrap();
+    assert_eq!(DidResolutionResult::error(DidResolutionError::NotFound), result);
+
+    // Between the two revisions
+    let result = contract
+        .resolve_did_at(Did::new(&did), VersionTime::Height(updated.height - 1))
+        .unwrap();
+    assert_eq!(Some(1), result.did_document_metadata.version_id);
+    assert_eq!(Some(new_did_doc.clone()), result.did_document);
+
+    let result = contract
+        .resolve_did_at(Did::new(&did), VersionTime::Time(created.time.plus_seconds(10)))
+        .unwrap();
+    assert_eq!(Some(1), result.did_document_metadata.version_id);
+
+    // At and after the update
+    let result = contract
+        .resolve_did_at(Did::new(&did), VersionTime::Height(updated.height))
+        .unwrap();
+    assert_eq!(Some(2), result.did_document_metadata.version_id);
+
+    let result = contract
+        .resolve_did_at(Did::new(&did), VersionTime::Time(updated.time.plus_seconds(100)))
+        .unwrap();
+    assert_eq!(Some(2), result.did_document_metadata.version_id);
+    assert_eq!(2, result.did_document.unwrap().controller.len());
+}
+
+#[test]
+fn resolve_did_at_invalid_did() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let result = contract
+        .resolve_did_at(Did::new("invalid_did_format"), VersionTime::Height(1))
+        .unwrap();
+    assert_eq!(DidResolutionResult::error(DidResolutionError::InvalidDid), result);
+}
@@ -0,0 +1,121 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{BlockStamp, Did, DidDocument, DidResolutionError, DidResolutionResult},
+    test::{DID_METHOD, DID_PREFIX},
+};
+
+#[test]
+fn resolve_did_version() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let controller = "controller".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "versioned_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    app.update_block(|block| {
+        block.height += 1;
+        block.time = block.time.plus_seconds(5);
+    });
+
+    let result = contract
+        .add_controller(Did::new(&did), controller.to_string().into())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    let updated = BlockStamp::from(&app.block_info());
+
+    let result = contract.resolve_did_version(Did::new(&did), 1).unwrap();
+    assert_eq!(Some(new_did_doc.clone()), result.did_document);
+    assert_eq!(Some(1), result.did_document_metadata.version_id);
+    assert_eq!(None, result.did_document_metadata.updated);
+    assert_eq!(Some(updated.clone()), result.did_document_metadata.next_update);
+
+    let result = contract.resolve_did_version(Did::new(&did), 2).unwrap();
+    assert_eq!(
+        Some(DidDocument {
+            controller: vec![owner.to_string().into(), controller.to_string().into()],
+            ..new_did_doc
+        }),
+        result.did_document
+    );
+    assert_eq!(Some(2), result.did_document_metadata.version_id);
+    assert_eq!(Some(updated), result.did_document_metadata.updated);
+    assert_eq!(None, result.did_document_metadata.next_update);
+}
+
+#[test]
+fn resolve_did_version_deactivated() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "deactivated_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract.deactivate_did_document(Did::new(&did)).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract.resolve_did_version(Did::new(&did), 1).unwrap();
+    assert!(!result.did_document_metadata.deactivated);
+
+    let result = contract.resolve_did_version(Did::new(&did), 2).unwrap();
+    assert!(result.did_document_metadata.deactivated);
+}
+
+#[test]
+fn resolve_did_version_not_found() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "versioned_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract
+        .create_did_document(new_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract.resolve_did_version(Did::new(&did), 2).unwrap();
+    assert_eq!(DidResolutionResult::error(DidResolutionError::NotFound), result);
+
+    let result = contract
+        .resolve_did_version(Did::new("invalid_did_format"), 1)
+        .unwrap();
+    assert_eq!(DidResolutionResult::error(DidResolutionError::InvalidDid), result);
+}
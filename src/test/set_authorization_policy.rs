@@ -0,0 +1,223 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{AuthorizationPolicy, ControllerWeight, Did, DidDocument},
+    test::{DID_METHOD, DID_PREFIX},
+};
+
+#[test]
+fn set_authorization_policy() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let second = "second".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "multisig_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into(), second.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let policy = AuthorizationPolicy {
+        threshold: 2,
+        weights: vec![
+            ControllerWeight {
+                controller: owner.to_string().into(),
+                weight: 1,
+            },
+            ControllerWeight {
+                controller: second.to_string().into(),
+                weight: 1,
+            },
+        ],
+    };
+    let res = contract
+        .set_authorization_policy(Did::new(&did), Some(policy.clone()))
+        .call(&owner)
+        .expect("Failed to get result");
+    assert_eq!(res.events[1].ty, "wasm-set_authorization_policy");
+    assert_eq!(res.events[1].attributes[1].key, "did");
+    assert_eq!(res.events[1].attributes[1].value, did);
+    assert_eq!(res.events[1].attributes[2].key, "threshold");
+    assert_eq!(res.events[1].attributes[2].value, "2");
+
+    let stored = contract.get_authorization_policy(Did::new(&did)).unwrap();
+    assert_eq!(Some(policy), stored);
+
+    // A single controller is no longer enough
+    let result = contract
+        .add_controller(Did::new(&did), "third".into_addr().to_string().into())
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Authorization threshold not met for {}: 1 of 2", did),
+        result.err().unwrap().to_string()
+    );
+
+    let result = contract
+        .set_authorization_policy(Did::new(&did), None)
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+}
+
+#[test]
+fn set_authorization_policy_unauthorized() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let unauthorized_user = "unauthorized_user".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "policy_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let policy = AuthorizationPolicy {
+        threshold: 1,
+        weights: vec![ControllerWeight {
+            controller: owner.to_string().into(),
+            weight: 1,
+        }],
+    };
+    let result = contract
+        .set_authorization_policy(Did::new(&did), Some(policy))
+        .call(&unauthorized_user);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Unauthorized: {}", unauthorized_user),
+        result.err().unwrap().to_string()
+    );
+    assert_eq!(None, contract.get_authorization_policy(Did::new(&did)).unwrap());
+}
+
+#[test]
+fn set_invalid_authorization_policy() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let second = "second".into_addr();
+    let outsider = "outsider".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "invalid_policy_did");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into(), second.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let weight = |controller: &str, weight: u64| ControllerWeight {
+        controller: controller.to_string().into(),
+        weight,
+    };
+
+    let result = contract
+        .set_authorization_policy(
+            Did::new(&did),
+            Some(AuthorizationPolicy {
+                threshold: 0,
+                weights: vec![weight(owner.as_str(), 1)],
+            }),
+        )
+        .call(&owner);
+    assert_eq!(
+        "Invalid authorization policy: threshold must be greater than zero",
+        result.err().unwrap().to_string()
+    );
+
+    let result = contract
+        .set_authorization_policy(
+            Did::new(&did),
+            Some(AuthorizationPolicy {
+                threshold: 1,
+                weights: vec![weight(owner.as_str(), 0)],
+            }),
+        )
+        .call(&owner);
+    assert_eq!(
+        format!("Invalid authorization policy: zero weight for {}", owner),
+        result.err().unwrap().to_string()
+    );
+
+    let result = contract
+        .set_authorization_policy(
+            Did::new(&did),
+            Some(AuthorizationPolicy {
+                threshold: 1,
+                weights: vec![weight(owner.as_str(), 1), weight(owner.as_str(), 1)],
+            }),
+        )
+        .call(&owner);
+    assert_eq!(
+        format!("Duplicated controller: {}", owner),
+        result.err().unwrap().to_string()
+    );
+
+    let result = contract
+        .set_authorization_policy(
+            Did::new(&did),
+            Some(AuthorizationPolicy {
+                threshold: 1,
+                weights: vec![weight(outsider.as_str(), 1)],
+            }),
+        )
+        .call(&owner);
+    assert_eq!(
+        format!("Did document controller not exists: {}", outsider),
+        result.err().unwrap().to_string()
+    );
+
+    let result = contract
+        .set_authorization_policy(
+            Did::new(&did),
+            Some(AuthorizationPolicy {
+                threshold: 3,
+                weights: vec![weight(owner.as_str(), 1), weight(second.as_str(), 1)],
+            }),
+        )
+        .call(&owner);
+    assert_eq!(
+        format!(
+            "Authorization policy cannot be satisfied by the controllers of {}",
+            did
+        ),
+        result.err().unwrap().to_string()
+    );
+
+    // Weights that would wrap around are rejected instead of summed
+    let result = contract
+        .set_authorization_policy(
+            Did::new(&did),
+            Some(AuthorizationPolicy {
+                threshold: 1,
+                weights: vec![weight(owner.as_str(), u64::MAX), weight(second.as_str(), 2)],
+            }),
+        )
+        .call(&owner);
+    assert_eq!(
+        "Invalid authorization policy: total weight overflows",
+        result.err().unwrap().to_string()
+    );
+}
@@ -0,0 +1,305 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{ConfigUpdate, Controller, Did, Service, TraversalLimits},
+    test::{did_doc, DID_METHOD, DID_PREFIX},
+};
+
+fn with_limits(limits: TraversalLimits) -> Option<ConfigUpdate> {
+    Some(ConfigUpdate {
+        traversal_limits: Some(limits),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn instantiate_with_traversal_limits() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let limits = TraversalLimits {
+        max_delegation_depth: 3,
+        max_visited_nodes: 10,
+    };
+    let res = code_id
+        .instantiate(DID_METHOD.to_string(), None, with_limits(limits))
+        .call(&owner);
+    assert!(res.is_ok(), "Expected Ok, but got an Err");
+
+    for (max_delegation_depth, max_visited_nodes, field) in
+        [(0, 10, "max_delegation_depth"), (3, 0, "max_visited_nodes")]
+    {
+        let limits = TraversalLimits {
+            max_delegation_depth,
+            max_visited_nodes,
+        };
+        let result = code_id
+            .instantiate(DID_METHOD.to_string(), None, with_limits(limits))
+            .call(&owner);
+        assert!(result.is_err(), "Expected Err, but got an Ok");
+        assert_eq!(
+            format!("Invalid traversal limits: {} must be positive", field),
+            result.err().unwrap().to_string()
+        );
+    }
+}
+
+#[test]
+fn create_did_document_exceeding_delegation_depth() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let limits = TraversalLimits {
+        max_delegation_depth: 2,
+        max_visited_nodes: 10,
+    };
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, with_limits(limits))
+        .call(&owner)
+        .unwrap();
+
+    // owner -> did_a -> did_b reaches the owner in two links
+    let did_a = format!("{}{}", DID_PREFIX, "depth_a");
+    let did_b = format!("{}{}", DID_PREFIX, "depth_b");
+    let did_c = format!("{}{}", DID_PREFIX, "depth_c");
+    let result = contract
+        .create_did_document(did_doc(&did_a, owner.as_str()))
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+    let result = contract
+        .create_did_document(did_doc(&did_b, &did_a))
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .is_did_controller(Did::new(&did_b), owner.to_string().into())
+        .unwrap();
+    assert!(result, "Expected owner to control the document at the maximum depth");
+
+    let result = contract
+        .create_did_document(did_doc(&did_c, &did_b))
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Delegation depth of {} exceeds the maximum of 2", did_c),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn add_controller_exceeding_delegation_depth_of_controlled_document() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let limits = TraversalLimits {
+        max_delegation_depth: 3,
+        max_visited_nodes: 10,
+    };
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, with_limits(limits))
+        .call(&owner)
+        .unwrap();
+
+    // owner -> did_a -> did_b -> did_c, and owner -> did_d
+    let did_a = format!("{}{}", DID_PREFIX, "chain_a");
+    let did_b = format!("{}{}", DID_PREFIX, "chain_b");
+    let did_c = format!("{}{}", DID_PREFIX, "chain_c");
+    let did_d = format!("{}{}", DID_PREFIX, "chain_d");
+    for (did, controller) in [
+        (&did_a, owner.to_string()),
+        (&did_b, did_a.clone()),
+        (&did_c, did_b.clone()),
+        (&did_d, owner.to_string()),
+    ] {
+        let result = contract
+            .create_did_document(did_doc(did, &controller))
+            .call(&owner);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+    }
+
+    // Through did_d the controllers of did_a are two links away, which puts
+    // them four links away from did_c
+    let result = contract
+        .add_controller(Did::new(&did_a), did_d.clone().into())
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Delegation depth of {} exceeds the maximum of 3", did_c),
+        result.err().unwrap().to_string()
+    );
+
+    let mut new_did_doc = did_doc(&did_a, owner.as_str());
+    new_did_doc.controller.push(did_d.clone().into());
+    let result = contract.update_did_document(new_did_doc).call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Delegation depth of {} exceeds the maximum of 3", did_c),
+        result.err().unwrap().to_string()
+    );
+
+    let did_document = contract.get_did_document(Did::new(&did_a)).unwrap();
+    assert_eq!(vec![Controller::from(owner.to_string())], did_document.controller);
+
+    // A leaf document has nothing under it
+    let result = contract
+        .add_controller(Did::new(&did_c), did_d.clone().into())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+}
+
+#[test]
+fn create_did_document_exceeding_visited_nodes() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let limits = TraversalLimits {
+        max_delegation_depth: 10,
+        max_visited_nodes: 2,
+    };
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, with_limits(limits))
+        .call(&owner)
+        .unwrap();
+
+    let did_a = format!("{}{}", DID_PREFIX, "budget_a");
+    let did_b = format!("{}{}", DID_PREFIX, "budget_b");
+    let did_c = format!("{}{}", DID_PREFIX, "budget_c");
+    let did_d = format!("{}{}", DID_PREFIX, "budget_d");
+    for (did, controller) in [
+        (&did_a, owner.to_string()),
+        (&did_b, did_a.clone()),
+        (&did_c, did_b.clone()),
+    ] {
+        let result = contract
+            .create_did_document(did_doc(did, &controller))
+            .call(&owner);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+    }
+
+    let result = contract
+        .create_did_document(did_doc(&did_d, &did_c))
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Controller traversal of {} visits more than 2 documents", did_d),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn add_controller_exceeding_visited_nodes_of_controlled_document() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let limits = TraversalLimits {
+        max_delegation_depth: 10,
+        max_visited_nodes: 3,
+    };
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, with_limits(limits))
+        .call(&owner)
+        .unwrap();
+
+    // owner -> did_a -> did_b, and owner -> did_x1..did_x3
+    let did_a = format!("{}{}", DID_PREFIX, "visited_a");
+    let did_b = format!("{}{}", DID_PREFIX, "visited_b");
+    let xs: Vec<String> = (1..=3)
+        .map(|i| format!("{}visited_x{}", DID_PREFIX, i))
+        .collect();
+    let mut docs = vec![(&did_a, owner.to_string()), (&did_b, did_a.clone())];
+    docs.extend(xs.iter().map(|x| (x, owner.to_string())));
+    for (did, controller) in docs {
+        let result = contract
+            .create_did_document(did_doc(did, &controller))
+            .call(&owner);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+    }
+
+    for x in &xs[..2] {
+        let result = contract
+            .add_controller(Did::new(&did_a), x.clone().into())
+            .call(&owner);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+    }
+
+    // did_a itself looks into three documents, but did_b would need four
+    let result = contract
+        .add_controller(Did::new(&did_a), xs[2].clone().into())
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Controller traversal of {} visits more than 3 documents", did_b),
+        result.err().unwrap().to_string()
+    );
+
+    let result = contract
+        .is_did_controller(Did::new(&did_b), owner.to_string().into())
+        .unwrap();
+    assert!(result, "Expected did_b to stay authorizable");
+}
+
+#[test]
+fn add_controller_with_many_controlled_documents() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let other = "other".into_addr();
+
+    let limits = TraversalLimits {
+        max_delegation_depth: 10,
+        max_visited_nodes: 2,
+    };
+    let contract = code_id
+        .instantiate(DID_METHOD.to_string(), None, with_limits(limits))
+        .call(&owner)
+        .unwrap();
+
+    let did_a = format!("{}{}", DID_PREFIX, "wide_a");
+    let result = contract
+        .create_did_document(did_doc(&did_a, owner.as_str()))
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // Anyone may name did_a as a controller, more times than the budget
+    for i in 1..=3 {
+        let did = format!("{}wide_b{}", DID_PREFIX, i);
+        let result = contract
+            .create_did_document(did_doc(&did, &did_a))
+            .call(&other);
+        assert!(result.is_ok(), "Expected Ok, but got an Err");
+    }
+
+    let result = contract
+        .add_controller(Did::new(&did_a), other.to_string().into())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let mut new_did_doc = did_doc(&did_a, owner.as_str());
+    new_did_doc.controller.push(other.to_string().into());
+    new_did_doc.service.push(Service {
+        id: Did::new(&format!("{}#{}", did_a, "service1")),
+        a_type: "LinkedDomains".into(),
+        service_endpoint: "https://example.com".into(),
+    });
+    let result = contract.update_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let did = format!("{}wide_b{}", DID_PREFIX, 3);
+    let result = contract
+        .is_did_controller(Did::new(&did), owner.to_string().into())
+        .unwrap();
+    assert!(result, "Expected owner to control wide_b3");
+}
@@ -4,7 +4,8 @@ use sylvia::multitest::App;
 
 use crate::{
     contract::sv::mt::{CodeId, DidContractProxy},
-    state::{Did, DidDocument, Service, DID_PREFIX},
+    state::{Did, DidDocument, RelationshipMethod, Service, VerificationMethod},
+    test::{DID_METHOD, DID_PREFIX},
 };
 
 #[test]
@@ -14,17 +15,18 @@ fn update_did_document_modify_services() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "valid_update_did");
     let mut original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", did, "service1")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -35,9 +37,9 @@ fn update_did_document_modify_services() {
 
     // Update the DID Document
     original_did_doc.service.push(Service {
-        a_type: "NewServiceType".to_string(),
-        id: Did::new(&format!("{}{}", DID_PREFIX, "service2")),
-        service_endpoint: "http://new-service.com".to_string(),
+        a_type: "NewServiceType".into(),
+        id: Did::new(&format!("{}#{}", did, "service2")),
+        service_endpoint: "http://new-service.com".into(),
     });
 
     let result = contract
@@ -58,17 +60,18 @@ fn update_did_document_modify_controllers_and_services() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "valid_update_did");
     let mut original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", did, "service1")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -83,9 +86,9 @@ fn update_did_document_modify_controllers_and_services() {
 
     // Update the DID Document
     original_did_doc.service.push(Service {
-        a_type: "NewServiceType".to_string(),
-        id: Did::new(&format!("{}{}", DID_PREFIX, "service2")),
-        service_endpoint: "http://new-service.com".to_string(),
+        a_type: "NewServiceType".into(),
+        id: Did::new(&format!("{}#{}", did, "service2")),
+        service_endpoint: "http://new-service.com".into(),
     });
 
     let result = contract
@@ -106,17 +109,18 @@ fn update_did_document_modify_controllers() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "valid_update_did");
     let mut original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", did, "service1")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -146,13 +150,14 @@ fn update_nonexistent_did_document() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "nonexistent_did");
     let nonexistent_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     let result = contract
@@ -177,13 +182,14 @@ fn update_did_document_with_invalid_controller() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "invalid_controller_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -217,13 +223,14 @@ fn update_did_document_with_duplicate_controllers() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "duplicate_controllers_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -242,7 +249,7 @@ fn update_did_document_with_duplicate_controllers() {
         .call(&owner);
     assert!(result.is_err(), "Expected Err, but got an Ok");
     assert_eq!(
-        format!("Duplicated controller: {}", owner.to_string()),
+        format!("Duplicated controller: {}", owner),
         result.err().unwrap().to_string()
     );
 
@@ -258,13 +265,14 @@ fn update_did_document_to_self_controlled() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "self_controlled_update_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -299,13 +307,14 @@ fn update_did_document_unauthorized() {
     let owner = "owner".into_addr();
     let unauthorized_user = "unauthorized_user".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "unauthorized_update_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -318,9 +327,9 @@ fn update_did_document_unauthorized() {
 
     // Attempt to update the DID Document as an unauthorized user
     update_did_doc.service.push(Service {
-        a_type: "UnauthorizedService".to_string(),
-        id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-        service_endpoint: "http://unauthorized-service.com".to_string(),
+        a_type: "UnauthorizedService".into(),
+        id: Did::new(&format!("{}#{}", did, "service1")),
+        service_endpoint: "http://unauthorized-service.com".into(),
     });
 
     let result = contract
@@ -328,7 +337,7 @@ fn update_did_document_unauthorized() {
         .call(&unauthorized_user);
     assert!(result.is_err(), "Expected Err, but got an Ok");
     assert_eq!(
-        format!("Unauthorized: {}", unauthorized_user.to_string()),
+        format!("Unauthorized: {}", unauthorized_user),
         result.err().unwrap().to_string()
     );
 
@@ -344,7 +353,7 @@ fn update_did_document_with_did_as_controller() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the first DID Document (controller DID)
     let controller_did = format!("{}{}", DID_PREFIX, "controller_did");
@@ -352,10 +361,11 @@ fn update_did_document_with_did_as_controller() {
         id: Did::new(&controller_did),
         controller: vec![owner.to_string().into()], // Normal address as controller
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", controller_did, "service1")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -369,10 +379,11 @@ fn update_did_document_with_did_as_controller() {
         id: Did::new(&did),
         controller: vec![controller_did.into()],
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service2")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", did, "service2")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -402,17 +413,18 @@ fn update_did_document_by_two_separate_controllers() {
     let owner2 = "owner2".into_addr();
     let second_controller_did = format!("{}{}", DID_PREFIX, "second_controller_did");
 
-    let contract = code_id.instantiate().call(&owner1).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner1).unwrap();
 
     // Create the first DID Document (second controller DID)
     let second_controller_did_doc = DidDocument {
         id: Did::new(&second_controller_did),
         controller: vec![owner2.to_string().into()], // Normal address as controller
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", second_controller_did, "service1")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -426,10 +438,11 @@ fn update_did_document_by_two_separate_controllers() {
         id: Did::new(&did),
         controller: vec![owner1.to_string().into(), second_controller_did.to_string().into()],
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service2")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", did, "service2")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -439,9 +452,9 @@ fn update_did_document_by_two_separate_controllers() {
 
     // First update by the address controller
     original_did_doc.service.push(Service {
-        a_type: "NewServiceTypeByOwner".to_string(),
-        id: Did::new(&format!("{}{}", DID_PREFIX, "service3")),
-        service_endpoint: "http://owner-service.com".to_string(),
+        a_type: "NewServiceTypeByOwner".into(),
+        id: Did::new(&format!("{}#{}", did, "service3")),
+        service_endpoint: "http://owner-service.com".into(),
     });
 
     let result = contract
@@ -456,9 +469,9 @@ fn update_did_document_by_two_separate_controllers() {
     // Second update by the DID controller
     let mut updated_did_doc_by_did = updated_did_doc.clone();
     updated_did_doc_by_did.service.push(Service {
-        a_type: "NewServiceTypeByDID".to_string(),
-        id: Did::new(&format!("{}{}", DID_PREFIX, "service4")),
-        service_endpoint: "http://did-service.com".to_string(),
+        a_type: "NewServiceTypeByDID".into(),
+        id: Did::new(&format!("{}#{}", did, "service4")),
+        service_endpoint: "http://did-service.com".into(),
     });
 
     let result = contract
@@ -478,17 +491,18 @@ fn update_did_document_with_no_controllers() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "no_controllers_update_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", did, "service1")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -522,7 +536,7 @@ fn update_document_with_unsignable_did() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     const DID_STORE: Map<String, DidDocument> = Map::new("dids");
 
@@ -531,6 +545,7 @@ fn update_document_with_unsignable_did() {
         id: Did::new(&did_controlled_by_itself),
         controller: vec![did_controlled_by_itself.to_string().into()],
         service: vec![],
+        ..Default::default()
     };
 
     {
@@ -555,10 +570,11 @@ fn update_document_with_unsignable_did() {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", did, "service1")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -593,7 +609,7 @@ fn update_did_document_unauthorized_when_did_is_controller() {
     let owner = "owner".into_addr();
     let unauthorized_user = "unauthorized_user".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     // Create the first DID Document (controller DID)
     let controller_did = format!("{}{}", DID_PREFIX, "controller_did");
@@ -601,10 +617,11 @@ fn update_did_document_unauthorized_when_did_is_controller() {
         id: Did::new(&controller_did),
         controller: vec![owner.to_string().into()], // Normal address as controller
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", controller_did, "service1")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -618,10 +635,11 @@ fn update_did_document_unauthorized_when_did_is_controller() {
         id: Did::new(&did),
         controller: vec![controller_did.to_string().into()], // DID as controller
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service2")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", did, "service2")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -632,9 +650,9 @@ fn update_did_document_unauthorized_when_did_is_controller() {
     // Attempt to update the DID Document by an unauthorized user
     let mut updated_did_doc = original_did_doc.clone();
     updated_did_doc.service.push(Service {
-        a_type: "NewServiceType".to_string(),
-        id: Did::new(&format!("{}{}", DID_PREFIX, "service3")),
-        service_endpoint: "http://new-service.com".to_string(),
+        a_type: "NewServiceType".into(),
+        id: Did::new(&format!("{}#{}", did, "service3")),
+        service_endpoint: "http://new-service.com".into(),
     });
 
     let result = contract
@@ -642,7 +660,7 @@ fn update_did_document_unauthorized_when_did_is_controller() {
         .call(&unauthorized_user);
     assert!(result.is_err(), "Expected Err, but got an Ok");
     assert_eq!(
-        format!("Unauthorized: {}", unauthorized_user.to_string()),
+        format!("Unauthorized: {}", unauthorized_user),
         result.err().unwrap().to_string()
     );
 
@@ -658,18 +676,19 @@ fn update_did_document_with_event_verification() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "event_verification_did");
-    let service1_did = format!("{}{}", DID_PREFIX, "service_did");
+    let service1_did = format!("{}#{}", did, "service_did");
     let mut original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
+            a_type: "ServiceType".into(),
             id: Did::new(&service1_did),
-            service_endpoint: "http://example.com".to_string(),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -679,11 +698,11 @@ fn update_did_document_with_event_verification() {
     assert!(result.is_ok(), "Expected Ok, but got an Err");
 
     // Update the DID Document
-    let service2_did = format!("{}{}", DID_PREFIX, "service2_did");
+    let service2_did = format!("{}#{}", did, "service2_did");
     original_did_doc.service = vec![Service {
-        a_type: "NewServiceType".to_string(),
+        a_type: "NewServiceType".into(),
         id: Did::new(&service2_did),
-        service_endpoint: "http://new-service.com".to_string(),
+        service_endpoint: "http://new-service.com".into(),
     }];
 
     let result = contract
@@ -751,18 +770,19 @@ fn update_did_document_with_event_verification_many_controller_and_services() {
 
     let owner = "owner".into_addr();
     let owner2 = "owner2".into_addr();
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "event_verification_did");
-    let service1_did = format!("{}{}", DID_PREFIX, "service_did");
+    let service1_did = format!("{}#{}", did, "service_did");
     let mut original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
+            a_type: "ServiceType".into(),
             id: Did::new(&service1_did),
-            service_endpoint: "http://example.com".to_string(),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -774,11 +794,11 @@ fn update_did_document_with_event_verification_many_controller_and_services() {
     // Update the DID Document
     original_did_doc.controller.push(owner2.to_string().into());
     
-    let service2_did = format!("{}{}", DID_PREFIX, "service2_did");
+    let service2_did = format!("{}#{}", did, "service2_did");
     original_did_doc.service.push(Service {
-        a_type: "NewServiceType".to_string(),
+        a_type: "NewServiceType".into(),
         id: Did::new(&service2_did),
-        service_endpoint: "http://new-service.com".to_string(),
+        service_endpoint: "http://new-service.com".into(),
     });
 
     let result = contract
@@ -818,7 +838,7 @@ fn update_did_document_with_event_verification_many_controller_and_services() {
     assert_eq!(res.events[1].attributes[3].key, "new_controllers");
     assert_eq!(
         res.events[1].attributes[3].value,
-         format!("{},{}", owner.to_string(), owner2.to_string())
+         format!("{},{}", owner, owner2)
     );
     assert_eq!(res.events[1].attributes[4].key, "old_services");
     assert_eq!(
@@ -829,7 +849,7 @@ fn update_did_document_with_event_verification_many_controller_and_services() {
     assert_eq!(res.events[1].attributes[5].key, "new_services");
     assert_eq!(
         res.events[1].attributes[5].value,
-        format!("{},{}", service1_did.to_string(), service2_did.to_string())
+        format!("{},{}", service1_did, service2_did)
     );
 
     // Verify the updated DID Document
@@ -846,17 +866,18 @@ fn update_did_document_with_index_verification() {
     let owner = "owner".into_addr();
     let new_controller = "new_controller".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "index_verification_did");
     let mut original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", did, "service1")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -878,9 +899,9 @@ fn update_did_document_with_index_verification() {
     // Update the DID Document: Add a new controller and remove the old one
     original_did_doc.controller = vec![new_controller.to_string().into()];
     original_did_doc.service.push(Service {
-        a_type: "NewServiceType".to_string(),
-        id: Did::new(&format!("{}{}", DID_PREFIX, "service2")),
-        service_endpoint: "http://new-service.com".to_string(),
+        a_type: "NewServiceType".into(),
+        id: Did::new(&format!("{}#{}", did, "service2")),
+        service_endpoint: "http://new-service.com".into(),
     });
 
     let result = contract
@@ -923,17 +944,18 @@ fn update_did_document_with_invalid_service_id_format() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "invalid_service_id_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", did, "service1")),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -945,9 +967,9 @@ fn update_did_document_with_invalid_service_id_format() {
     // Attempt to update the DID Document with an invalid service ID format
     let mut updated_did_doc = original_did_doc.clone();
     updated_did_doc.service.push(Service {
-        a_type: "InvalidServiceType".to_string(),
+        a_type: "InvalidServiceType".into(),
         id: Did::new("invalid_service_id"), // Invalid service ID format
-        service_endpoint: "http://invalid-service.com".to_string(),
+        service_endpoint: "http://invalid-service.com".into(),
     });
 
     let result = contract
@@ -955,7 +977,7 @@ fn update_did_document_with_invalid_service_id_format() {
         .call(&owner);
     assert!(result.is_err(), "Expected Err, but got an Ok");
     assert_eq!(
-        "Service id format error: Did format error: invalid_service_id",
+        "Service id format error: Did format error: invalid_service_id (missing did scheme)",
         result.err().unwrap().to_string()
     );
 
@@ -971,13 +993,14 @@ fn update_did_document_add_service() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "add_service_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![], // Initially no services
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -989,9 +1012,9 @@ fn update_did_document_add_service() {
     // Update the DID Document to add a service
     let mut updated_did_doc = original_did_doc.clone();
     updated_did_doc.service.push(Service {
-        a_type: "NewServiceType".to_string(),
-        id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-        service_endpoint: "http://new-service.com".to_string(),
+        a_type: "NewServiceType".into(),
+        id: Did::new(&format!("{}#{}", did, "service1")),
+        service_endpoint: "http://new-service.com".into(),
     });
 
     let result = contract
@@ -1014,17 +1037,17 @@ fn update_did_document_add_service() {
     );
     assert_eq!(
         current_did_doc.service[0].id,
-        Did::new(&format!("{}{}", DID_PREFIX, "service1")),
+        Did::new(&format!("{}#{}", did, "service1")),
         "Service ID does not match"
     );
     assert_eq!(
         current_did_doc.service[0].a_type,
-        "NewServiceType",
+        "NewServiceType".into(),
         "Service type does not match"
     );
     assert_eq!(
         current_did_doc.service[0].service_endpoint,
-        "http://new-service.com",
+        "http://new-service.com".into(),
         "Service endpoint does not match"
     );
 }
@@ -1036,17 +1059,18 @@ fn update_did_document_remove_service() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "remove_service_did");
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
-            id: Did::new(&format!("{}{}", DID_PREFIX, "service1")),
-            service_endpoint: "http://example.com".to_string(),
+            a_type: "ServiceType".into(),
+            id: Did::new(&format!("{}#{}", did, "service1")),
+            service_endpoint: "http://example.com".into(),
         }], // Initially has one service
+        ..Default::default()
     };
 
     // Create the original DID Document
@@ -1086,20 +1110,21 @@ fn update_document_with_duplicated_service() {
 
     let owner = "owner".into_addr();
 
-    let contract = code_id.instantiate().call(&owner).unwrap();
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
 
     let did = format!("{}{}", DID_PREFIX, "duplicated_service_did");
-    let service_id = format!("{}{}", DID_PREFIX, "service1");
+    let service_id = format!("{}#{}", did, "service1");
 
     // Create the original DID Document
     let original_did_doc = DidDocument {
         id: Did::new(&did),
         controller: vec![owner.to_string().into()],
         service: vec![Service {
-            a_type: "ServiceType".to_string(),
+            a_type: "ServiceType".into(),
             id: Did::new(&service_id),
-            service_endpoint: "http://example.com".to_string(),
+            service_endpoint: "http://example.com".into(),
         }],
+        ..Default::default()
     };
 
     let result = contract
@@ -1110,9 +1135,9 @@ fn update_document_with_duplicated_service() {
     // Attempt to update the DID Document with duplicated services
     let mut updated_did_doc = original_did_doc.clone();
     updated_did_doc.service.push(Service {
-        a_type: "ServiceType".to_string(),
+        a_type: "ServiceType".into(),
         id: Did::new(&service_id), // Duplicate service ID
-        service_endpoint: "http://example.com".to_string(),
+        service_endpoint: "http://example.com".into(),
     });
 
     let result = contract
@@ -1132,4 +1157,118 @@ fn update_document_with_duplicated_service() {
         original_did_doc, current_did_doc,
         "DID Document was updated incorrectly"
     );
-}
\ No newline at end of file
+}
+
+#[test]
+fn update_document_with_duplicated_verification_method() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "duplicated_vm_did");
+    let method = VerificationMethod {
+        id: Did::new(&format!("{}#{}", did, "key1")),
+        a_type: "Ed25519VerificationKey2020".to_string(),
+        controller: Did::new(&did),
+        public_key_multibase: Some("z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH".to_string()),
+        public_key_jwk: None,
+        blockchain_account_id: None,
+    };
+
+    // Create the original DID Document
+    let original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        verification_method: vec![method.clone()],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // Attempt to update the DID Document with duplicated verification methods
+    let mut updated_did_doc = original_did_doc.clone();
+    updated_did_doc.verification_method.push(method.clone());
+
+    let result = contract
+        .update_did_document(updated_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+
+    // Verify the error message
+    assert_eq!(
+        format!("Duplicated verification method: {}", method.id),
+        result.err().unwrap().to_string()
+    );
+
+    // Verify that the DID Document was not updated
+    let current_did_doc = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(
+        original_did_doc, current_did_doc,
+        "DID Document was updated incorrectly"
+    );
+}
+
+#[test]
+fn update_did_document_removing_referenced_verification_method() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "referenced_vm_did");
+    let method = VerificationMethod {
+        id: Did::new(&format!("{}#{}", did, "key1")),
+        a_type: "Ed25519VerificationKey2020".to_string(),
+        controller: Did::new(&did),
+        public_key_multibase: Some("z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH".to_string()),
+        public_key_jwk: None,
+        blockchain_account_id: None,
+    };
+
+    let original_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        verification_method: vec![method.clone()],
+        authentication: vec![RelationshipMethod::Reference(method.id.clone())],
+        ..Default::default()
+    };
+
+    let result = contract
+        .create_did_document(original_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // Remove the verification method but keep the reference to it
+    let mut updated_did_doc = original_did_doc.clone();
+    updated_did_doc.verification_method.clear();
+
+    let result = contract
+        .update_did_document(updated_did_doc.clone())
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+
+    assert_eq!(
+        format!(
+            "Verification relationship references unknown verification method: {}",
+            method.id
+        ),
+        result.err().unwrap().to_string()
+    );
+
+    // Verify that the DID Document was not updated
+    let current_did_doc = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(
+        original_did_doc, current_did_doc,
+        "DID Document was updated incorrectly"
+    );
+}
@@ -0,0 +1,260 @@
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+use crate::{
+    contract::sv::mt::{CodeId, DidContractProxy},
+    state::{Did, DidDocument, Service},
+    test::{DID_METHOD, DID_PREFIX},
+};
+
+#[test]
+fn update_service_endpoint() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "update_service_did");
+    let service_id = format!("{}#{}", did, "service1");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![Service {
+            id: Did::new(&service_id),
+            a_type: "Service".into(),
+            service_endpoint: "https://example.com".into(),
+        }],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .update_service(
+            Did::new(&did),
+            Did::new(&service_id),
+            None,
+            Some("https://new.example.com".into()),
+        )
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let did_document = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(
+        vec![Service {
+            id: Did::new(&service_id),
+            a_type: "Service".into(),
+            service_endpoint: "https://new.example.com".into(),
+        }],
+        did_document.service
+    );
+}
+
+#[test]
+fn update_service_type_and_endpoint() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "replace_service_did");
+    let service_id = format!("{}#{}", did, "service1");
+    let other_service = Service {
+        id: Did::new(&format!("{}#{}", did, "service2")),
+        a_type: "Other".into(),
+        service_endpoint: "https://other.example.com".into(),
+    };
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![
+            Service {
+                id: Did::new(&service_id),
+                a_type: "Service".into(),
+                service_endpoint: "https://example.com".into(),
+            },
+            other_service.clone(),
+        ],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .update_service(
+            Did::new(&did),
+            Did::new(&service_id),
+            Some("LinkedDomains".into()),
+            Some("https://new.example.com".into()),
+        )
+        .call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    // The service keeps its position, the others are untouched
+    let did_document = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(
+        vec![
+            Service {
+                id: Did::new(&service_id),
+                a_type: "LinkedDomains".into(),
+                service_endpoint: "https://new.example.com".into(),
+            },
+            other_service,
+        ],
+        did_document.service
+    );
+}
+
+#[test]
+fn update_service_with_event_verification() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "event_service_did");
+    let service_id = format!("{}#{}", did, "service1");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![Service {
+            id: Did::new(&service_id),
+            a_type: "Service".into(),
+            service_endpoint: "https://example.com".into(),
+        }],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let res = contract
+        .update_service(
+            Did::new(&did),
+            Did::new(&service_id),
+            Some("LinkedDomains".into()),
+            None,
+        )
+        .call(&owner)
+        .expect("Failed to get result");
+
+    assert_eq!(res.events.len(), 2);
+    assert_eq!(res.events[1].ty, "wasm-update_service");
+    assert_eq!(res.events[1].attributes.len(), 7);
+    assert_eq!(res.events[1].attributes[1].key, "did");
+    assert_eq!(res.events[1].attributes[1].value, did);
+    assert_eq!(res.events[1].attributes[2].key, "service");
+    assert_eq!(res.events[1].attributes[2].value, service_id);
+    assert_eq!(res.events[1].attributes[3].key, "old_type");
+    assert_eq!(res.events[1].attributes[3].value, "Service");
+    assert_eq!(res.events[1].attributes[4].key, "new_type");
+    assert_eq!(res.events[1].attributes[4].value, "LinkedDomains");
+    assert_eq!(res.events[1].attributes[5].key, "old_service_endpoint");
+    assert_eq!(res.events[1].attributes[5].value, "https://example.com");
+    assert_eq!(res.events[1].attributes[6].key, "new_service_endpoint");
+    assert_eq!(res.events[1].attributes[6].value, "https://example.com");
+}
+
+#[test]
+fn update_non_existing_service() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "no_services_did");
+    let service_id = format!("{}#{}", did, "service1");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .update_service(
+            Did::new(&did),
+            Did::new(&service_id),
+            None,
+            Some("https://example.com".into()),
+        )
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Did document service does not exist: {}", service_id),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn update_service_without_changes() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "unchanged_service_did");
+    let service_id = format!("{}#{}", did, "service1");
+    let result = contract
+        .update_service(Did::new(&did), Did::new(&service_id), None, None)
+        .call(&owner);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Service update changes nothing: {}", service_id),
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn update_service_unauthorized() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let owner = "owner".into_addr();
+    let unauthorized_user = "unauthorized_user".into_addr();
+
+    let contract = code_id.instantiate(DID_METHOD.to_string(), None, None).call(&owner).unwrap();
+
+    let did = format!("{}{}", DID_PREFIX, "unauthorized_update_service_did");
+    let service_id = format!("{}#{}", did, "service1");
+    let new_did_doc = DidDocument {
+        id: Did::new(&did),
+        controller: vec![owner.to_string().into()],
+        service: vec![Service {
+            id: Did::new(&service_id),
+            a_type: "Service".into(),
+            service_endpoint: "https://example.com".into(),
+        }],
+        ..Default::default()
+    };
+    let result = contract.create_did_document(new_did_doc.clone()).call(&owner);
+    assert!(result.is_ok(), "Expected Ok, but got an Err");
+
+    let result = contract
+        .update_service(
+            Did::new(&did),
+            Did::new(&service_id),
+            None,
+            Some("https://evil.example.com".into()),
+        )
+        .call(&unauthorized_user);
+    assert!(result.is_err(), "Expected Err, but got an Ok");
+    assert_eq!(
+        format!("Unauthorized: {}", unauthorized_user),
+        result.err().unwrap().to_string()
+    );
+
+    let did_document = contract.get_did_document(Did::new(&did)).unwrap();
+    assert_eq!(new_did_doc, did_document);
+}